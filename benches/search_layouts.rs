@@ -0,0 +1,60 @@
+//! Compares `binary_search`, `branchless_binary_search` and `EytzingerSet` lookups
+//! on tables of different sizes, from one that fits in the L1 cache to one that
+//! does not fit in any. Run it with `cargo bench --bench search_layouts`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use algo_front_end_masters::search::{binary_search, branchless_binary_search, EytzingerSet};
+
+const QUERIES: usize = 1 << 20;
+
+/// A xorshift generator, so the queries are the same on every run.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+fn time<F: FnMut(u32) -> bool>(queries: &[u32], mut lookup: F) -> Duration {
+    let start = Instant::now();
+    let mut found = 0;
+    for &query in queries {
+        found += lookup(black_box(query)) as usize;
+    }
+    black_box(found);
+    start.elapsed()
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>16} {:>16} {:>16}",
+        "elements", "binary_search", "branchless", "eytzinger"
+    );
+    for size in [1 << 10, 1 << 14, 1 << 18, 1 << 22] {
+        // Only the even numbers, so half the queries are misses.
+        let sorted: Vec<u32> = (0..size).map(|i| i * 2).collect();
+        let set = EytzingerSet::from_sorted(sorted.clone());
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        let queries: Vec<u32> = (0..QUERIES)
+            .map(|_| (rng.next() % (size as u64 * 2)) as u32)
+            .collect();
+
+        let binary = time(&queries, |q| binary_search(&sorted, &q));
+        let branchless = time(&queries, |q| branchless_binary_search(&sorted, &q).is_ok());
+        let eytzinger = time(&queries, |q| set.contains(&q));
+        let per_query = |d: Duration| format!("{:.1} ns", d.as_nanos() as f64 / QUERIES as f64);
+        println!(
+            "{:>10} {:>16} {:>16} {:>16}",
+            size,
+            per_query(binary),
+            per_query(branchless),
+            per_query(eytzinger)
+        );
+    }
+}
@@ -1,8 +1,12 @@
 use std::{
-    ops::{Deref, DerefMut},
-    ptr::{self},
+    fmt,
+    iter::FusedIterator,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut, RangeBounds},
+    ptr::{self, NonNull},
+    slice,
 };
-use super::RawVec;
+use super::{check_range, RawVec};
 
 pub struct ArrayList<T> {
     buf: RawVec<T>,
@@ -25,6 +29,36 @@ impl<T> ArrayList<T> {
         }
     }
 
+    pub fn with_capacity(cap: usize) -> Self {
+        ArrayList {
+            buf: RawVec::with_capacity(cap),
+            len: 0,
+        }
+    }
+
+    /// How many elements fit before the buffer has to grow.
+    pub fn capacity(&self) -> usize {
+        self.cap()
+    }
+
+    /// Makes room for at least `additional` more elements. When it grows the
+    /// capacity is at least doubled, so pushing after a reserve stays amortized
+    /// **O (1)**.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.len.checked_add(additional).expect("capacity overflow");
+        if needed > self.cap() {
+            self.buf.resize(needed.max(2 * self.cap()));
+        }
+    }
+
+    /// Drops the capacity that is not used.
+    pub fn shrink_to_fit(&mut self) {
+        // Zero sized types are never allocated.
+        if self.cap() > self.len && mem::size_of::<T>() != 0 {
+            self.buf.resize(self.len);
+        }
+    }
+
     pub fn push(&mut self, elem: T) {
         if self.len == self.cap() {
             self.buf.grow()
@@ -70,6 +104,131 @@ impl<T> ArrayList<T> {
         }
     }
 
+    /// Removes the element at `idx` and puts the last element in its place, which
+    /// is **O (1)** but does not keep the order.
+    pub fn swap_remove(&mut self, idx: usize) -> T {
+        assert!(idx < self.len, "index out of bounds");
+        unsafe {
+            self.len -= 1;
+            let result = ptr::read(self.ptr().add(idx));
+            ptr::copy(self.ptr().add(self.len), self.ptr().add(idx), 1);
+            result
+        }
+    }
+
+    /// Drops every element from `len` on, keeping the capacity.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let tail = ptr::slice_from_raw_parts_mut(unsafe { self.ptr().add(len) }, self.len - len);
+        // Shorten first, so if a drop panics the list does not see the dropped
+        // elements.
+        self.len = len;
+        unsafe { ptr::drop_in_place(tail) };
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keeps only the elements for which `f` returns true, in order.
+    ///
+    /// Each kept element is moved back at most once. If `f` or a drop panics,
+    /// the elements not visited yet are kept.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut gap = Gap::new(self);
+        while gap.read < gap.original_len {
+            unsafe {
+                let cur = gap.list.ptr().add(gap.read);
+                if f(&*cur) {
+                    if gap.read != gap.write {
+                        ptr::copy_nonoverlapping(cur, gap.list.ptr().add(gap.write), 1);
+                    }
+                    gap.write += 1;
+                    gap.read += 1;
+                } else {
+                    // Moved past it before dropping, in case the drop panics.
+                    gap.read += 1;
+                    ptr::drop_in_place(cur);
+                }
+            }
+        }
+    }
+
+    /// Removes the consecutive elements for which `same_bucket(element, previous)`
+    /// returns true, keeping the first of each run.
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        if self.len <= 1 {
+            return;
+        }
+        let mut gap = Gap::new(self);
+        // The first element is always kept.
+        gap.read = 1;
+        gap.write = 1;
+        while gap.read < gap.original_len {
+            unsafe {
+                let ptr = gap.list.ptr();
+                let cur = ptr.add(gap.read);
+                if same_bucket(&mut *cur, &mut *ptr.add(gap.write - 1)) {
+                    gap.read += 1;
+                    ptr::drop_in_place(cur);
+                } else {
+                    if gap.read != gap.write {
+                        ptr::copy_nonoverlapping(cur, ptr.add(gap.write), 1);
+                    }
+                    gap.write += 1;
+                    gap.read += 1;
+                }
+            }
+        }
+    }
+
+    /// Splits the list in two at `at`, returning the elements from `at` on.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "index out of bounds");
+        let mut other = ArrayList::with_capacity(self.len - at);
+        unsafe {
+            ptr::copy_nonoverlapping(self.ptr().add(at), other.ptr(), self.len - at);
+        }
+        other.len = self.len - at;
+        self.len = at;
+        other
+    }
+
+    /// Moves every element of `other` to the end of this list, leaving it empty.
+    pub fn append(&mut self, other: &mut Self) {
+        self.reserve(other.len);
+        unsafe {
+            ptr::copy_nonoverlapping(other.ptr(), self.ptr().add(self.len), other.len);
+        }
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    /// Removes the elements in `range`, returning them as an iterator. When the
+    /// iterator is dropped the elements after the range are moved back to close
+    /// the gap, even the ones it did not yield are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or starts after its end.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        let range = check_range(range, self.len);
+        let tail_len = self.len - range.end;
+        // Until the drain is dropped the list only owns the head, so forgetting
+        // the drain leaks the rest instead of dropping it twice.
+        self.len = range.start;
+        Drain {
+            ptr: NonNull::new(self.ptr()).unwrap(),
+            front: range.start,
+            back: range.end,
+            tail_start: range.end,
+            tail_len,
+            list: self,
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.len
     }
@@ -79,12 +238,318 @@ impl<T> ArrayList<T> {
     }
 }
 
+impl<T: PartialEq> ArrayList<T> {
+    /// Removes consecutive repeated elements.
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b)
+    }
+}
+
+impl<T: Clone> ArrayList<T> {
+    pub fn extend_from_slice(&mut self, other: &[T]) {
+        self.reserve(other.len());
+        // Pushed one by one, so if a clone panics the list stays valid.
+        for elem in other {
+            self.push(elem.clone());
+        }
+    }
+
+    /// Truncates the list, or fills it with clones of `value` up to `new_len`.
+    pub fn resize(&mut self, new_len: usize, value: T) {
+        if new_len <= self.len {
+            self.truncate(new_len);
+            return;
+        }
+        self.reserve(new_len - self.len);
+        while self.len + 1 < new_len {
+            self.push(value.clone());
+        }
+        self.push(value);
+    }
+}
+
+/// Keeps a list valid while elements are removed from its middle: `read` is the
+/// next element to visit and `write` where the next kept one goes. On drop, even
+/// when unwinding, the elements not visited are moved back to close the gap.
+struct Gap<'a, T> {
+    list: &'a mut ArrayList<T>,
+    read: usize,
+    write: usize,
+    original_len: usize,
+}
+
+impl<'a, T> Gap<'a, T> {
+    fn new(list: &'a mut ArrayList<T>) -> Self {
+        let original_len = list.len;
+        // Until the gap is closed the list owns nothing, so a panic can only leak.
+        list.len = 0;
+        Gap {
+            list,
+            read: 0,
+            write: 0,
+            original_len,
+        }
+    }
+}
+
+impl<T> Drop for Gap<'_, T> {
+    fn drop(&mut self) {
+        let rest = self.original_len - self.read;
+        unsafe {
+            let ptr = self.list.ptr();
+            ptr::copy(ptr.add(self.read), ptr.add(self.write), rest);
+        }
+        self.list.len = self.write + rest;
+    }
+}
+
+impl<T> Default for ArrayList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> Drop for ArrayList<T> {
     fn drop(&mut self) {
-        while self.pop().is_some() {}
+        self.clear();
+    }
+}
+
+impl<T> FromIterator<T> for ArrayList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = ArrayList::new();
+        list.extend(iter);
+        list
     }
 }
 
+impl<T> Extend<T> for ArrayList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+impl<T: Clone> Clone for ArrayList<T> {
+    fn clone(&self) -> Self {
+        let mut list = ArrayList::with_capacity(self.len);
+        list.extend_from_slice(self);
+        list
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArrayList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArrayList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq> Eq for ArrayList<T> {}
+
+impl<T> IntoIterator for ArrayList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Moves the elements out of the list, reusing its buffer.
+    fn into_iter(self) -> IntoIter<T> {
+        let list = ManuallyDrop::new(self);
+        IntoIter {
+            // The list is not dropped, so the buffer is moved out of it.
+            buf: unsafe { ptr::read(&list.buf) },
+            front: 0,
+            back: list.len,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArrayList<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArrayList<T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// An iterator that moves the elements out of an [`ArrayList`], from both ends.
+///
+/// It owns the buffer of the list, and the elements it did not yield are
+/// dropped with it.
+pub struct IntoIter<T> {
+    buf: RawVec<T>,
+    // The elements in [front, back) have not been yielded yet.
+    front: usize,
+    back: usize,
+}
+
+impl<T> IntoIter<T> {
+    /// The elements that have not been yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.buf.ptr.as_ptr().add(self.front), self.len()) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.buf.ptr.as_ptr().add(self.front), self.len()) }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        unsafe { Some(ptr::read(self.buf.ptr.as_ptr().add(self.front - 1))) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        unsafe { Some(ptr::read(self.buf.ptr.as_ptr().add(self.back))) }
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        let rest = ptr::slice_from_raw_parts_mut(
+            unsafe { self.buf.ptr.as_ptr().add(self.front) },
+            self.back - self.front,
+        );
+        self.front = self.back;
+        // If a drop panics the others are still dropped, and so is the buffer.
+        unsafe { ptr::drop_in_place(rest) };
+    }
+}
+
+unsafe impl<T: Send> Send for IntoIter<T> {}
+unsafe impl<T: Sync> Sync for IntoIter<T> {}
+
+/// An iterator that removes a range of an [`ArrayList`], returned by
+/// [`ArrayList::drain`].
+pub struct Drain<'a, T> {
+    list: &'a mut ArrayList<T>,
+    // Kept apart from 'list' so the elements can be read while it is borrowed.
+    ptr: NonNull<T>,
+    // The elements in [front, back) have not been yielded yet.
+    front: usize,
+    back: usize,
+    // Where the elements after the range start, and how many there are.
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<T> Drain<'_, T> {
+    /// The elements that have not been yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr().add(self.front), self.back - self.front) }
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        unsafe { Some(ptr::read(self.ptr.as_ptr().add(self.front - 1))) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        unsafe { Some(ptr::read(self.ptr.as_ptr().add(self.back))) }
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+impl<T> FusedIterator for Drain<'_, T> {}
+
+impl<T: fmt::Debug> fmt::Debug for Drain<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        /// Moves the tail back when dropped, so it also happens if dropping the
+        /// elements that were not yielded panics.
+        struct Backshift<'r, 'a, T>(&'r mut Drain<'a, T>);
+
+        impl<T> Drop for Backshift<'_, '_, T> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let start = drain.list.len;
+                unsafe {
+                    let ptr = drain.ptr.as_ptr();
+                    ptr::copy(ptr.add(drain.tail_start), ptr.add(start), drain.tail_len);
+                }
+                drain.list.len = start + drain.tail_len;
+            }
+        }
+
+        let rest = ptr::slice_from_raw_parts_mut(
+            unsafe { self.ptr.as_ptr().add(self.front) },
+            self.back - self.front,
+        );
+        let guard = Backshift(self);
+        unsafe { ptr::drop_in_place(rest) };
+        drop(guard);
+    }
+}
+
+unsafe impl<T: Send> Send for Drain<'_, T> {}
+unsafe impl<T: Sync> Sync for Drain<'_, T> {}
+
 impl<T> Deref for ArrayList<T> {
     type Target = [T];
     fn deref(&self) -> &Self::Target {
@@ -103,8 +568,49 @@ unsafe impl<T: Sync> Sync for ArrayList<T> {}
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+
     use super::ArrayList;
 
+    /// Counts its drops, and panics when dropped if asked to.
+    #[derive(Debug, Clone)]
+    struct Tracked {
+        value: i32,
+        drops: Rc<Cell<usize>>,
+        panic_on_drop: bool,
+    }
+
+    impl Tracked {
+        fn list(
+            values: impl IntoIterator<Item = i32>,
+            drops: &Rc<Cell<usize>>,
+        ) -> ArrayList<Tracked> {
+            values
+                .into_iter()
+                .map(|value| Tracked {
+                    value,
+                    drops: drops.clone(),
+                    panic_on_drop: false,
+                })
+                .collect()
+        }
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+            if self.panic_on_drop {
+                panic!("drop of {}", self.value);
+            }
+        }
+    }
+
+    fn values(list: &ArrayList<Tracked>) -> Vec<i32> {
+        list.iter().map(|t| t.value).collect()
+    }
+
     #[test]
     fn test_ring_buffer() {
         let mut al = ArrayList::new();
@@ -121,4 +627,222 @@ mod tests {
         assert_eq!(al.pop(), Some(30));
         assert!(al.is_empty());
     }
+
+    #[test]
+    fn test_capacity() {
+        let mut al: ArrayList<u64> = ArrayList::with_capacity(10);
+        assert_eq!(al.capacity(), 10);
+        al.extend(0..10);
+        assert_eq!(al.capacity(), 10);
+        al.push(10);
+        assert_eq!(al.capacity(), 20);
+        al.reserve(100);
+        assert!(al.capacity() >= 111);
+        al.truncate(3);
+        al.shrink_to_fit();
+        assert_eq!(al.capacity(), 3);
+        assert_eq!(*al, [0, 1, 2]);
+        al.clear();
+        al.shrink_to_fit();
+        assert_eq!(al.capacity(), 0);
+        assert!(ArrayList::<u8>::with_capacity(0).is_empty());
+
+        // Zero sized types never allocate.
+        let mut units: ArrayList<()> = ArrayList::with_capacity(5);
+        units.resize(1000, ());
+        units.shrink_to_fit();
+        assert_eq!(units.len(), 1000);
+        assert_eq!(units.swap_remove(3), ());
+        assert_eq!(units.split_off(900).len(), 99);
+    }
+
+    #[test]
+    fn test_vec_api() {
+        let mut al: ArrayList<i32> = (0..10).collect();
+        al.retain(|&x| x % 3 != 0);
+        assert_eq!(*al, [1, 2, 4, 5, 7, 8]);
+        assert_eq!(al.swap_remove(1), 2);
+        assert_eq!(*al, [1, 8, 4, 5, 7]);
+        let mut tail = al.split_off(2);
+        assert_eq!(*al, [1, 8]);
+        assert_eq!(*tail, [4, 5, 7]);
+        al.append(&mut tail);
+        assert!(tail.is_empty());
+        assert_eq!(*al, [1, 8, 4, 5, 7]);
+        al.extend_from_slice(&[7, 7, 1]);
+        al.dedup();
+        assert_eq!(*al, [1, 8, 4, 5, 7, 1]);
+        al.resize(8, -1);
+        assert_eq!(*al, [1, 8, 4, 5, 7, 1, -1, -1]);
+        al.resize(2, 0);
+        assert_eq!(*al, [1, 8]);
+
+        let mut words: ArrayList<String> = ["a", "A", "b", "B", "b", "c"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        words.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        assert_eq!(*words, ["a", "b", "c"]);
+
+        let copy = words.clone();
+        assert_eq!(copy, words);
+        words.push("d".to_string());
+        assert_ne!(copy, words);
+        assert_eq!(format!("{copy:?}"), r#"["a", "b", "c"]"#);
+    }
+
+    #[test]
+    fn test_drops() {
+        let drops = Rc::new(Cell::new(0));
+        let mut al = Tracked::list(0..10, &drops);
+        al.retain(|t| t.value < 5);
+        assert_eq!(drops.get(), 5);
+        al.truncate(2);
+        assert_eq!(drops.get(), 8);
+        let cloned = al.clone();
+        al.resize(4, cloned[0].clone());
+        drop(cloned);
+        assert_eq!(drops.get(), 10);
+        assert_eq!(values(&al), [0, 1, 0, 0]);
+        al.dedup_by(|a, b| a.value == b.value);
+        assert_eq!(drops.get(), 11);
+        drop(al.split_off(1));
+        drop(al);
+        assert_eq!(drops.get(), 14);
+    }
+
+    #[test]
+    fn test_panic_safety() {
+        let drops = Rc::new(Cell::new(0));
+        let mut al = Tracked::list(0..10, &drops);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            al.retain(|t| {
+                assert!(t.value != 6, "predicate panicked");
+                t.value % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+        // The visited odd elements are gone, the rest are kept.
+        assert_eq!(values(&al), [0, 2, 4, 6, 7, 8, 9]);
+        assert_eq!(drops.get(), 3);
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            al.dedup_by(|a, _| {
+                assert!(a.value != 8, "predicate panicked");
+                true
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(values(&al), [0, 8, 9]);
+        assert_eq!(drops.get(), 7);
+
+        // A panicking drop in the middle of a truncate still drops the rest.
+        al[1].panic_on_drop = true;
+        assert!(catch_unwind(AssertUnwindSafe(|| al.truncate(0))).is_err());
+        assert!(al.is_empty());
+        assert_eq!(drops.get(), 10);
+
+        let mut al = Tracked::list(0..4, &drops);
+        al[1].panic_on_drop = true;
+        assert!(catch_unwind(AssertUnwindSafe(|| al.retain(|t| t.value >= 2))).is_err());
+        assert_eq!(values(&al), [2, 3]);
+        drop(al);
+        assert_eq!(drops.get(), 14);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let al: ArrayList<i32> = (0..6).collect();
+        let mut iter = al.into_iter();
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.as_slice(), [1, 2, 3, 4]);
+        assert_eq!(iter.rev().collect::<Vec<_>>(), [4, 3, 2, 1]);
+
+        let mut al: ArrayList<i32> = (0..3).collect();
+        for x in &mut al {
+            *x *= 10;
+        }
+        assert_eq!((&al).into_iter().sum::<i32>(), 30);
+
+        let drops = Rc::new(Cell::new(0));
+        let mut iter = Tracked::list(0..5, &drops).into_iter();
+        assert_eq!(iter.next().map(|t| t.value), Some(0));
+        assert_eq!(drops.get(), 1);
+        drop(iter);
+        assert_eq!(drops.get(), 5);
+
+        let units: ArrayList<()> = std::iter::repeat_n((), 10).collect();
+        assert_eq!(units.into_iter().rev().count(), 10);
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut al: ArrayList<i32> = (0..10).collect();
+        assert_eq!(al.drain(2..5).collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(*al, [0, 1, 5, 6, 7, 8, 9]);
+        let mut drain = al.drain(1..=4);
+        assert_eq!(drain.next_back(), Some(7));
+        assert_eq!(drain.next(), Some(1));
+        assert_eq!(drain.as_slice(), [5, 6]);
+        drop(drain);
+        assert_eq!(*al, [0, 8, 9]);
+        assert_eq!(al.drain(..).len(), 3);
+        assert!(al.is_empty());
+        al.extend([1, 2]);
+        assert_eq!(al.drain(2..).count(), 0);
+        assert_eq!(*al, [1, 2]);
+
+        // A forgotten drain loses the range and the tail, but the list is valid.
+        let mut al: ArrayList<i32> = (0..6).collect();
+        std::mem::forget(al.drain(2..4));
+        assert_eq!(*al, [0, 1]);
+        al.push(2);
+        assert_eq!(*al, [0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_drain_out_of_bounds() {
+        let mut al: ArrayList<i32> = (0..3).collect();
+        al.drain(1..4);
+    }
+
+    #[test]
+    fn test_iterators_panic_safety() {
+        let drops = Rc::new(Cell::new(0));
+        let mut al = Tracked::list(0..8, &drops);
+        // The consumer panics halfway.
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            for t in al.drain(2..6) {
+                assert!(t.value != 4, "consumer panicked");
+            }
+        }));
+        assert!(result.is_err());
+        assert_eq!(values(&al), [0, 1, 6, 7]);
+        assert_eq!(drops.get(), 4);
+
+        // Dropping an element that was not yielded panics.
+        al[1].panic_on_drop = true;
+        assert!(catch_unwind(AssertUnwindSafe(|| drop(al.drain(..3)))).is_err());
+        assert_eq!(values(&al), [7]);
+        assert_eq!(drops.get(), 7);
+
+        let mut al = Tracked::list(0..4, &drops);
+        al[2].panic_on_drop = true;
+        let mut iter = al.into_iter();
+        iter.next();
+        assert!(catch_unwind(AssertUnwindSafe(|| drop(iter))).is_err());
+        assert_eq!(drops.get(), 11);
+
+        let al = Tracked::list(0..4, &drops);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            for t in al.into_iter().rev() {
+                assert!(t.value != 1, "consumer panicked");
+            }
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 15);
+    }
 }
@@ -0,0 +1,263 @@
+use std::fmt;
+use std::ops::Index;
+
+use super::ring_buffer::{IntoIter, Iter};
+use super::RingBuffer;
+
+/// What a [`BoundedRingBuffer`] does with a push when it is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FullPolicy {
+    /// The oldest element is removed to make room, and returned.
+    OverwriteOldest,
+    /// The new element is not stored, and is given back.
+    Reject,
+}
+
+/// A ring buffer that never grows, for keeping the last N log lines or
+/// metric samples.
+///
+/// It is a [`RingBuffer`] created with the exact capacity, which is only
+/// pushed to when there is room. With [`FullPolicy::OverwriteOldest`] a push
+/// to a full buffer writes where the oldest element was, so with a capacity of
+/// 10 the 20th element ends up in the slot of the 10th.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::array::{BoundedRingBuffer, FullPolicy};
+/// let mut window = BoundedRingBuffer::new(3, FullPolicy::OverwriteOldest);
+/// for sample in [10, 20, 30] {
+///     assert_eq!(window.push(sample), Ok(None));
+/// }
+/// assert_eq!(window.push(40), Ok(Some(10)));
+/// assert_eq!(window.iter().sum::<i32>(), 90);
+///
+/// let mut queue = BoundedRingBuffer::new(1, FullPolicy::Reject);
+/// assert_eq!(queue.push("first"), Ok(None));
+/// assert_eq!(queue.push("second"), Err("second"));
+/// ```
+pub struct BoundedRingBuffer<T> {
+    ring: RingBuffer<T>,
+    capacity: usize,
+    policy: FullPolicy,
+}
+
+impl<T> BoundedRingBuffer<T> {
+    /// Allocates room for `capacity` elements up front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize, policy: FullPolicy) -> Self {
+        assert!(capacity > 0, "capacity must be bigger than 0");
+        BoundedRingBuffer {
+            ring: RingBuffer::with_capacity(capacity),
+            capacity,
+            policy,
+        }
+    }
+
+    /// Adds an element after the newest one.
+    ///
+    /// Returns `Ok(None)` if there was room, `Ok(Some(oldest))` if the oldest
+    /// element was evicted to make room, or `Err(elem)` if the buffer is full
+    /// and rejects new elements.
+    pub fn push(&mut self, elem: T) -> Result<Option<T>, T> {
+        if !self.is_full() {
+            self.ring.push_back(elem);
+            return Ok(None);
+        }
+        match self.policy {
+            FullPolicy::OverwriteOldest => {
+                let oldest = self.ring.pop_front();
+                self.ring.push_back(elem);
+                Ok(oldest)
+            }
+            FullPolicy::Reject => Err(elem),
+        }
+    }
+
+    /// Removes the oldest element.
+    pub fn pop(&mut self) -> Option<T> {
+        self.ring.pop_front()
+    }
+
+    /// The oldest element.
+    pub fn oldest(&self) -> Option<&T> {
+        self.ring.front()
+    }
+
+    /// The newest element.
+    pub fn newest(&self) -> Option<&T> {
+        self.ring.back()
+    }
+
+    /// The element `idx`, counting from the oldest.
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        self.ring.get(idx)
+    }
+
+    /// The elements from the oldest to the newest.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.ring.iter()
+    }
+
+    /// The elements from the oldest to the newest, as two slices.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.ring.as_slices()
+    }
+
+    pub fn clear(&mut self) {
+        self.ring.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.ring.len() == self.capacity
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn policy(&self) -> FullPolicy {
+        self.policy
+    }
+}
+
+impl<T> Index<usize> for BoundedRingBuffer<T> {
+    type Output = T;
+    fn index(&self, idx: usize) -> &T {
+        &self.ring[idx]
+    }
+}
+
+impl<T: Clone> Clone for BoundedRingBuffer<T> {
+    fn clone(&self) -> Self {
+        let mut clone = BoundedRingBuffer::new(self.capacity, self.policy);
+        clone.ring.extend(self.iter().cloned());
+        clone
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for BoundedRingBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> IntoIterator for BoundedRingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        self.ring.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BoundedRingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    use super::{BoundedRingBuffer, FullPolicy};
+
+    #[test]
+    fn test_overwrite_oldest() {
+        let mut log = BoundedRingBuffer::new(10, FullPolicy::OverwriteOldest);
+        for i in 1..=10 {
+            assert_eq!(log.push(i), Ok(None));
+        }
+        assert!(log.is_full());
+        // The 20th element takes the slot of the 10th.
+        let evicted: Vec<_> = (11..=20).map(|i| log.push(i).unwrap().unwrap()).collect();
+        assert_eq!(evicted, (1..=10).collect::<Vec<_>>());
+        assert_eq!(
+            log.as_slices(),
+            (&[11, 12, 13, 14, 15, 16, 17, 18, 19, 20][..], &[][..])
+        );
+        assert_eq!(log.len(), 10);
+        assert_eq!(log.capacity(), 10);
+        assert_eq!((log.oldest(), log.newest()), (Some(&11), Some(&20)));
+        assert_eq!(log.push(21), Ok(Some(11)));
+        assert_eq!(log.as_slices().1, [21]);
+        assert_eq!(log[9], 21);
+        assert_eq!(log.pop(), Some(12));
+        assert_eq!(log.push(22), Ok(None));
+        assert_eq!(
+            format!("{:?}", log.clone()),
+            "[13, 14, 15, 16, 17, 18, 19, 20, 21, 22]"
+        );
+    }
+
+    #[test]
+    fn test_reject() {
+        let mut queue = BoundedRingBuffer::new(2, FullPolicy::Reject);
+        assert_eq!(queue.push('a'), Ok(None));
+        assert_eq!(queue.push('b'), Ok(None));
+        assert_eq!(queue.push('c'), Err('c'));
+        assert_eq!(queue.pop(), Some('a'));
+        assert_eq!(queue.push('c'), Ok(None));
+        assert_eq!(queue.into_iter().collect::<String>(), "bc");
+    }
+
+    #[test]
+    fn test_against_vec_deque() {
+        // A xorshift generator for the random operations.
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for capacity in 1..8 {
+            for policy in [FullPolicy::OverwriteOldest, FullPolicy::Reject] {
+                let mut window = BoundedRingBuffer::new(capacity, policy);
+                let mut expected = VecDeque::new();
+                for step in 0..200 {
+                    // Rc so a leak or a double drop would show up under Miri.
+                    let value = Rc::new(step);
+                    if next() % 3 == 0 {
+                        assert_eq!(window.pop(), expected.pop_front());
+                        continue;
+                    }
+                    let pushed = window.push(value.clone());
+                    if expected.len() < capacity {
+                        expected.push_back(value);
+                        assert_eq!(pushed, Ok(None));
+                    } else if policy == FullPolicy::Reject {
+                        assert_eq!(pushed, Err(value));
+                    } else {
+                        assert_eq!(pushed, Ok(expected.pop_front()));
+                        expected.push_back(value);
+                    }
+                    assert!(window.iter().eq(expected.iter()));
+                    assert!(window.len() <= capacity);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be bigger than 0")]
+    fn test_zero_capacity() {
+        BoundedRingBuffer::<u8>::new(0, FullPolicy::Reject);
+    }
+}
@@ -1,10 +1,14 @@
 use std::alloc::Layout;
+use std::ops::{Bound, Range, RangeBounds};
 use std::ptr::NonNull;
 use std::{alloc, mem};
 
-mod array_list;
-mod ring_buffer;
+pub mod array_list;
+mod bounded_ring_buffer;
+pub mod ring_buffer;
+pub mod spsc;
 pub use array_list::ArrayList;
+pub use bounded_ring_buffer::{BoundedRingBuffer, FullPolicy};
 pub use ring_buffer::RingBuffer;
 
 struct RawVec<T> {
@@ -29,25 +33,36 @@ impl<T> RawVec<T> {
     }
 
     fn with_capacity(cap: usize) -> Self {
-        let cap = if mem::size_of::<T>() == 0 {
-            usize::MAX
-        } else {
-            cap
-        };
-        let layout = Layout::array::<T>(cap).unwrap();
-        assert!(layout.size() <= isize::MAX as usize, "Allocation too large");
-        let ptr = unsafe { alloc::alloc(layout) };
-        let ptr = match NonNull::new(ptr as *mut T) {
-            Some(p) => p,
-            None => alloc::handle_alloc_error(layout),
-        };
-        RawVec { ptr, cap }
+        let mut buf = RawVec::new();
+        // Zero sized types already have every capacity, and an allocation of 0
+        // bytes is not allowed.
+        if cap > buf.cap {
+            buf.resize(cap);
+        }
+        buf
     }
 
     fn grow(&mut self) {
         assert!(mem::size_of::<T>() != 0, "capacity overflow");
         let new_cap = if self.cap == 0 { 1 } else { 2 * self.cap };
-        let new_layout = Layout::array::<T>(new_cap).unwrap();
+        self.resize(new_cap);
+    }
+
+    /// Reallocates the buffer to hold exactly `new_cap` elements, moving the
+    /// first `min(cap, new_cap)` slots. A capacity of 0 frees it.
+    fn resize(&mut self, new_cap: usize) {
+        assert!(mem::size_of::<T>() != 0, "capacity overflow");
+        if new_cap == self.cap {
+            return;
+        }
+        if new_cap == 0 {
+            let old_layout = Layout::array::<T>(self.cap).unwrap();
+            unsafe { alloc::dealloc(self.ptr.as_ptr() as *mut u8, old_layout) };
+            self.ptr = NonNull::dangling();
+            self.cap = 0;
+            return;
+        }
+        let new_layout = Layout::array::<T>(new_cap).expect("capacity overflow");
         assert!(
             new_layout.size() <= isize::MAX as usize,
             "Allocation too large"
@@ -78,3 +93,21 @@ impl<T> Drop for RawVec<T> {
         }
     }
 }
+
+/// Turns any range of indices into a `Range` checked against `len`, panicking
+/// like slice indexing does.
+fn check_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).expect("range start overflow"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).expect("range end overflow"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "range starts at {start} but ends at {end}");
+    assert!(end <= len, "range end {end} out of bounds for length {len}");
+    start..end
+}
@@ -1,23 +1,38 @@
-use std::ptr;
-use super::RawVec;
+use super::{check_range, RawVec};
+use std::fmt;
+use std::iter::FusedIterator;
+use std::mem::{self, MaybeUninit};
+use std::ops::{Index, IndexMut, RangeBounds};
+use std::{ptr, slice};
 
+/// A growable double ended queue stored in a single buffer.
+///
+/// The element `i` is at `(head + i) % cap`, so the elements can wrap around
+/// the end of the buffer and both ends take **O (1)** to push or pop.
 pub struct RingBuffer<T> {
     buf: RawVec<T>,
     head: usize,
     len: usize,
 }
 
-
 unsafe impl<T: Send> Send for RingBuffer<T> {}
 unsafe impl<T: Sync> Sync for RingBuffer<T> {}
 
 impl<T> RingBuffer<T> {
     pub fn new() -> Self {
-        RingBuffer { buf: RawVec::new(), head: 0, len: 0 }
+        RingBuffer {
+            buf: RawVec::new(),
+            head: 0,
+            len: 0,
+        }
     }
 
     pub fn with_capacity(cap: usize) -> Self {
-        RingBuffer { buf: RawVec::with_capacity(cap), head: 0, len: 0 }
+        RingBuffer {
+            buf: RawVec::with_capacity(cap),
+            head: 0,
+            len: 0,
+        }
     }
 
     fn is_full(&self) -> bool {
@@ -40,41 +55,585 @@ impl<T> RingBuffer<T> {
         self.buf.ptr.as_ptr()
     }
 
-    pub fn push_back(&mut self, elem: T) {
-        if self.is_full() { self.buf.grow() }
+    /// The position in the buffer that is `offset` slots after `idx`, wrapping
+    /// around its end. It never overflows, even with the `usize::MAX` capacity
+    /// of zero sized types.
+    fn wrap_add(&self, idx: usize, offset: usize) -> usize {
+        debug_assert!(idx < self.cap() && offset <= self.cap());
+        if idx >= self.cap() - offset {
+            idx - (self.cap() - offset)
+        } else {
+            idx + offset
+        }
+    }
+
+    /// The position in the buffer that is `offset` slots before `idx`.
+    fn wrap_sub(&self, idx: usize, offset: usize) -> usize {
+        debug_assert!(idx < self.cap() && offset <= self.cap());
+        if idx >= offset {
+            idx - offset
+        } else {
+            idx + (self.cap() - offset)
+        }
+    }
+
+    /// Doubles the capacity, moving the elements that wrapped around the end of
+    /// the old buffer so they are in order in the new one.
+    fn grow(&mut self) {
+        let old_cap = self.cap();
+        self.buf.grow();
+        let new_cap = self.cap();
+        if self.head <= old_cap - self.len {
+            // The elements did not wrap.
+            return;
+        }
+        let head_len = old_cap - self.head;
+        let tail_len = self.len - head_len;
         unsafe {
-            ptr::write(self.ptr().add(self.len % self.cap()), elem)
+            if tail_len <= head_len {
+                // [o o T T T . . . . .] -> [o o . . . . T T T o o]
+                // The front part fits after the old end, as the capacity at least doubled.
+                ptr::copy_nonoverlapping(self.ptr(), self.ptr().add(old_cap), tail_len);
+            } else {
+                // [H H H o . . . . . .] -> [H H H o . . . . . . o]
+                let new_head = new_cap - head_len;
+                ptr::copy_nonoverlapping(
+                    self.ptr().add(self.head),
+                    self.ptr().add(new_head),
+                    head_len,
+                );
+                self.head = new_head;
+            }
         }
+    }
+
+    /// The position in the buffer of the element `i`.
+    fn slot(&self, i: usize) -> *mut T {
+        unsafe { self.ptr().add(self.wrap_add(self.head, i)) }
+    }
+
+    /// How many elements fit before the buffer has to grow.
+    pub fn capacity(&self) -> usize {
+        self.cap()
+    }
+
+    pub fn push_back(&mut self, elem: T) {
+        if self.is_full() {
+            self.grow()
+        }
+        unsafe { ptr::write(self.slot(self.len), elem) }
         self.len += 1;
     }
 
     pub fn push_front(&mut self, elem: T) {
-        if self.is_full() { self.buf.grow() }
-        if !self.is_empty() {
-            self.head -= 1;
-        }
-        unsafe {
-            ptr::write(self.ptr().add(self.head % self.cap()), elem)
+        if self.is_full() {
+            self.grow()
         }
+        self.head = self.wrap_sub(self.head, 1);
+        unsafe { ptr::write(self.ptr().add(self.head), elem) }
         self.len += 1;
     }
 
+    /// Same as [`pop_front`](RingBuffer::pop_front).
     pub fn pop(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
         if self.len == 0 {
             None
         } else {
-            let elem = unsafe {
-                ptr::read(self.ptr().add(self.head % self.cap()))
-            };
+            let elem = unsafe { ptr::read(self.ptr().add(self.head)) };
             self.len -= 1;
-            self.head += 1;
+            self.head = self.wrap_add(self.head, 1);
             Some(elem)
         }
     }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            unsafe { Some(ptr::read(self.slot(self.len))) }
+        }
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        if idx < self.len {
+            unsafe { Some(&*self.slot(idx)) }
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        if idx < self.len {
+            unsafe { Some(&mut *self.slot(idx)) }
+        } else {
+            None
+        }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len.wrapping_sub(1))
+    }
+
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back) = (front as *mut [T], back as *mut [T]);
+        // Emptied first, so if a drop panics the buffer does not see the dropped
+        // elements.
+        self.len = 0;
+        self.head = 0;
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+
+    /// Returns the elements in order as two slices, the second one holds the
+    /// elements that wrapped around the end of the buffer.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.split();
+        unsafe {
+            (
+                slice::from_raw_parts(self.ptr().add(self.head), front),
+                slice::from_raw_parts(self.ptr(), back),
+            )
+        }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.split();
+        unsafe {
+            (
+                slice::from_raw_parts_mut(self.ptr().add(self.head), front),
+                slice::from_raw_parts_mut(self.ptr(), back),
+            )
+        }
+    }
+
+    /// How many elements are before the end of the buffer and how many wrapped.
+    fn split(&self) -> (usize, usize) {
+        if self.head <= self.cap() - self.len {
+            (self.len, 0)
+        } else {
+            let front = self.cap() - self.head;
+            (front, self.len - front)
+        }
+    }
+
+    /// Moves the elements so they do not wrap, returning them as a single slice.
+    ///
+    /// It takes **O (capacity)** when they wrap, and nothing otherwise.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        // Zero sized types are never moved, any head works for them.
+        if self.split().1 > 0 && mem::size_of::<T>() != 0 {
+            // Rotating the whole buffer puts the head at 0. The free slots are
+            // not initialized, so they are moved as `MaybeUninit`.
+            let buf =
+                unsafe { slice::from_raw_parts_mut(self.ptr() as *mut MaybeUninit<T>, self.cap()) };
+            buf.rotate_left(self.head);
+            self.head = 0;
+        } else if self.split().1 > 0 {
+            self.head = 0;
+        }
+        let (front, _) = self.as_mut_slices();
+        front
+    }
+
+    /// Rotates the elements `n` places to the left, so the element `n` becomes
+    /// the first one. Moves `min(n, len - n)` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is bigger than the length.
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len, "rotate by more than the length");
+        if n <= self.len - n {
+            self.rotate_front_to_back(n);
+        } else {
+            self.rotate_back_to_front(self.len - n);
+        }
+    }
+
+    /// Rotates the elements `n` places to the right, so the last `n` elements
+    /// become the first ones. Moves `min(n, len - n)` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is bigger than the length.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len, "rotate by more than the length");
+        if n <= self.len - n {
+            self.rotate_back_to_front(n);
+        } else {
+            self.rotate_front_to_back(self.len - n);
+        }
+    }
+
+    // An element popped from one end always fits at the other, so these never
+    // grow the buffer.
+    fn rotate_front_to_back(&mut self, n: usize) {
+        for _ in 0..n {
+            let elem = self.pop_front().unwrap();
+            self.push_back(elem);
+        }
+    }
+
+    fn rotate_back_to_front(&mut self, n: usize) {
+        for _ in 0..n {
+            let elem = self.pop_back().unwrap();
+            self.push_front(elem);
+        }
+    }
+
+    /// Removes the elements in `range`, returning them as an iterator. When the
+    /// iterator is dropped, the ones it did not yield are dropped and the shorter
+    /// of the parts before and after the range is moved to close the gap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or starts after its end.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        let range = check_range(range, self.len);
+        let tail_len = self.len - range.end;
+        // Until the drain is dropped the buffer only owns the elements before
+        // the range, so forgetting the drain leaks the rest instead of dropping
+        // it twice.
+        self.len = range.start;
+        Drain {
+            front: range.start,
+            back: range.end,
+            tail_start: range.end,
+            tail_len,
+            ring: self,
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        Iter {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        IterMut {
+            front: front.iter_mut(),
+            back: back.iter_mut(),
+        }
+    }
+}
+
+impl<T> Default for RingBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for RingBuffer<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Index<usize> for RingBuffer<T> {
+    type Output = T;
+    fn index(&self, idx: usize) -> &T {
+        self.get(idx).expect("index out of bounds")
+    }
+}
+
+impl<T> IndexMut<usize> for RingBuffer<T> {
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        self.get_mut(idx).expect("index out of bounds")
+    }
+}
+
+impl<T> FromIterator<T> for RingBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ring = RingBuffer::new();
+        ring.extend(iter);
+        ring
+    }
+}
+
+impl<T> Extend<T> for RingBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+impl<T: Clone> Clone for RingBuffer<T> {
+    fn clone(&self) -> Self {
+        let mut ring = RingBuffer::with_capacity(self.len);
+        ring.extend(self.iter().cloned());
+        ring
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RingBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for RingBuffer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for RingBuffer<T> {}
+
+impl<T> IntoIterator for RingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { ring: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RingBuffer<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the elements of a [`RingBuffer`], from both ends.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, T> {
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over mutable references to the elements of a [`RingBuffer`].
+#[derive(Debug)]
+pub struct IterMut<'a, T> {
+    front: slice::IterMut<'a, T>,
+    back: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An iterator that moves the elements out of a [`RingBuffer`], from both ends.
+pub struct IntoIter<T> {
+    ring: RingBuffer<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.ring.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.ring.len, Some(self.ring.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.ring.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.ring).finish()
+    }
+}
+
+/// An iterator that removes a range of a [`RingBuffer`], returned by
+/// [`RingBuffer::drain`].
+pub struct Drain<'a, T> {
+    ring: &'a mut RingBuffer<T>,
+    // The elements in [front, back) have not been yielded yet.
+    front: usize,
+    back: usize,
+    // Where the elements after the range start, and how many there are.
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        unsafe { Some(ptr::read(self.ring.slot(self.front - 1))) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        unsafe { Some(ptr::read(self.ring.slot(self.back))) }
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+impl<T> FusedIterator for Drain<'_, T> {}
+
+impl<T> fmt::Debug for Drain<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Drain")
+            .field("remaining", &(self.back - self.front))
+            .finish()
+    }
+}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        /// Closes the gap when dropped, so it also happens if dropping the
+        /// elements that were not yielded panics.
+        struct CloseGap<'r, 'a, T>(&'r mut Drain<'a, T>);
+
+        impl<T> Drop for CloseGap<'_, '_, T> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let ring = &mut *drain.ring;
+                let (head_len, tail_start, tail_len) = (ring.len, drain.tail_start, drain.tail_len);
+                let removed = tail_start - head_len;
+                ring.len = head_len + tail_len;
+                if removed == 0 {
+                    return;
+                }
+                unsafe {
+                    if head_len <= tail_len {
+                        // Move the elements before the range forward, last first.
+                        for i in (0..head_len).rev() {
+                            ptr::copy(ring.slot(i), ring.slot(i + removed), 1);
+                        }
+                        ring.head = ring.wrap_add(ring.head, removed);
+                    } else {
+                        for i in 0..tail_len {
+                            ptr::copy(ring.slot(tail_start + i), ring.slot(head_len + i), 1);
+                        }
+                    }
+                }
+            }
+        }
+
+        // The elements not yielded, as the part before the end of the buffer
+        // and the part that wrapped.
+        let count = self.back - self.front;
+        let (first, wrapped) = if count == 0 {
+            (ptr::slice_from_raw_parts_mut(self.ring.ptr(), 0), 0)
+        } else {
+            let start = self.ring.slot(self.front);
+            let first_len =
+                count.min(self.ring.cap() - self.ring.wrap_add(self.ring.head, self.front));
+            (
+                ptr::slice_from_raw_parts_mut(start, first_len),
+                count - first_len,
+            )
+        };
+        let wrapped = ptr::slice_from_raw_parts_mut(self.ring.ptr(), wrapped);
+        self.front = self.back;
+        let _guard = CloseGap(self);
+        unsafe {
+            ptr::drop_in_place(first);
+            ptr::drop_in_place(wrapped);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+    use std::collections::VecDeque;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+
     use super::RingBuffer;
 
     #[test]
@@ -82,7 +641,10 @@ mod tests {
         let mut rg = RingBuffer::new();
         assert_eq!(rg.pop(), None);
         assert!(rg.is_empty());
-        rg.push_back(10); rg.push_back(15); rg.push_back(20); rg.push_back(25);
+        rg.push_back(10);
+        rg.push_back(15);
+        rg.push_back(20);
+        rg.push_back(25);
         assert_eq!(rg.len(), 4);
         assert_eq!(rg.pop(), Some(10));
         assert_eq!(rg.pop(), Some(15));
@@ -93,4 +655,208 @@ mod tests {
         assert_eq!(rg.pop(), Some(25));
         assert!(rg.is_empty());
     }
+
+    #[test]
+    fn test_growth_when_wrapped() {
+        // Wrapped with a short front part, which is moved after the old end.
+        let mut rg = RingBuffer::with_capacity(4);
+        for x in [1, 2, 3] {
+            rg.push_back(x);
+        }
+        rg.pop();
+        rg.push_back(4);
+        rg.push_back(5);
+        rg.push_back(6);
+        let drained: Vec<_> = std::iter::from_fn(|| rg.pop()).collect();
+        assert_eq!(drained, [2, 3, 4, 5, 6]);
+
+        // Wrapped with a short back part, which is moved to the new end.
+        let mut rg = RingBuffer::with_capacity(4);
+        rg.push_back(1);
+        rg.push_back(2);
+        rg.push_back(3);
+        rg.push_front(0);
+        rg.push_front(-1);
+        let drained: Vec<_> = std::iter::from_fn(|| rg.pop()).collect();
+        assert_eq!(drained, [-1, 0, 1, 2, 3]);
+
+        let mut units = RingBuffer::new();
+        units.push_front(());
+        units.push_back(());
+        assert_eq!(units.pop(), Some(()));
+        assert_eq!(units.len(), 1);
+    }
+
+    #[test]
+    fn test_against_vec_deque() {
+        // A xorshift generator for the random operations.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let rounds = if cfg!(miri) { 4 } else { 50 };
+        for round in 0..rounds {
+            let mut rg = if round % 2 == 0 {
+                RingBuffer::new()
+            } else {
+                RingBuffer::with_capacity(round)
+            };
+            let mut expected = VecDeque::new();
+            for step in 0..500 {
+                let value = format!("{round}-{step}");
+                let len = expected.len();
+                // A random amount in 0..=len.
+                let n = next() as usize % (len + 1);
+                match next() % 12 {
+                    0..=2 => {
+                        rg.push_back(value.clone());
+                        expected.push_back(value);
+                    }
+                    3 | 4 => {
+                        rg.push_front(value.clone());
+                        expected.push_front(value);
+                    }
+                    5 => assert_eq!(rg.pop_front(), expected.pop_front()),
+                    6 => assert_eq!(rg.pop_back(), expected.pop_back()),
+                    7 => {
+                        rg.rotate_left(n);
+                        expected.rotate_left(n);
+                    }
+                    8 => {
+                        rg.rotate_right(n);
+                        expected.rotate_right(n);
+                    }
+                    9 => {
+                        let end = n + next() as usize % (len - n + 1);
+                        let mut drain = rg.drain(n..end);
+                        let mut expected_drain = expected.drain(n..end);
+                        // Only part of it is consumed, from both ends.
+                        assert_eq!(drain.next(), expected_drain.next());
+                        assert_eq!(drain.next_back(), expected_drain.next_back());
+                        assert_eq!(drain.len(), expected_drain.len());
+                    }
+                    10 => {
+                        assert_eq!(rg.make_contiguous(), expected.make_contiguous());
+                        assert!(rg.as_slices().1.is_empty());
+                    }
+                    _ => {
+                        assert_eq!(rg.front(), expected.front());
+                        assert_eq!(rg.back(), expected.back());
+                        assert_eq!(rg.get(n), expected.get(n));
+                        if let Some(x) = rg.back_mut() {
+                            x.push('!');
+                            expected.back_mut().unwrap().push('!');
+                        }
+                    }
+                }
+                assert_eq!(rg.len(), expected.len());
+                assert!(rg.iter().eq(expected.iter()));
+                assert!(rg.iter().rev().eq(expected.iter().rev()));
+            }
+            // Left for the drop, so it is checked under Miri too.
+            for _ in 0..expected.len() / 2 {
+                assert_eq!(rg.pop(), expected.pop_front());
+            }
+        }
+    }
+
+    #[test]
+    fn test_deque_api() {
+        let mut rg: RingBuffer<i32> = (1..=5).collect();
+        rg.push_front(0);
+        assert_eq!(rg.front(), Some(&0));
+        assert_eq!(rg.back(), Some(&5));
+        assert_eq!(rg[2], 2);
+        rg[2] = 20;
+        *rg.front_mut().unwrap() -= 1;
+        *rg.back_mut().unwrap() += 1;
+        assert_eq!(rg.get(6), None);
+        assert_eq!(format!("{rg:?}"), "[-1, 1, 20, 3, 4, 6]");
+        // The 0 was pushed at the end of the buffer.
+        assert_eq!(rg.as_slices(), (&[-1][..], &[1, 20, 3, 4, 6][..]));
+        assert_eq!(rg.make_contiguous(), [-1, 1, 20, 3, 4, 6]);
+        assert_eq!(rg.as_slices().1, []);
+
+        rg.rotate_left(2);
+        assert_eq!(rg.iter().copied().collect::<Vec<_>>(), [20, 3, 4, 6, -1, 1]);
+        rg.rotate_right(1);
+        assert_eq!(rg.pop_back(), Some(-1));
+        for x in &mut rg {
+            *x *= 2;
+        }
+        assert_eq!(rg.iter().rev().copied().collect::<Vec<_>>(), [12, 8, 6, 40, 2]);
+        assert_eq!(rg.drain(1..3).collect::<Vec<_>>(), [40, 6]);
+        assert_eq!(rg.clone(), [2, 8, 12].into_iter().collect());
+        let mut iter = rg.into_iter();
+        assert_eq!(iter.next_back(), Some(12));
+        assert_eq!(iter.len(), 2);
+
+        let mut empty: RingBuffer<String> = RingBuffer::new();
+        assert_eq!(empty.back(), None);
+        assert_eq!(empty.drain(..).count(), 0);
+        assert!(empty.make_contiguous().is_empty());
+        assert_eq!(empty.capacity(), 0);
+
+        let mut units: RingBuffer<()> = RingBuffer::new();
+        units.push_back(());
+        units.push_front(());
+        assert_eq!(units.make_contiguous().len(), 2);
+        assert_eq!(units.drain(..1).count(), 1);
+        assert_eq!(units.into_iter().count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_out_of_bounds() {
+        let rg: RingBuffer<i32> = (0..3).collect();
+        let _ = rg[3];
+    }
+
+    #[test]
+    fn test_drain_panic_safety() {
+        /// Counts its drops, and panics when dropped if asked to.
+        struct Tracked(i32, Rc<Cell<usize>>, bool);
+
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+                if self.2 {
+                    panic!("drop of {}", self.0);
+                }
+            }
+        }
+
+        let drops = Rc::new(Cell::new(0));
+        let mut rg = RingBuffer::with_capacity(8);
+        // Wrapped: the first 3 are at the end of the buffer.
+        for i in (0..3).rev() {
+            rg.push_front(Tracked(i, drops.clone(), false));
+        }
+        for i in 3..8 {
+            rg.push_back(Tracked(i, drops.clone(), false));
+        }
+        rg[4].2 = true;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mut drain = rg.drain(1..6);
+            drain.next();
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 5);
+        let values: Vec<i32> = rg.iter().map(|t| t.0).collect();
+        assert_eq!(values, [0, 6, 7]);
+
+        // The consumer panics halfway.
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            for t in rg.drain(..2) {
+                assert!(t.0 != 6, "consumer panicked");
+            }
+        }));
+        assert!(result.is_err());
+        assert_eq!(rg.len(), 1);
+        drop(rg);
+        assert_eq!(drops.get(), 8);
+    }
 }
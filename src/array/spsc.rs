@@ -0,0 +1,609 @@
+//! A wait-free single producer, single consumer queue.
+//!
+//! [`channel`] returns a [`Producer`] and a [`Consumer`] that share a fixed
+//! size buffer. Each handle can be moved to its own thread, and neither of them
+//! ever blocks or takes a lock: a push to a full queue or a pop from an empty
+//! one returns right away, so they are safe to call from an audio callback.
+//!
+//! # Examples
+//!
+//!```rust
+//! # use algo_front_end_masters::array::spsc;
+//! let (mut producer, mut consumer) = spsc::channel(1024);
+//! let audio = std::thread::spawn(move || {
+//!     let samples = [0.25_f32; 256];
+//!     let mut sent = 0;
+//!     while sent < samples.len() {
+//!         sent += producer.push_slice(&samples[sent..]);
+//!     }
+//! });
+//! let mut received = [0.0_f32; 256];
+//! let mut count = 0;
+//! while count < received.len() {
+//!     count += consumer.pop_slice(&mut received[count..]);
+//! }
+//! audio.join().unwrap();
+//! assert!(received.iter().all(|&s| s == 0.25));
+//! ```
+
+use std::fmt;
+use std::iter::FusedIterator;
+use std::ops::Deref;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::RawVec;
+
+/// Gives a value its own cache line, so the producer writing `tail` does not
+/// slow down the consumer writing `head` (false sharing). 128 bytes covers the
+/// pairs of lines prefetched together on x86 and the bigger lines of Apple chips.
+#[repr(align(128))]
+struct CachePadded<T>(T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// The buffer shared by both handles.
+///
+/// `head` is the position of the next element to pop and `tail` the position of
+/// the next push. Positions go from 0 to `2 * capacity`, so a full queue
+/// (`tail - head == capacity`) can be told apart from an empty one
+/// (`tail == head`) for any capacity. The slot of a position is
+/// `position % capacity`.
+struct Shared<T> {
+    buf: RawVec<T>,
+    capacity: usize,
+    head: CachePadded<AtomicUsize>,
+    tail: CachePadded<AtomicUsize>,
+}
+
+// Only the producer writes slots and only the consumer reads them, and each
+// element is handed from one to the other, so sharing the buffer between two
+// threads only needs the elements to be `Send`.
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    /// How many elements are between two positions.
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        if tail >= head {
+            tail - head
+        } else {
+            tail + 2 * self.capacity - head
+        }
+    }
+
+    /// The position `n` places after `position`, without overflowing.
+    fn advance(&self, position: usize, n: usize) -> usize {
+        let end = 2 * self.capacity;
+        if position >= end - n {
+            position - (end - n)
+        } else {
+            position + n
+        }
+    }
+
+    fn slot_index(&self, position: usize) -> usize {
+        if position >= self.capacity {
+            position - self.capacity
+        } else {
+            position
+        }
+    }
+
+    fn slot(&self, position: usize) -> *mut T {
+        unsafe { self.buf.ptr.as_ptr().add(self.slot_index(position)) }
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        // Both handles are gone and stored their final positions.
+        let mut head = *self.head.0.get_mut();
+        let tail = *self.tail.0.get_mut();
+        while head != tail {
+            unsafe { ptr::drop_in_place(self.slot(head)) };
+            head = self.advance(head, 1);
+        }
+    }
+}
+
+/// Creates a queue that holds up to `capacity` elements, returning its two
+/// ends.
+///
+/// # Panics
+///
+/// Panics if `capacity` is 0 or bigger than `usize::MAX / 2`.
+pub fn channel<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    assert!(capacity > 0, "capacity must be bigger than 0");
+    assert!(capacity <= usize::MAX / 2, "capacity overflow");
+    let shared = Arc::new(Shared {
+        buf: RawVec::with_capacity(capacity),
+        capacity,
+        head: CachePadded(AtomicUsize::new(0)),
+        tail: CachePadded(AtomicUsize::new(0)),
+    });
+    (
+        Producer {
+            shared: shared.clone(),
+            tail: 0,
+            cached_head: 0,
+        },
+        Consumer {
+            shared,
+            head: 0,
+            cached_tail: 0,
+        },
+    )
+}
+
+/// The end of a [`channel`] that pushes elements.
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+    // Only the producer moves the tail, so its own copy is always right.
+    tail: usize,
+    // The last head read from the consumer. It can only be behind the real one,
+    // so the free space it gives is never too big, and it is read again only
+    // when the queue looks full.
+    cached_head: usize,
+}
+
+impl<T> Producer<T> {
+    /// How many elements can be pushed without waiting for the consumer. The
+    /// head is only read again when the cached one leaves less than `wanted`.
+    fn free(&mut self, wanted: usize) -> usize {
+        let capacity = self.shared.capacity;
+        let free = capacity - self.shared.distance(self.cached_head, self.tail);
+        if free >= wanted {
+            return free;
+        }
+        // Acquire, so the consumer is done reading the slots it freed.
+        self.cached_head = self.shared.head.load(Ordering::Acquire);
+        capacity - self.shared.distance(self.cached_head, self.tail)
+    }
+
+    /// Makes the elements written up to `tail` visible to the consumer.
+    fn publish(&mut self, tail: usize) {
+        self.tail = tail;
+        // Release, so the writes of the elements happen before the consumer
+        // sees them.
+        self.shared.tail.store(tail, Ordering::Release);
+    }
+
+    /// Adds an element, or gives it back if the queue is full.
+    pub fn push(&mut self, elem: T) -> Result<(), T> {
+        if self.free(1) == 0 {
+            return Err(elem);
+        }
+        unsafe { ptr::write(self.shared.slot(self.tail), elem) };
+        self.publish(self.shared.advance(self.tail, 1));
+        Ok(())
+    }
+
+    /// Pushes elements from an iterator while there is room, making them
+    /// visible to the consumer all at once. Returns how many were pushed, and no
+    /// element is taken from the iterator unless there is room for it.
+    ///
+    /// If the iterator panics, the elements taken before are still pushed.
+    pub fn push_iter<I: Iterator<Item = T>>(&mut self, iter: &mut I) -> usize {
+        /// Publishes what was written when dropped, even when unwinding.
+        struct Publish<'a, T> {
+            producer: &'a mut Producer<T>,
+            tail: usize,
+            pushed: usize,
+        }
+
+        impl<T> Drop for Publish<'_, T> {
+            fn drop(&mut self) {
+                if self.pushed > 0 {
+                    self.producer.publish(self.tail);
+                }
+            }
+        }
+
+        let free = self.free(usize::MAX);
+        let tail = self.tail;
+        let mut guard = Publish {
+            producer: self,
+            tail,
+            pushed: 0,
+        };
+        while guard.pushed < free {
+            let Some(elem) = iter.next() else { break };
+            let shared = &guard.producer.shared;
+            unsafe { ptr::write(shared.slot(guard.tail), elem) };
+            guard.tail = shared.advance(guard.tail, 1);
+            guard.pushed += 1;
+        }
+        guard.pushed
+    }
+
+    /// Copies as many elements of `elems` as fit, with at most two `memcpy`,
+    /// and returns how many were pushed.
+    pub fn push_slice(&mut self, elems: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        let count = elems.len().min(self.free(elems.len()));
+        if count == 0 {
+            return 0;
+        }
+        let shared = &self.shared;
+        let start = shared.slot_index(self.tail);
+        // Up to the end of the buffer, and the rest from its start.
+        let first = count.min(shared.capacity - start);
+        unsafe {
+            let ptr = shared.buf.ptr.as_ptr();
+            ptr::copy_nonoverlapping(elems.as_ptr(), ptr.add(start), first);
+            ptr::copy_nonoverlapping(elems.as_ptr().add(first), ptr, count - first);
+        }
+        self.publish(self.shared.advance(self.tail, count));
+        count
+    }
+
+    /// How many elements are in the queue. The consumer can pop at the same
+    /// time, so it can only be too big.
+    pub fn len(&self) -> usize {
+        let head = self.shared.head.load(Ordering::Acquire);
+        self.shared.distance(head, self.tail)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.shared.capacity
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Returns true if the consumer was dropped, so nothing pushed will be read.
+    pub fn is_abandoned(&self) -> bool {
+        Arc::strong_count(&self.shared) == 1
+    }
+}
+
+impl<T> fmt::Debug for Producer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Producer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/// The end of a [`channel`] that pops elements.
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+    // Only the consumer moves the head, so its own copy is always right.
+    head: usize,
+    // The last tail read from the producer, read again only when the queue
+    // looks empty.
+    cached_tail: usize,
+}
+
+impl<T> Consumer<T> {
+    /// How many elements can be popped without waiting for the producer. The
+    /// tail is only read again when the cached one gives less than `wanted`.
+    fn available(&mut self, wanted: usize) -> usize {
+        let available = self.shared.distance(self.head, self.cached_tail);
+        if available >= wanted {
+            return available;
+        }
+        // Acquire, so the writes of the elements are seen.
+        self.cached_tail = self.shared.tail.load(Ordering::Acquire);
+        self.shared.distance(self.head, self.cached_tail)
+    }
+
+    /// Gives the slots up to `head` back to the producer.
+    fn release(&mut self, head: usize) {
+        self.head = head;
+        // Release, so the reads of the elements happen before the producer
+        // writes over them.
+        self.shared.head.store(head, Ordering::Release);
+    }
+
+    /// Removes the oldest element, or returns `None` if the queue is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.available(1) == 0 {
+            return None;
+        }
+        let elem = unsafe { ptr::read(self.shared.slot(self.head)) };
+        self.release(self.shared.advance(self.head, 1));
+        Some(elem)
+    }
+
+    /// The oldest element, without removing it.
+    pub fn peek(&mut self) -> Option<&T> {
+        if self.available(1) == 0 {
+            return None;
+        }
+        unsafe { Some(&*self.shared.slot(self.head)) }
+    }
+
+    /// Returns an iterator that pops up to `max` of the elements that are in
+    /// the queue now. Their slots are given back to the producer all at once,
+    /// when the iterator is dropped, and the elements it did not yield stay in
+    /// the queue.
+    pub fn pop_batch(&mut self, max: usize) -> Batch<'_, T> {
+        let remaining = self.available(max).min(max);
+        let start = self.head;
+        Batch {
+            consumer: self,
+            start,
+            remaining,
+        }
+    }
+
+    /// Copies up to `out.len()` elements into `out`, with at most two `memcpy`,
+    /// and returns how many were popped.
+    pub fn pop_slice(&mut self, out: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let count = out.len().min(self.available(out.len()));
+        if count == 0 {
+            return 0;
+        }
+        let shared = &self.shared;
+        let start = shared.slot_index(self.head);
+        let first = count.min(shared.capacity - start);
+        unsafe {
+            let ptr = shared.buf.ptr.as_ptr();
+            ptr::copy_nonoverlapping(ptr.add(start), out.as_mut_ptr(), first);
+            ptr::copy_nonoverlapping(ptr, out.as_mut_ptr().add(first), count - first);
+        }
+        self.release(self.shared.advance(self.head, count));
+        count
+    }
+
+    /// How many elements are in the queue. The producer can push at the same
+    /// time, so it can only be too small.
+    pub fn len(&self) -> usize {
+        let tail = self.shared.tail.load(Ordering::Acquire);
+        self.shared.distance(self.head, tail)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Returns true if the producer was dropped, so once the queue is empty
+    /// nothing else will arrive.
+    pub fn is_abandoned(&self) -> bool {
+        Arc::strong_count(&self.shared) == 1
+    }
+}
+
+impl<T> Drop for Consumer<T> {
+    fn drop(&mut self) {
+        // A forgotten batch may not have given back what it popped, and the
+        // queue must not drop those elements again.
+        self.shared.head.store(self.head, Ordering::Release);
+    }
+}
+
+impl<T> fmt::Debug for Consumer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Consumer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/// An iterator over popped elements, returned by [`Consumer::pop_batch`].
+pub struct Batch<'a, T> {
+    consumer: &'a mut Consumer<T>,
+    // The head of the consumer when the batch started. The consumer's own head
+    // moves with each element, and is only given to the producer on drop.
+    start: usize,
+    remaining: usize,
+}
+
+impl<T> Iterator for Batch<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let consumer = &mut *self.consumer;
+        let elem = unsafe { ptr::read(consumer.shared.slot(consumer.head)) };
+        consumer.head = consumer.shared.advance(consumer.head, 1);
+        self.remaining -= 1;
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Batch<'_, T> {}
+impl<T> FusedIterator for Batch<'_, T> {}
+
+impl<T> Drop for Batch<'_, T> {
+    fn drop(&mut self) {
+        if self.consumer.head != self.start {
+            self.consumer.release(self.consumer.head);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::channel;
+
+    /// Counts its drops.
+    struct Tracked(usize, Arc<AtomicUsize>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.1.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let (mut producer, mut consumer) = channel(3);
+        assert_eq!(consumer.pop(), None);
+        for x in 0..3 {
+            assert_eq!(producer.push(x), Ok(()));
+        }
+        assert_eq!(producer.push(3), Err(3));
+        assert!(producer.is_full());
+        assert_eq!(consumer.peek(), Some(&0));
+        assert_eq!(consumer.pop(), Some(0));
+        assert_eq!(producer.push(3), Ok(()));
+        assert_eq!(consumer.len(), 3);
+
+        // Batches wrap around the end of the buffer.
+        assert_eq!(consumer.pop_batch(2).collect::<Vec<_>>(), [1, 2]);
+        let mut items = 4..10;
+        assert_eq!(producer.push_iter(&mut items), 2);
+        assert_eq!(items.next(), Some(6));
+        let mut batch = consumer.pop_batch(usize::MAX);
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.next(), Some(3));
+        drop(batch);
+        assert_eq!(consumer.pop(), Some(4));
+
+        assert_eq!(producer.push_slice(&[7, 8, 9, 10]), 2);
+        let mut out = [0; 8];
+        assert_eq!(consumer.pop_slice(&mut out), 3);
+        assert_eq!(out[..3], [5, 7, 8]);
+        assert!(consumer.is_empty());
+        assert!(!consumer.is_abandoned());
+        drop(producer);
+        assert!(consumer.is_abandoned());
+    }
+
+    #[test]
+    fn test_drops() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let (mut producer, mut consumer) = channel(4);
+        let mut items = (0..4).map(|i| Tracked(i, drops.clone()));
+        assert_eq!(producer.push_iter(&mut items), 4);
+        drop(consumer.pop());
+        // The batch only yields one, the other stays in the queue.
+        let mut batch = consumer.pop_batch(2);
+        drop(batch.next());
+        drop(batch);
+        assert_eq!(drops.load(Ordering::Relaxed), 2);
+        assert_eq!(consumer.len(), 2);
+        assert!(producer.push(Tracked(4, drops.clone())).is_ok());
+        drop(producer);
+        drop(consumer);
+        assert_eq!(drops.load(Ordering::Relaxed), 5);
+
+        // A forgotten batch still gives its elements to the consumer.
+        let (mut producer, mut consumer) = channel(2);
+        producer.push(Tracked(0, drops.clone())).ok();
+        producer.push(Tracked(1, drops.clone())).ok();
+        let mut batch = consumer.pop_batch(2);
+        let first = batch.next().unwrap();
+        std::mem::forget(batch);
+        assert_eq!(consumer.pop().map(|t| t.0), Some(1));
+        drop((first, producer, consumer));
+        assert_eq!(drops.load(Ordering::Relaxed), 7);
+
+        let (mut producer, mut consumer) = channel(usize::MAX / 2);
+        assert_eq!(producer.push_iter(&mut std::iter::repeat_n((), 10)), 10);
+        assert_eq!(consumer.pop_batch(20).count(), 10);
+    }
+
+    #[test]
+    fn test_stress() {
+        const COUNT: usize = if cfg!(miri) { 2_000 } else { 1_000_000 };
+        // A small capacity that is not a power of two, so the queue is often
+        // full and the positions wrap many times.
+        let (mut producer, mut consumer) = channel::<usize>(7);
+        let producer = thread::spawn(move || {
+            let mut next = 0;
+            while next < COUNT {
+                // Each kind of push in turn.
+                let pushed = match next % 3 {
+                    0 => producer.push(next).map_or(0, |()| 1),
+                    1 => producer.push_iter(&mut (next..COUNT.min(next + 5))),
+                    _ => {
+                        let batch: Vec<usize> = (next..COUNT.min(next + 4)).collect();
+                        producer.push_slice(&batch)
+                    }
+                };
+                next += pushed;
+                if pushed == 0 {
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut expected = 0;
+        let mut out = [0; 6];
+        while expected < COUNT {
+            let popped: Vec<usize> = match expected % 3 {
+                0 => consumer.pop().into_iter().collect(),
+                1 => consumer.pop_batch(5).collect(),
+                _ => {
+                    let count = consumer.pop_slice(&mut out);
+                    out[..count].to_vec()
+                }
+            };
+            if popped.is_empty() {
+                thread::yield_now();
+            }
+            for value in popped {
+                assert_eq!(value, expected);
+                expected += 1;
+            }
+        }
+        producer.join().unwrap();
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn test_stress_owned_elements() {
+        const COUNT: usize = if cfg!(miri) { 500 } else { 100_000 };
+        let drops = Arc::new(AtomicUsize::new(0));
+        let (mut producer, mut consumer) = channel(16);
+        let items: Vec<_> = (0..COUNT).map(|i| Tracked(i, drops.clone())).collect();
+        let producer = thread::spawn(move || {
+            let mut items = items.into_iter().peekable();
+            // Stops once the consumer is gone, the rest is dropped with `items`.
+            while items.peek().is_some() && !producer.is_abandoned() {
+                if producer.push_iter(&mut items) == 0 {
+                    thread::yield_now();
+                }
+            }
+        });
+        // Stops early, the rest is dropped with the queue.
+        let mut expected = 0;
+        while expected < COUNT / 2 {
+            match consumer.pop() {
+                Some(item) => {
+                    assert_eq!(item.0, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        drop(consumer);
+        producer.join().unwrap();
+        assert_eq!(drops.load(Ordering::Relaxed), COUNT);
+    }
+}
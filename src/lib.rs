@@ -107,6 +107,10 @@
 /// A linear search will take two arguments: A sequence of data and a needle that will be the value that we want to find in
 /// the sequence. The r
 ///
+/// `linear_search_index` returns where the needle is. On big slices, `parallel_linear_search` splits the
+/// work between threads, and `simd_position` compares many `u8`, `u32` or `f32` at once, as does
+/// `simd_position_in_range` to find the first element inside a range.
+///
 /// ## Binary Search
 ///
 /// This mod provides a binary search algorithm.
@@ -119,6 +123,90 @@
 /// binary search is **O (log n)** because the number of steps necessary to search for a value in the sequence
 /// will be equal to *log<sub>2</sub>N*, so if we have a sequence 256 items long, we will need
 /// *log<sub>2</sub>256 = 8* steps to find a value in it.uining time of a linear search is **O (N)**
+///
+/// Besides the `bool` returning `binary_search`, `binary_search_index` works like the std one, returning
+/// `Ok` with the position of the needle or `Err` with the position where it could be inserted.
+/// `lower_bound`, `upper_bound` and `equal_range` find the range of elements equal to the needle, and
+/// `partition_point` finds where a predicate stops being true. All of them take the needle by reference
+/// and have `_by` and `_by_key` variants.
+///
+/// ## Other Searches Over Sorted Data
+///
+/// `exponential_search` doubles its step until it passes the needle and then does a binary search,
+/// so it works on data with no known end (like a stream) with `exponential_search_unbounded`.
+/// `interpolation_search` guesses the position from the value of the needle, which takes
+/// **O (log log N)** on uniformly distributed numbers, `fibonacci_search` splits the sequence in
+/// Fibonacci sized parts and `jump_search` checks one element per block before scanning a single block.
+///
+/// ## Crystal Balls
+///
+/// Given a building and two crystal balls, find the first floor from where a ball breaks when dropped.
+/// `two_crystal_balls` solves it over a slice of bools, and `crystal_balls` takes any number of balls
+/// and any monotone predicate, dropping each ball following the optimal schedule of the egg drop
+/// problem and returning how many drops it took.
+///
+/// ## Cache Friendly Layouts
+///
+/// On big tables a binary search is slowed down by the memory and by the CPU guessing the wrong half.
+/// `branchless_binary_search` picks the half with a conditional move instead of a jump, and
+/// `EytzingerSet` stores a sorted set in the breadth first order of its search tree, so the first levels
+/// share the same cache lines and the next ones can be prefetched. `cargo bench --bench search_layouts`
+/// compares both with `binary_search`.
+///
+/// ## Searching for an Answer
+///
+/// A binary search does not need a sequence, only a question whose answer changes once, like "can
+/// everything be shipped with this capacity?". `bisect_int` finds the first integer where a predicate
+/// becomes true, even over the whole range of `u64` or `i64`, and `bisect_float` does the same for floats
+/// up to a precision. For functions that go down and then up, `ternary_search`, `golden_section_search`
+/// and `ternary_search_int` find their minimum.
+///
+/// ## Substring Search
+///
+/// The `substring` mod finds every position of a sequence inside another one, over slices of anything
+/// or over strings. Knuth-Morris-Pratt never goes back in the haystack, Boyer-Moore and Horspool compare
+/// from the end of the needle to skip most of the haystack, and Rabin-Karp compares rolling hashes.
+/// The items only need `Eq`, and the last three look up the items of the needle by scanning them, or
+/// in O(1) with `BytePositions` for strings and `HashPositions` for items that can be hashed.
+/// To look for many patterns at once, `AhoCorasick` builds an automaton from all of them that reads the
+/// input once, even in chunks, returning every match or only the leftmost-longest ones.
+///
+/// When the same text is searched many times it is better to index it. A suffix array is the list of
+/// every suffix of the text in order, so the suffixes that start with a pattern are next to each other and
+/// can be found with a lower and an upper bound. `SuffixIndex` uses it with the LCP array (the common
+/// prefix of each pair of neighbours) to count occurrences and find the longest repeated substring, and
+/// `longest_common_substring` finds the longest part shared by two texts.
+///
+/// ## Fuzzy Search
+///
+/// To find what the user meant even with typos, `levenshtein` counts the chars that must be inserted,
+/// removed or replaced to turn a string into another, and `damerau_levenshtein` also counts swapping two
+/// chars as one edit. `approx_search` returns the words of a list within some edits of a needle, and a
+/// `BkTree` indexes the words by their distances so the close ones can be found without checking them all.
+///
+/// ## Probabilistic Filters
+///
+/// Before searching a big dataset it is cheaper to ask a filter if the value can be there. A filter can
+/// answer "definitely not" or "probably yes", using a few bits per value. `BloomFilter` is sized from the
+/// rate of false "yes" answers wanted, `CountingBloomFilter` keeps counters so values can be removed, and
+/// `CuckooFilter` stores small fingerprints that can be removed too. All of them can be turned into bytes
+/// and read back, so they can be stored next to the data. The values are `FilterKey`s, hashed from a fixed
+/// encoding of their bytes, so a filter stored on one machine gives the same answers on any other.
+///
+/// ## Searching Files
+///
+/// A sorted file can be binary searched without loading it: `binary_search_file_by` jumps to a byte offset,
+/// skips to the start of the next line and compares it, so only **O (log N)** lines are read. It works
+/// with anything that is `Read + Seek`, and returns the first matching line with its offset.
+///
+/// ## Tracing a Search
+///
+/// To see if a search really takes the steps its Big-O says, a `Tracer` wraps the haystack and the needle
+/// in values that write down every element read and every comparison, and they can be passed to any search
+/// of this mod. `trace_search` does it in one call, so a test can check that `binary_search` looks at no
+/// more than 9 of 256 items. Searches that call a function or read a file are traced by wrapping the
+/// function with `probe_fn` or `eval_fn` and the file with `source`. A `Trace` can be exported as JSON, or
+/// as an SVG that draws where each step landed.
 pub mod search;
 
 /// # Sort
@@ -134,7 +222,44 @@ pub mod search;
 /// will take a subsequent pair of data. Let's say X and Y, if X is greater than Y then they swap their positions,
 /// If not, the *algo* will take another pair now, Y and Z and make the same comparison. If X was greater than Y
 /// the pair in this scenario would be X and Z. The *algo* will continue in this loop of instructions
-/// until it sorts the entire sequence.
+/// until it sorts the entire sequence. If a full pass does not swap any pair the sequence is already
+/// sorted and the *algo* stops, so the best case (a sorted input) is **O (N)**.
+///
+/// ## Elementary Sorts
+///
+/// Besides bubble sort this mod provides other simple sorts: insertion, binary insertion, selection,
+/// cocktail shaker, gnome, comb and Shell sort (with Shell, Knuth, Ciura and Sedgewick gap sequences).
+/// Every sort has a `_by` variant that takes a comparator, and returns a `SortStats` with the
+/// number of comparisons and swaps it made, so you can check the Big O of each one in practice.
+///
+/// ## Selection
+///
+/// When only the smallest K elements or the median are needed there is no reason to sort everything.
+/// `select_nth` is a quickselect that puts the Nth element in its sorted position in **O (N)**,
+/// `partial_sort` sorts only the first K positions and `top_k` keeps the K smallest items of an
+/// iterator using only K elements of memory.
+///
+/// ## Sorting Networks
+///
+/// A sorting network is a fixed list of compare-exchange operations that sorts any input of a given
+/// length, the operations do not depend on the data so they can be done without branches.
+/// `network_sort` sorts arrays of up to 16 elements with the smallest networks known, and
+/// `bitonic_sort` is a network for any power of two length with **O (N log² N)** comparisons.
+/// A network can be checked with `verify_network`, using the 0-1 principle: if a network sorts every
+/// sequence of zeros and ones, it sorts every sequence.
+///
+/// ## String Ordering
+///
+/// The plain `PartialOrd` of strings compares them char by char, so "file10" comes before "file2".
+/// This mod provides comparators that can be given to any `_by` sort: `natural_cmp` compares runs of
+/// digits by their numeric value, `case_insensitive_cmp` ignores the case of the letters and
+/// `version_cmp` orders dotted version strings like "1.2.10" following the semver rules.
+///
+/// ## Recording
+///
+/// To see how an algorithm works, `record` runs any of the in place algorithms of this mod and captures
+/// every compare, swap and write it does as a `SortEvent`. The `Recording` can be replayed to rebuild
+/// each intermediate state of the sequence, and exported as JSON or as an animated SVG.
 pub mod sort;
 
 /// # Linked List
@@ -179,6 +304,15 @@ pub mod linked_list;
 /// provide operations like "enqueue" or "deque" as you will need to shift every element in the
 /// array.
 ///
+/// `ArrayList` has most of the `Vec` API: the capacity can be set up front with `with_capacity` and
+/// `reserve` and given back with `shrink_to_fit`, and `retain`, `dedup_by`, `split_off` or `append`
+/// move every element at most once. The ones that run user code while moving elements keep the list
+/// valid if that code panics.
+///
+/// A list can be consumed from both ends with `into_iter`, which keeps using its buffer, and `drain`
+/// removes a range while yielding it, moving the elements after the range back only once, when it is
+/// dropped. Both drop the elements they did not yield, even if the loop that uses them panics.
+///
 /// ## RingBuffer or VecDeque
 ///
 /// A Ring Buffer is a list that instead of using 0 idx as it's head and len as it's tail, 
@@ -188,10 +322,29 @@ pub mod linked_list;
 /// ring buffer because given an especific idx an element will wrap to the inverse side of the list
 /// and we can determine it's real position using the % operator, so for example, if we have a
 /// fixed size ring buffer with a capacity to hold 10 itens, we can determine the real idx of the
-/// element by doing (self.len % self.buf.cap), so for example, this same fixed size ring buffer
+/// element by doing ((self.head + idx) % self.buf.cap), so for example, this same fixed size ring buffer
 /// will overwrite any element that is already in the write position, so if we insert the 20th
 /// element in this ring buffer, the element will be writen in the (20 % 10 = 0) idx.
 ///
+/// `BoundedRingBuffer` is such a fixed size ring buffer, for keeping the last N log lines or metric samples:
+/// it never grows, and when it is full a push either overwrites the oldest element and returns it, or is
+/// rejected, depending on its `FullPolicy`. As the idx starts at 0, the 20th element has the idx 19, so with
+/// a capacity of 10 it is written in the (19 % 10 = 9) idx, the slot of the 10th element.
+///
+/// Our `RingBuffer` grows instead, doubling its buffer when it is full. The elements that had wrapped
+/// around the end of the old buffer are moved so they follow the others in the new one, copying the
+/// shorter of the two parts.
+///
+/// It has the same API as `VecDeque`: both ends can be pushed, popped and peeked, any element can be
+/// indexed, and it can be iterated from both ends. As the elements can wrap, `as_slices` returns them
+/// as two slices, and `make_contiguous` moves them so they fit in one. `drain` removes a range and then
+/// moves whichever side of it is shorter to close the gap.
+///
+/// `spsc::channel` splits a fixed ring buffer into a `Producer` and a `Consumer` that can live on two
+/// threads, like an audio callback and the thread feeding it. There is no lock: the producer only moves
+/// the tail and the consumer only moves the head, both atomics on their own cache line, so a push or a
+/// pop never waits. `push_slice`/`pop_slice` copy a whole batch at once.
+///
 pub mod array;
 
 // Recursion is when a function call itself over and over until it reachs a base case, from there they will
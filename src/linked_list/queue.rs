@@ -1,4 +1,4 @@
-use std::{marker::PhantomData, ptr::NonNull, fmt::Debug};
+use std::{cmp::Ordering, marker::PhantomData, ptr::NonNull, fmt::Debug};
 
 /// A Queue is a data structure that store 2 pointers, one to the head of the list and other to
 /// the tail of it, you can interact with a queue by inserting a node to the tail
@@ -182,7 +182,7 @@ impl<T> Queue<T> {
     /// assert_eq!(iterator.next(), Some(&3));
     /// assert_eq!(iterator.next(), None);
     /// ```
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             head: self.head,
             tail: self.tail,
@@ -209,7 +209,7 @@ impl<T> Queue<T> {
     /// assert_eq!(queue.deque(), Some(4));
     /// assert_eq!(queue.deque(), Some(6));
     /// ```
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             head: self.head,
             tail: self.tail,
@@ -222,6 +222,164 @@ impl<T> Queue<T> {
     pub fn is_empty(&self) -> bool {
         self.length == 0 
     }
+
+    /// Sorts the queue so the smallest element is on the head.
+    ///
+    /// It is a bottom-up merge sort that relinks the nodes in place, so it runs
+    /// in **O (N log N)**, does not allocate and does not move any element. The sort
+    /// is stable, and the tail points to the biggest element afterwards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two elements can not be compared, like a NaN. If a comparison
+    /// panics the queue keeps all its elements, in an unspecified order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use algo_front_end_masters::linked_list::Queue;
+    /// let mut queue = Queue::from_iter([3, 1, 2]);
+    /// queue.sort();
+    /// queue.enqueue(4);
+    /// assert_eq!(queue.into_iter().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    /// ```
+    pub fn sort(&mut self)
+    where
+        T: PartialOrd,
+    {
+        self.sort_by(|a, b| a.partial_cmp(b).unwrap())
+    }
+
+    /// Same as [`Queue::sort`] but orders the elements with a comparator function.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use algo_front_end_masters::linked_list::Queue;
+    /// let mut queue = Queue::from_iter([3, 1, 2]);
+    /// queue.sort_by(|a, b| b.cmp(a));
+    /// assert_eq!(queue.deque(), Some(3));
+    /// ```
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // Each pass merges pairs of sorted runs of 'width' nodes.
+        let mut width = 1;
+        while width < self.length {
+            let mut pass = Pass {
+                rest: self.head.take(),
+                queue: self,
+                head: None,
+                tail: None,
+                left: None,
+                right: None,
+            };
+            // SAFETY: Every link comes from a node of this queue, and the pass
+            // holds each node in exactly one of its lists until it is dropped.
+            unsafe {
+                while let Some(left) = pass.rest {
+                    // Cut the next two runs from the rest of the list.
+                    let right = Self::split(left, width);
+                    pass.rest = right.and_then(|right| Self::split(right, width));
+                    pass.left = Some(left);
+                    pass.right = right;
+                    pass.merge(&mut compare);
+                }
+            }
+            // Dropping the pass links the merged list back into the queue.
+            drop(pass);
+            width *= 2;
+        }
+    }
+
+    /// Cuts the list after `len` nodes and returns the rest.
+    ///
+    /// SAFETY: `node` must point to a valid node.
+    unsafe fn split(node: NonNull<Node<T>>, len: usize) -> Link<T> {
+        let mut last = node;
+        for _ in 1..len {
+            match (*last.as_ptr()).next {
+                Some(next) => last = next,
+                None => return None,
+            }
+        }
+        (*last.as_ptr()).next.take()
+    }
+}
+
+/// A pass of [`Queue::sort_by`]. Its nodes are split between the lists below,
+/// each ending in None, and dropping it links them back into the queue in this
+/// order, so the queue keeps every node even if the comparator panics.
+struct Pass<'a, T> {
+    queue: &'a mut Queue<T>,
+    // The nodes already merged in this pass.
+    head: Link<T>,
+    tail: Link<T>,
+    // The two sorted runs being merged.
+    left: Link<T>,
+    right: Link<T>,
+    // The nodes not reached yet in this pass.
+    rest: Link<T>,
+}
+
+impl<T> Pass<'_, T> {
+    /// Moves the nodes of `left` and `right` after the merged ones, on ties the
+    /// node from `left` goes first.
+    ///
+    /// SAFETY: All the lists must be made of valid nodes and end in None.
+    unsafe fn merge<F>(&mut self, compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        while let (Some(l), Some(r)) = (self.left, self.right) {
+            // A panic here leaves both runs untouched.
+            let node = if compare(&(*r.as_ptr()).elem, &(*l.as_ptr()).elem) == Ordering::Less {
+                self.right = (*r.as_ptr()).next;
+                r
+            } else {
+                self.left = (*l.as_ptr()).next;
+                l
+            };
+            (*node.as_ptr()).next = None;
+            self.append(Some(node));
+        }
+        // One of the runs is empty, the other one is already sorted so we only
+        // need to link it.
+        let run = self.left.take().or(self.right.take());
+        self.append(run);
+    }
+
+    /// Links a list after the merged nodes and moves the tail to its last node.
+    ///
+    /// SAFETY: The list must be made of valid nodes and end in None.
+    unsafe fn append(&mut self, list: Link<T>) {
+        let Some(first) = list else { return };
+        match self.tail {
+            Some(tail) => (*tail.as_ptr()).next = Some(first),
+            None => self.head = Some(first),
+        }
+        let mut last = first;
+        while let Some(next) = (*last.as_ptr()).next {
+            last = next;
+        }
+        self.tail = Some(last);
+    }
+}
+
+impl<T> Drop for Pass<'_, T> {
+    fn drop(&mut self) {
+        // When the pass ends normally only the merged list is left, and the
+        // last merged node is the new tail of the queue.
+        // SAFETY: See `Queue::sort_by`.
+        unsafe {
+            for list in [self.left.take(), self.right.take(), self.rest.take()] {
+                self.append(list);
+            }
+        }
+        self.queue.head = self.head;
+        self.queue.tail = self.tail;
+    }
 }
 
 impl<T> Default for Queue<T> {
@@ -341,8 +499,21 @@ impl<T: Ord> Ord for Queue<T> {
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+
     use super::Queue;
 
+    /// Counts its drops.
+    struct Tracked(u32, Rc<Cell<usize>>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+
     #[test]
     fn test_queue() {
         let mut queue = Queue::new();
@@ -413,4 +584,75 @@ mod tests {
         assert!(queue_b > queue_a);
         assert!(queue_b != queue_a);
     }
+
+    #[test]
+    fn test_queue_sort() {
+        let mut queue: Queue<u32> = (0..1000).map(|i| (i * 7919) % 1009).collect();
+        let mut expected: Vec<u32> = queue.iter().copied().collect();
+        expected.sort();
+        queue.sort();
+        assert_eq!(queue.len(), 1000);
+        assert!(queue.iter().eq(expected.iter()));
+
+        // The tail must point to the last node after the sort.
+        queue.enqueue(0);
+        queue.enqueue(2000);
+        assert_eq!(queue.iter().nth(1000), Some(&0));
+        assert_eq!(queue.iter().last(), Some(&2000));
+
+        // Stable: equal keys keep the order they had from the head.
+        let mut pairs = Queue::from_iter([(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')]);
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(pairs.into_iter().collect::<Vec<_>>(), [(0, 'b'), (0, 'd'), (1, 'a'), (1, 'c')]);
+
+        let mut single = Queue::from_iter([1]);
+        single.sort();
+        single.enqueue(0);
+        assert_eq!(single.into_iter().collect::<Vec<_>>(), [1, 0]);
+
+        let mut empty = Queue::<i32>::new();
+        empty.sort();
+        empty.enqueue(1);
+        assert_eq!(empty.deque(), Some(1));
+    }
+
+    #[test]
+    fn test_queue_sort_panic_safety() {
+        // Panics at every possible comparison, in every pass.
+        for panic_at in 0..40 {
+            let drops = Rc::new(Cell::new(0));
+            let mut queue: Queue<Tracked> = [5, 3, 8, 1, 9, 2, 7, 4, 6, 0, 3]
+                .into_iter()
+                .map(|v| Tracked(v, drops.clone()))
+                .collect();
+            let mut comparisons = 0;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                queue.sort_by(|a, b| {
+                    comparisons += 1;
+                    assert!(comparisons != panic_at, "comparator panicked");
+                    a.0.cmp(&b.0)
+                })
+            }));
+            if result.is_ok() {
+                assert!(queue.iter().map(|t| t.0).is_sorted());
+            }
+            // No node is lost or duplicated, and the tail is the last node.
+            assert_eq!(queue.len(), 11);
+            let mut values: Vec<u32> = queue.iter().map(|t| t.0).collect();
+            values.sort();
+            assert_eq!(values, [0, 1, 2, 3, 3, 4, 5, 6, 7, 8, 9]);
+            queue.enqueue(Tracked(10, drops.clone()));
+            assert_eq!(queue.iter().last().map(|t| t.0), Some(10));
+            assert_eq!(drops.get(), 0);
+            drop(queue);
+            assert_eq!(drops.get(), 12);
+        }
+
+        // sort panics on NaN.
+        let mut queue = Queue::from_iter([1.0, f64::NAN, 0.0]);
+        assert!(catch_unwind(AssertUnwindSafe(|| queue.sort())).is_err());
+        assert_eq!(queue.len(), 3);
+        queue.enqueue(2.0);
+        assert_eq!(queue.iter().last(), Some(&2.0));
+    }
 }
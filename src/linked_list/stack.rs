@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{cmp::Ordering, fmt::Debug};
 
 /// A stack is a LIFO data structure that you can push a item onto it
 /// or pop it.
@@ -143,7 +143,7 @@ impl<T> Stack<T> {
     /// assert_eq!(iterator.next(), Some(&1));
     /// assert_eq!(iterator.next(), None);
     /// ```
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             next: self.head.as_deref(),
             length: self.length,
@@ -168,7 +168,7 @@ impl<T> Stack<T> {
     /// assert_eq!(stack.pop(), Some(4));
     /// assert_eq!(stack.pop(), Some(2));
     /// ```
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             next: self.head.as_deref_mut(),
             length: self.length,
@@ -179,6 +179,142 @@ impl<T> Stack<T> {
     pub fn is_empty(&self) -> bool {
         self.length == 0 
     }
+
+    /// Sorts the stack so the smallest element is on the top.
+    ///
+    /// It is a bottom-up merge sort that relinks the nodes in place, so it runs
+    /// in **O (N log N)**, does not allocate and does not move any element. The sort
+    /// is stable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two elements can not be compared, like a NaN. If a comparison
+    /// panics the stack keeps all its elements, in an unspecified order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use algo_front_end_masters::linked_list::Stack;
+    /// let mut stack = Stack::from_iter([3, 1, 2]);
+    /// stack.sort();
+    /// assert_eq!(stack.pop(), Some(1));
+    /// assert_eq!(stack.pop(), Some(2));
+    /// assert_eq!(stack.pop(), Some(3));
+    /// ```
+    pub fn sort(&mut self)
+    where
+        T: PartialOrd,
+    {
+        self.sort_by(|a, b| a.partial_cmp(b).unwrap())
+    }
+
+    /// Same as [`Stack::sort`] but orders the elements with a comparator function.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use algo_front_end_masters::linked_list::Stack;
+    /// let mut stack = Stack::from_iter([3, 1, 2]);
+    /// stack.sort_by(|a, b| b.cmp(a));
+    /// assert_eq!(stack.pop(), Some(3));
+    /// ```
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // Each pass merges pairs of sorted runs of 'width' nodes.
+        let mut width = 1;
+        while width < self.length {
+            let mut pass = Pass {
+                rest: self.head.take(),
+                stack: self,
+                left: None,
+                right: None,
+            };
+            let Pass { stack, left, right, rest } = &mut pass;
+            // The end of the part of the list that was already merged in this pass.
+            let mut tail = &mut stack.head;
+            while rest.is_some() {
+                // Cut the next two runs from the rest of the list.
+                *left = rest.take();
+                *right = Self::split(left, width);
+                *rest = Self::split(right, width);
+                tail = Self::merge(left, right, tail, &mut compare);
+            }
+            width *= 2;
+        }
+    }
+
+    /// Cuts the list after `len` nodes and returns the rest.
+    fn split(list: &mut Link<T>, len: usize) -> Link<T> {
+        let mut cursor = list;
+        for _ in 0..len {
+            match cursor {
+                Some(node) => cursor = &mut node.next,
+                None => return None,
+            }
+        }
+        cursor.take()
+    }
+
+    /// Moves the nodes of two sorted lists to `tail`, on ties the node from `left`
+    /// goes first, and returns the new end of the list.
+    fn merge<'a, F>(
+        left: &mut Link<T>,
+        right: &mut Link<T>,
+        mut tail: &'a mut Link<T>,
+        compare: &mut F,
+    ) -> &'a mut Link<T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        while let (Some(l), Some(r)) = (&*left, &*right) {
+            // A panic here leaves both lists untouched.
+            let source = if compare(&r.elem, &l.elem) == Ordering::Less {
+                &mut *right
+            } else {
+                &mut *left
+            };
+            // Unlink the first node of 'source' and link it to the tail.
+            let mut node = source.take().unwrap();
+            *source = node.next.take();
+            tail = &mut tail.insert(node).next;
+        }
+        // One of the lists is empty, the other one is already sorted.
+        *tail = if left.is_some() { left.take() } else { right.take() };
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+        tail
+    }
+}
+
+/// A pass of [`Stack::sort_by`]. The merged nodes are linked to the stack as
+/// they are merged, and dropping it links the lists below after them, so the
+/// stack keeps every node even if the comparator panics.
+struct Pass<'a, T> {
+    stack: &'a mut Stack<T>,
+    // The two sorted runs being merged.
+    left: Link<T>,
+    right: Link<T>,
+    // The nodes not reached yet in this pass.
+    rest: Link<T>,
+}
+
+impl<T> Drop for Pass<'_, T> {
+    fn drop(&mut self) {
+        // When the pass ends normally all the lists are empty.
+        if self.left.is_none() && self.right.is_none() && self.rest.is_none() {
+            return;
+        }
+        let mut tail = &mut self.stack.head;
+        for list in [self.left.take(), self.right.take(), self.rest.take()] {
+            while let Some(node) = tail {
+                tail = &mut node.next;
+            }
+            *tail = list;
+        }
+    }
 }
 
 impl<T> Default for Stack<T> {
@@ -301,8 +437,21 @@ impl<T> Drop for Stack<T> {
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+
     use super::Stack;
 
+    /// Counts its drops.
+    struct Tracked(u32, Rc<Cell<usize>>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+
     #[test]
     fn test_stack() {
         let mut stack = Stack::new();
@@ -361,4 +510,64 @@ mod tests {
         stack_b.push(4);
         assert!(stack_b > stack_a);
     }
+
+    #[test]
+    fn test_stack_sort() {
+        let mut stack: Stack<u32> = (0..1000).map(|i| (i * 7919) % 1009).collect();
+        let mut expected: Vec<u32> = stack.iter().copied().collect();
+        expected.sort();
+        stack.sort();
+        assert_eq!(stack.len(), 1000);
+        assert!(stack.iter().eq(expected.iter()));
+
+        // Stable: equal keys keep the order they had from the top.
+        let mut pairs = Stack::new();
+        for pair in [(1, 'd'), (0, 'c'), (1, 'b'), (0, 'a')] {
+            pairs.push(pair);
+        }
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(pairs.into_iter().collect::<Vec<_>>(), [(0, 'a'), (0, 'c'), (1, 'b'), (1, 'd')]);
+
+        let mut empty = Stack::<i32>::new();
+        empty.sort();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_stack_sort_panic_safety() {
+        // Panics at every possible comparison, in every pass.
+        for panic_at in 0..40 {
+            let drops = Rc::new(Cell::new(0));
+            let mut stack: Stack<Tracked> = [5, 3, 8, 1, 9, 2, 7, 4, 6, 0, 3]
+                .into_iter()
+                .map(|v| Tracked(v, drops.clone()))
+                .collect();
+            let mut comparisons = 0;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                stack.sort_by(|a, b| {
+                    comparisons += 1;
+                    assert!(comparisons != panic_at, "comparator panicked");
+                    a.0.cmp(&b.0)
+                })
+            }));
+            if result.is_ok() {
+                assert!(stack.iter().map(|t| t.0).is_sorted());
+            }
+            // No node is lost or duplicated, and the length still matches.
+            assert_eq!(stack.len(), 11);
+            assert_eq!(stack.iter().count(), 11);
+            let mut values: Vec<u32> = stack.iter().map(|t| t.0).collect();
+            values.sort();
+            assert_eq!(values, [0, 1, 2, 3, 3, 4, 5, 6, 7, 8, 9]);
+            assert_eq!(drops.get(), 0);
+            drop(stack);
+            assert_eq!(drops.get(), 11);
+        }
+
+        // sort panics on NaN.
+        let mut stack = Stack::from_iter([1.0, f64::NAN, 0.0, 2.0]);
+        assert!(catch_unwind(AssertUnwindSafe(|| stack.sort())).is_err());
+        assert_eq!(stack.len(), 4);
+        assert_eq!(stack.iter().count(), 4);
+    }
 }
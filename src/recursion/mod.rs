@@ -47,7 +47,7 @@ pub struct Maze {
 impl Debug for Maze {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for row in &self.maze {
-            write!(f, "{:?}\n", row)?
+            writeln!(f, "{:?}", row)?
         }
         Ok(())
     }
@@ -69,8 +69,8 @@ impl Maze {
             return Err(MazeError::IncompatibleTile(*incompatible).into());
         }
         for (i, row) in maze_tile.iter().enumerate() {
-            for j in 0..row.len() {
-                match row[j] {
+            for (j, tile) in row.iter().enumerate() {
+                match tile {
                     MazeTile::End => end = (i, j),
                     MazeTile::Start => start = (i, j),
                     _ => {}
@@ -93,7 +93,7 @@ impl Maze {
         Ok(())
     }
 
-    fn walk(&self, path: &mut Vec<Point>, curr: &mut Point) -> bool {
+    fn walk(&self, _path: &mut [Point], curr: &mut Point) -> bool {
         if *curr < (0, 0) || *curr > self.limit {
             return false
         }
@@ -116,8 +116,6 @@ impl From<char> for MazeTile {
 
 #[cfg(test)]
 mod tests {
-    use crate::recursion::Point;
-
     use super::Maze;
 
     #[test]
@@ -1,4 +1,6 @@
-/// This function take two arguments: an ordered slice and needle T,
+use std::cmp::Ordering;
+
+/// This function take two arguments: an ordered slice and a reference to a needle T,
 /// returning true if the slice contains the needle.
 ///
 /// # Examples
@@ -7,9 +9,83 @@
 /// # use algo_front_end_masters::search::binary_search;
 /// let arr: [u32; 5] = [1, 2, 3, 4, 5];
 /// let needle = 2;
-/// assert!(binary_search(&arr, needle) == true);
+/// assert!(binary_search(&arr, &needle) == true);
+/// ```
+pub fn binary_search<T: PartialOrd>(haystack: &[T], needle: &T) -> bool {
+    binary_search_index(haystack, needle).is_ok()
+}
+
+/// Same as [`binary_search`] but with a function that compares an element of the
+/// slice with the needle, returning `Less` if the element is smaller than it.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::binary_search_by;
+/// let arr = ["a", "bb", "ccc"];
+/// assert!(binary_search_by(&arr, |e| e.len().cmp(&2)));
+/// ```
+pub fn binary_search_by<T, F>(haystack: &[T], f: F) -> bool
+where
+    F: FnMut(&T) -> Ordering,
+{
+    binary_search_index_by(haystack, f).is_ok()
+}
+
+/// Same as [`binary_search`] but compares a key extracted from each element, the
+/// slice must be sorted by that key.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::binary_search_by_key;
+/// let arr = [(1, 'a'), (3, 'b'), (7, 'c')];
+/// assert!(binary_search_by_key(&arr, &3, |&(n, _)| n));
+/// assert!(!binary_search_by_key(&arr, &4, |&(n, _)| n));
+/// ```
+pub fn binary_search_by_key<T, B, F>(haystack: &[T], key: &B, f: F) -> bool
+where
+    B: PartialOrd,
+    F: FnMut(&T) -> B,
+{
+    binary_search_index_by_key(haystack, key, f).is_ok()
+}
+
+/// Binary searches an ordered slice for a needle like the std `binary_search`.
+///
+/// Returns `Ok` with the index of a matching element, or `Err` with the index where
+/// the needle could be inserted keeping the slice ordered. If there are multiple
+/// matches any one of them can be returned.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::binary_search_index;
+/// let mut arr = vec![1, 3, 5, 7];
+/// assert_eq!(binary_search_index(&arr, &5), Ok(2));
+/// let idx = binary_search_index(&arr, &4).unwrap_err();
+/// assert_eq!(idx, 2);
+/// arr.insert(idx, 4);
+/// assert_eq!(arr, [1, 3, 4, 5, 7]);
 /// ```
-pub fn binary_search<T: PartialOrd>(haystack: &[T], needle: T) -> bool {
+pub fn binary_search_index<T: PartialOrd>(haystack: &[T], needle: &T) -> Result<usize, usize> {
+    binary_search_index_by(haystack, |e| e.partial_cmp(needle).unwrap())
+}
+
+/// Same as [`binary_search_index`] but with a function that compares an element of
+/// the slice with the needle.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::binary_search_index_by;
+/// let arr = [1.0_f64, 2.5, 4.0];
+/// assert_eq!(binary_search_index_by(&arr, |e| e.total_cmp(&3.0)), Err(2));
+/// ```
+pub fn binary_search_index_by<T, F>(haystack: &[T], mut f: F) -> Result<usize, usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
     // the lower bound of the binary search
     let mut low = 0;
     // The upper bound of the binary search
@@ -18,33 +94,73 @@ pub fn binary_search<T: PartialOrd>(haystack: &[T], needle: T) -> bool {
     let mut mid: usize;
     // While the lower bound is smaller than the upper bound, this loop
     // runs. If low has the same value of high, it means that the slice
-    // do not contain the needle and we return false.
+    // do not contain the needle, and low is where it would be.
     while low < high {
         // Calculate the mid index
         mid = low + (high - low) / 2;
         // Compare the element positioned in middle of the slice with the needle
-        match haystack[mid].partial_cmp(&needle).unwrap() {
-            // Return true if equal
-            std::cmp::Ordering::Equal => return true,
+        match f(&haystack[mid]) {
+            // Return the index if equal
+            Ordering::Equal => return Ok(mid),
             // If the element is smaller than the needle, we make the lower bound equal to
             // the mid index + 1, so we will discard everything to the left of the mid.
-            std::cmp::Ordering::Less => low = mid + 1,
+            Ordering::Less => low = mid + 1,
             // if the element is greater than the needle, we discard everything to the right of
             // the element by making the upper bound equal to the mid index.
-            std::cmp::Ordering::Greater => high = mid,
+            Ordering::Greater => high = mid,
         }
     }
-    false
+    Err(low)
+}
+
+/// Same as [`binary_search_index`] but compares a key extracted from each element,
+/// the slice must be sorted by that key.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::binary_search_index_by_key;
+/// let arr = [(1, 'a'), (3, 'b'), (7, 'c')];
+/// assert_eq!(binary_search_index_by_key(&arr, &7, |&(n, _)| n), Ok(2));
+/// ```
+pub fn binary_search_index_by_key<T, B, F>(
+    haystack: &[T],
+    key: &B,
+    mut f: F,
+) -> Result<usize, usize>
+where
+    B: PartialOrd,
+    F: FnMut(&T) -> B,
+{
+    binary_search_index_by(haystack, |e| f(e).partial_cmp(key).unwrap())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::binary_search;
+    use super::{binary_search, binary_search_index, binary_search_index_by_key};
 
     #[test]
     fn test_binary_search() {
         let arr: Vec<i32> = (0..=10).collect();
-        assert!(binary_search(&arr, 9));
-        assert!(!binary_search(&arr, 12));
+        assert!(binary_search(&arr, &9));
+        assert!(!binary_search(&arr, &12));
+    }
+
+    #[test]
+    fn test_binary_search_index() {
+        let arr = [1, 3, 5, 7, 9];
+        for (i, e) in arr.iter().enumerate() {
+            assert_eq!(binary_search_index(&arr, e), Ok(i));
+            assert_eq!(binary_search_index(&arr, &(e - 1)), Err(i));
+        }
+        assert_eq!(binary_search_index(&arr, &10), Err(5));
+        assert_eq!(binary_search_index(&[], &1), Err(0));
+
+        // Sorted by length, the key.
+        let words = ["fig", "kiwi", "apple", "banana"];
+        assert_eq!(binary_search_index_by_key(&words, &6, |w| w.len()), Ok(3));
+        assert_eq!(binary_search_index_by_key(&words, &3, |w| w.len()), Ok(0));
+        assert_eq!(binary_search_index_by_key(&words, &2, |w| w.len()), Err(0));
+        assert_eq!(binary_search_index_by_key(&words, &7, |w| w.len()), Err(4));
     }
 }
@@ -0,0 +1,190 @@
+/// Integer types that can be bisected by [`bisect_int`].
+pub trait BisectInt: Copy + Ord {
+    /// The average of two values rounded down, without overflowing.
+    fn floor_midpoint(self, other: Self) -> Self;
+    /// The next value, it is never called on the biggest one.
+    fn plus_one(self) -> Self;
+}
+
+macro_rules! impl_bisect_int {
+    ($($t:ty),*) => {
+        $(impl BisectInt for $t {
+            fn floor_midpoint(self, other: Self) -> Self {
+                // The common bits plus half of the different ones, the shift is
+                // arithmetic on signed types so it rounds down for them too.
+                (self & other) + ((self ^ other) >> 1)
+            }
+
+            fn plus_one(self) -> Self {
+                self + 1
+            }
+        })*
+    };
+}
+
+impl_bisect_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// This function finds the smallest value in `[lo, hi]` for which `pred` returns
+/// true, or `None` if there is none.
+///
+/// Instead of searching a slice, it binary searches a range of values, which is
+/// useful when the answer can be checked but not computed directly, like "the
+/// smallest capacity that can hold everything". `pred` must be monotone: false up
+/// to some value and true from it on. It is called **O (log (hi - lo))** times, and
+/// both ends can be the minimum and maximum of the type without overflowing.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::bisect_int;
+/// // The smallest truck capacity to ship every package in 3 trips, keeping their order.
+/// let packages = [7, 2, 5, 10, 8];
+/// let trips = |capacity: u32| {
+///     let (mut trips, mut load) = (1, 0);
+///     for &p in &packages {
+///         if load + p > capacity {
+///             trips += 1;
+///             load = 0;
+///         }
+///         load += p;
+///     }
+///     trips
+/// };
+/// assert_eq!(bisect_int(10, 32, |c| trips(c) <= 3), Some(14));
+/// assert_eq!(bisect_int(i64::MIN, i64::MAX, |x| x >= -5), Some(-5));
+/// assert_eq!(bisect_int(0, u64::MAX, |_| false), None);
+/// ```
+pub fn bisect_int<T, F>(mut lo: T, mut hi: T, mut pred: F) -> Option<T>
+where
+    T: BisectInt,
+    F: FnMut(T) -> bool,
+{
+    if lo > hi {
+        return None;
+    }
+    // True once 'hi' is a value that was checked.
+    let mut found = false;
+    while lo < hi {
+        let mid = lo.floor_midpoint(hi);
+        if pred(mid) {
+            hi = mid;
+            found = true;
+        } else {
+            // 'mid' is smaller than 'hi', so this can not overflow.
+            lo = mid.plus_one();
+        }
+    }
+    (found || pred(hi)).then_some(hi)
+}
+
+/// This function finds the smallest value in `[lo, hi]` for which `pred` returns
+/// true, up to `eps`, or `None` if `pred(hi)` is false.
+///
+/// `pred` must be monotone: false up to some value and true from it on. The value
+/// returned always makes `pred` true and is at most `eps` after the real
+/// boundary. With an `eps` of 0 the search goes on until there is no float left
+/// between both ends, so it is as close as an `f64` can be.
+///
+/// # Panics
+///
+/// Panics if `eps` is negative or NaN, or if an end is NaN.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::bisect_float;
+/// let sqrt_2 = bisect_float(0.0, 2.0, 1e-12, |x| x * x >= 2.0).unwrap();
+/// assert!((sqrt_2 - 2.0_f64.sqrt()).abs() <= 1e-12);
+/// assert_eq!(bisect_float(f64::MIN, f64::MAX, 0.0, |x| x >= 0.0), Some(0.0));
+/// ```
+pub fn bisect_float<F>(mut lo: f64, mut hi: f64, eps: f64, mut pred: F) -> Option<f64>
+where
+    F: FnMut(f64) -> bool,
+{
+    assert!(eps >= 0.0, "eps must be a positive number");
+    assert!(!lo.is_nan() && !hi.is_nan(), "the ends can not be NaN");
+    if lo > hi || !pred(hi) {
+        return None;
+    }
+    if pred(lo) {
+        return Some(lo);
+    }
+    // 'pred(lo)' is false and 'pred(hi)' true. The width is halved before the
+    // subtraction so it does not overflow to infinity.
+    while hi / 2.0 - lo / 2.0 > eps / 2.0 {
+        // Halving each end first so it can not overflow either.
+        let mid = lo / 2.0 + hi / 2.0;
+        // Stop when there is no float between both ends.
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    Some(hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bisect_float, bisect_int};
+
+    #[test]
+    fn test_bisect_int() {
+        for lo in -5..5 {
+            for hi in -5..5 {
+                for first in -7..7 {
+                    let expected = (lo..=hi).find(|&x| x >= first);
+                    assert_eq!(bisect_int(lo, hi, |x: i8| x >= first), expected);
+                }
+            }
+        }
+        assert_eq!(bisect_int(0, u64::MAX, |x| x == u64::MAX), Some(u64::MAX));
+        assert_eq!(bisect_int(0, u64::MAX, |_| true), Some(0));
+        assert_eq!(
+            bisect_int(i64::MIN, i64::MAX, |x| x == i64::MAX),
+            Some(i64::MAX)
+        );
+        assert_eq!(bisect_int(i64::MIN, i64::MAX, |_| true), Some(i64::MIN));
+        assert_eq!(bisect_int(i64::MIN, i64::MAX, |_| false), None);
+        assert_eq!(
+            bisect_int(u128::MIN, u128::MAX, |x| x > 1 << 100),
+            Some((1 << 100) + 1)
+        );
+        // The smallest integer square root bigger or equal to it.
+        assert_eq!(
+            bisect_int(0_u64, 1 << 32, |x| x * x >= 1_000_001),
+            Some(1001)
+        );
+
+        let mut calls = 0;
+        bisect_int(0, u64::MAX, |x| {
+            calls += 1;
+            x > 12345
+        });
+        assert!(calls <= 65);
+    }
+
+    #[test]
+    fn test_bisect_float() {
+        let cube_root = bisect_float(-10.0, 10.0, 1e-9, |x| x * x * x >= -27.0).unwrap();
+        assert!(cube_root >= -3.0 && cube_root - 1e-9 <= -3.0);
+        assert_eq!(bisect_float(0.0, 1.0, 0.1, |_| true), Some(0.0));
+        assert_eq!(bisect_float(0.0, 1.0, 0.1, |_| false), None);
+        assert_eq!(bisect_float(1.0, 0.0, 0.1, |_| true), None);
+        assert_eq!(
+            bisect_float(f64::MIN, f64::MAX, 0.0, |x| x >= f64::MAX),
+            Some(f64::MAX)
+        );
+        let exact = bisect_float(0.0, 1.0, 0.0, |x| x >= 0.1).unwrap();
+        assert_eq!(exact, 0.1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bisect_float_nan_eps() {
+        bisect_float(0.0, 1.0, f64::NAN, |_| true);
+    }
+}
@@ -0,0 +1,216 @@
+use super::edit_distance::bounded_levenshtein;
+
+/// A BK-tree of words, to find the words that are close to another one by
+/// [`levenshtein`](super::levenshtein) distance without checking all of them.
+///
+/// Each child of a node is at a different distance from it. As the distance
+/// obeys the triangle inequality, the words within `d` edits of a query `q` can
+/// only be in the children whose distance to the node is within `d` of the
+/// distance between the node and `q`, so most of the tree is skipped when `d` is
+/// small.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::BkTree;
+/// let tree: BkTree<&str> = ["book", "books", "cake", "boo", "cape", "cart"].into_iter().collect();
+/// let mut found: Vec<_> = tree.find("bo", 2).into_iter().map(|(w, d)| (*w, d)).collect();
+/// found.sort();
+/// assert_eq!(found, [("boo", 1), ("book", 2)]);
+/// assert_eq!(tree.nearest("capr"), Some((&"cape", 1)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BkTree<S> {
+    // The root is the node 0.
+    nodes: Vec<Node<S>>,
+}
+
+#[derive(Debug, Clone)]
+struct Node<S> {
+    word: S,
+    chars: Vec<char>,
+    // The distance to each child and its node.
+    children: Vec<(usize, usize)>,
+}
+
+impl<S: AsRef<str>> BkTree<S> {
+    pub fn new() -> Self {
+        BkTree { nodes: Vec::new() }
+    }
+
+    /// Adds a word to the tree, returning false if it was already in it.
+    pub fn insert(&mut self, word: S) -> bool {
+        let chars: Vec<char> = word.as_ref().chars().collect();
+        let new = self.nodes.len();
+        if self.nodes.is_empty() {
+            self.nodes.push(Node {
+                word,
+                chars,
+                children: Vec::new(),
+            });
+            return true;
+        }
+        // Go down through the child at the same distance, until there is none.
+        let mut node = 0;
+        loop {
+            let distance = distance(&self.nodes[node].chars, &chars);
+            if distance == 0 {
+                return false;
+            }
+            match self.nodes[node]
+                .children
+                .iter()
+                .find(|&&(d, _)| d == distance)
+            {
+                Some(&(_, child)) => node = child,
+                None => {
+                    self.nodes[node].children.push((distance, new));
+                    break;
+                }
+            }
+        }
+        self.nodes.push(Node {
+            word,
+            chars,
+            children: Vec::new(),
+        });
+        true
+    }
+
+    /// Returns the words that are at most `max_edits` edits away from the query,
+    /// with their distance, in no particular order.
+    pub fn find<Q: AsRef<str> + ?Sized>(&self, query: &Q, max_edits: usize) -> Vec<(&S, usize)> {
+        let query: Vec<char> = query.as_ref().chars().collect();
+        let mut found = Vec::new();
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            let distance = distance(&node.chars, &query);
+            if distance <= max_edits {
+                found.push((&node.word, distance));
+            }
+            // Only the children within 'max_edits' of 'distance' can be close.
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|&&(d, _)| d.abs_diff(distance) <= max_edits)
+                    .map(|&(_, child)| child),
+            );
+        }
+        found
+    }
+
+    /// Returns the word that is closest to the query, with its distance, or `None`
+    /// if the tree is empty.
+    pub fn nearest<Q: AsRef<str> + ?Sized>(&self, query: &Q) -> Option<(&S, usize)> {
+        let query: Vec<char> = query.as_ref().chars().collect();
+        let mut best: Option<(usize, usize)> = None;
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            // The exact distance is needed even if it is not the best, to know
+            // which children can be closer.
+            let distance = distance(&node.chars, &query);
+            if best.is_none_or(|(_, d)| distance < d) {
+                best = Some((index, distance));
+            }
+            // Only the children that can be closer than the best.
+            let limit = best.map_or(usize::MAX, |(_, d)| d);
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|&&(d, _)| d.abs_diff(distance) < limit)
+                    .map(|&(_, child)| child),
+            );
+        }
+        best.map(|(index, d)| (&self.nodes[index].word, d))
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+fn distance(a: &[char], b: &[char]) -> usize {
+    bounded_levenshtein(a, b, usize::MAX).unwrap()
+}
+
+impl<S: AsRef<str>> Default for BkTree<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for BkTree<S> {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut tree = BkTree::new();
+        for word in iter {
+            tree.insert(word);
+        }
+        tree
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for BkTree<S> {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for word in iter {
+            self.insert(word);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BkTree;
+    use crate::search::levenshtein;
+
+    #[test]
+    fn test_bk_tree() {
+        // Every string of 'a', 'b' and 'c' up to 4 chars.
+        let mut words = vec![String::new()];
+        for _ in 0..4 {
+            let longer: Vec<String> = words
+                .iter()
+                .flat_map(|w| ["a", "b", "c"].map(|c| format!("{w}{c}")))
+                .collect();
+            words.extend(longer);
+        }
+        words.sort();
+        words.dedup();
+        let tree: BkTree<String> = words.iter().step_by(3).cloned().collect();
+        assert_eq!(tree.len(), words.len().div_ceil(3));
+        assert!(!tree.clone().insert(words[0].clone()));
+        for query in ["", "abc", "cccc", "abcabc", "bab"] {
+            for max in 0..4 {
+                let mut found: Vec<(String, usize)> = tree
+                    .find(query, max)
+                    .into_iter()
+                    .map(|(w, d)| (w.clone(), d))
+                    .collect();
+                found.sort();
+                let expected: Vec<(String, usize)> = words
+                    .iter()
+                    .step_by(3)
+                    .map(|w| (w.clone(), levenshtein(w, query)))
+                    .filter(|&(_, d)| d <= max)
+                    .collect();
+                assert_eq!(found, expected);
+            }
+            let closest = words.iter().step_by(3).map(|w| levenshtein(w, query)).min();
+            assert_eq!(tree.nearest(query).map(|(_, d)| d), closest);
+        }
+        assert_eq!(BkTree::<&str>::new().nearest("a"), None);
+    }
+}
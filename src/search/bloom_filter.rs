@@ -0,0 +1,418 @@
+use std::f64::consts::LN_2;
+
+use super::filter::{hash_with_seed, FilterError, FilterKey, Reader};
+
+/// A Bloom filter, a set that can tell if an item is definitely not in it or
+/// probably in it, using only a few bits per item.
+///
+/// Each item sets `k` bits of a bit array, chosen by hashing it. An item that was
+/// inserted always finds its bits set, but an item that was not can find them set
+/// by other items, which is a false positive. The more bits per item, the fewer
+/// false positives. Items can not be removed, see [`CountingBloomFilter`].
+///
+/// It is useful as a pre-check before an expensive search: if the filter says no,
+/// the search can be skipped.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::BloomFilter;
+/// let mut filter = BloomFilter::with_rate(1000, 0.01);
+/// filter.insert("apple");
+/// filter.insert("banana");
+/// assert!(filter.contains("apple"));
+/// // Probably false, but it can be a false positive 1% of the time.
+/// let _ = filter.contains("cherry");
+///
+/// let stored = filter.to_bytes();
+/// assert_eq!(BloomFilter::from_bytes(&stored).unwrap(), filter);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    words: Vec<u64>,
+    bits: usize,
+    hashes: u32,
+    items: usize,
+}
+
+impl BloomFilter {
+    const MAGIC: &'static [u8; 4] = b"BLM1";
+
+    /// Creates a filter with `bits` bits that sets `hashes` bits for each item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` or `hashes` is 0.
+    pub fn new(bits: usize, hashes: u32) -> Self {
+        assert!(
+            bits > 0 && hashes > 0,
+            "bits and hashes must be bigger than 0"
+        );
+        BloomFilter {
+            words: vec![0; bits.div_ceil(64)],
+            bits,
+            hashes,
+            items: 0,
+        }
+    }
+
+    /// Creates a filter sized to have a false positive rate of
+    /// `false_positive_rate` once `expected_items` are inserted.
+    ///
+    /// It uses `-n ln p / ln² 2` bits and `(bits / n) ln 2` hashes, which is about
+    /// 9.6 bits and 7 hashes per item for a rate of 1%.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate is not between 0 and 1.
+    pub fn with_rate(expected_items: usize, false_positive_rate: f64) -> Self {
+        let (bits, hashes) = optimal_size(expected_items, false_positive_rate);
+        BloomFilter::new(bits, hashes)
+    }
+
+    pub fn insert<T: FilterKey + ?Sized>(&mut self, item: &T) {
+        for bit in indices(item, self.bits, self.hashes) {
+            self.words[bit / 64] |= 1 << (bit % 64);
+        }
+        self.items += 1;
+    }
+
+    /// Returns false if the item was never inserted, true if it probably was.
+    pub fn contains<T: FilterKey + ?Sized>(&self, item: &T) -> bool {
+        indices(item, self.bits, self.hashes)
+            .all(|bit| self.words[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Returns the expected false positive rate for the items inserted so far.
+    pub fn false_positive_rate(&self) -> f64 {
+        false_positive_rate(self.bits, self.hashes, self.items)
+    }
+
+    /// Returns how many times `insert` was called.
+    pub fn len(&self) -> usize {
+        self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+        self.items = 0;
+    }
+
+    /// Serializes the filter, it can be read back with [`BloomFilter::from_bytes`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(24 + self.words.len() * 8);
+        bytes.extend_from_slice(Self::MAGIC);
+        bytes.extend_from_slice(&(self.bits as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.hashes.to_le_bytes());
+        bytes.extend_from_slice(&(self.items as u64).to_le_bytes());
+        for word in &self.words {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FilterError> {
+        let mut reader = Reader::new(bytes, Self::MAGIC, "Bloom filter")?;
+        let bits = reader.len()?;
+        let hashes = u32::from_le_bytes(reader.take(4)?.try_into().unwrap());
+        let items = reader.len()?;
+        if bits == 0 || hashes == 0 {
+            return Err(FilterError::Invalid(
+                "bits and hashes must be bigger than 0",
+            ));
+        }
+        let words = (0..bits.div_ceil(64))
+            .map(|_| reader.u64())
+            .collect::<Result<_, _>>()?;
+        reader.finish()?;
+        Ok(BloomFilter {
+            words,
+            bits,
+            hashes,
+            items,
+        })
+    }
+}
+
+/// A Bloom filter that keeps a counter instead of a bit, so items can be removed.
+///
+/// Each counter takes a byte instead of a bit. A counter that reaches 255 stays at
+/// 255, so removing can never cause a false negative, but that position will
+/// never be cleared again.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::CountingBloomFilter;
+/// let mut filter = CountingBloomFilter::with_rate(100, 0.001);
+/// filter.insert(&42);
+/// assert!(filter.contains(&42));
+/// assert!(filter.remove(&42));
+/// assert!(!filter.contains(&42));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountingBloomFilter {
+    counters: Vec<u8>,
+    hashes: u32,
+    items: usize,
+}
+
+impl CountingBloomFilter {
+    const MAGIC: &'static [u8; 4] = b"CBF1";
+
+    /// Creates a filter with `counters` counters that uses `hashes` of them for
+    /// each item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `counters` or `hashes` is 0.
+    pub fn new(counters: usize, hashes: u32) -> Self {
+        assert!(
+            counters > 0 && hashes > 0,
+            "counters and hashes must be bigger than 0"
+        );
+        CountingBloomFilter {
+            counters: vec![0; counters],
+            hashes,
+            items: 0,
+        }
+    }
+
+    /// Same as [`BloomFilter::with_rate`], with a counter for each bit.
+    pub fn with_rate(expected_items: usize, false_positive_rate: f64) -> Self {
+        let (counters, hashes) = optimal_size(expected_items, false_positive_rate);
+        CountingBloomFilter::new(counters, hashes)
+    }
+
+    pub fn insert<T: FilterKey + ?Sized>(&mut self, item: &T) {
+        for i in indices(item, self.counters.len(), self.hashes) {
+            self.counters[i] = self.counters[i].saturating_add(1);
+        }
+        self.items += 1;
+    }
+
+    /// Returns false if the item was never inserted, true if it probably was.
+    pub fn contains<T: FilterKey + ?Sized>(&self, item: &T) -> bool {
+        indices(item, self.counters.len(), self.hashes).all(|i| self.counters[i] > 0)
+    }
+
+    /// Removes an item, returning false if it was not in the filter.
+    ///
+    /// Only items that were inserted must be removed. Removing an item that was
+    /// never inserted corrupts the filter: if it is a false positive, or if its
+    /// counters are saturated, it decrements the counters of other items, which
+    /// can cause false negatives, and [`CountingBloomFilter::len`] undercounts,
+    /// down to 0.
+    pub fn remove<T: FilterKey + ?Sized>(&mut self, item: &T) -> bool {
+        if !self.contains(item) {
+            return false;
+        }
+        for i in indices(item, self.counters.len(), self.hashes) {
+            // A saturated counter lost count of its items, leave it.
+            if self.counters[i] < u8::MAX {
+                self.counters[i] -= 1;
+            }
+        }
+        self.items = self.items.saturating_sub(1);
+        true
+    }
+
+    /// Returns the expected false positive rate for the items in the filter.
+    pub fn false_positive_rate(&self) -> f64 {
+        false_positive_rate(self.counters.len(), self.hashes, self.items)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items == 0
+    }
+
+    /// Serializes the filter, it can be read back with
+    /// [`CountingBloomFilter::from_bytes`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(24 + self.counters.len());
+        bytes.extend_from_slice(Self::MAGIC);
+        bytes.extend_from_slice(&(self.counters.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.hashes.to_le_bytes());
+        bytes.extend_from_slice(&(self.items as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.counters);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FilterError> {
+        let mut reader = Reader::new(bytes, Self::MAGIC, "counting Bloom filter")?;
+        let len = reader.len()?;
+        let hashes = u32::from_le_bytes(reader.take(4)?.try_into().unwrap());
+        let items = reader.len()?;
+        if len == 0 || hashes == 0 {
+            return Err(FilterError::Invalid(
+                "counters and hashes must be bigger than 0",
+            ));
+        }
+        let counters = reader.take(len)?.to_vec();
+        reader.finish()?;
+        // Each item adds at most 1 to 'hashes' counters, and each remove takes
+        // the same away.
+        let total: u128 = counters.iter().map(|&c| c as u128).sum();
+        if total > items as u128 * hashes as u128 {
+            return Err(FilterError::Invalid(
+                "the counters hold more than the number of items",
+            ));
+        }
+        Ok(CountingBloomFilter {
+            counters,
+            hashes,
+            items,
+        })
+    }
+}
+
+/// The number of bits and hashes for a false positive rate with some items.
+fn optimal_size(items: usize, rate: f64) -> (usize, u32) {
+    assert!(
+        rate > 0.0 && rate < 1.0,
+        "the false positive rate must be between 0 and 1"
+    );
+    let items = items.max(1) as f64;
+    let bits = (-items * rate.ln() / (LN_2 * LN_2)).ceil().max(1.0);
+    let hashes = (bits / items * LN_2).round().max(1.0);
+    (bits as usize, hashes as u32)
+}
+
+fn false_positive_rate(bits: usize, hashes: u32, items: usize) -> f64 {
+    // The chance that a bit is set, to the power of the bits an item checks.
+    let set = 1.0 - (-(hashes as f64) * items as f64 / bits as f64).exp();
+    set.powi(hashes as i32)
+}
+
+/// The positions of an item, from two hashes combined as `h1 + i h2`, which is as
+/// good as `k` independent hashes.
+fn indices<T: FilterKey + ?Sized>(
+    item: &T,
+    len: usize,
+    hashes: u32,
+) -> impl Iterator<Item = usize> {
+    let h1 = hash_with_seed(item, 0);
+    // Odd, so it never repeats the same position too soon.
+    let h2 = hash_with_seed(item, 1) | 1;
+    (0..hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % len as u64) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BloomFilter, CountingBloomFilter};
+    use crate::search::FilterError;
+
+    #[test]
+    fn test_bloom_filter() {
+        let mut filter = BloomFilter::with_rate(10_000, 0.01);
+        for i in 0..10_000 {
+            filter.insert(&i);
+        }
+        // No false negatives, and about 1% of false positives.
+        assert!((0..10_000).all(|i| filter.contains(&i)));
+        let false_positives = (10_000..110_000).filter(|i| filter.contains(i)).count();
+        assert!(false_positives < 1_500, "{false_positives}");
+        assert!((filter.false_positive_rate() - 0.01).abs() < 0.002);
+
+        let bytes = filter.to_bytes();
+        let read = BloomFilter::from_bytes(&bytes).unwrap();
+        assert_eq!(read, filter);
+        assert!(read.contains(&1234));
+        assert_eq!(
+            BloomFilter::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(FilterError::Truncated)
+        );
+        assert!(matches!(
+            BloomFilter::from_bytes(b"XXXX"),
+            Err(FilterError::WrongHeader(_))
+        ));
+        filter.clear();
+        assert!(filter.is_empty());
+        assert!(!filter.contains(&1));
+    }
+
+    #[test]
+    fn test_counting_bloom_filter() {
+        let mut filter = CountingBloomFilter::with_rate(1000, 0.01);
+        for word in ["a", "b", "c", "a"] {
+            filter.insert(word);
+        }
+        assert!(filter.remove("a"));
+        assert!(filter.contains("a"));
+        assert!(filter.remove("a"));
+        assert!(!filter.contains("a"));
+        assert!(!filter.remove("a"));
+        assert!(filter.contains("b") && filter.contains("c"));
+        assert_eq!(filter.len(), 2);
+
+        // Saturated counters are never cleared.
+        let mut small = CountingBloomFilter::new(1, 1);
+        for _ in 0..300 {
+            small.insert(&0);
+        }
+        for _ in 0..300 {
+            small.remove(&0);
+        }
+        assert!(small.contains(&0));
+
+        // Removing items that were never inserted does not underflow the count.
+        let mut small = CountingBloomFilter::new(1, 1);
+        for _ in 0..300 {
+            small.insert(&1_u32);
+        }
+        for _ in 0..301 {
+            assert!(small.remove(&2_u32));
+        }
+        assert_eq!(small.len(), 0);
+        assert!(small.is_empty());
+
+        let read = CountingBloomFilter::from_bytes(&filter.to_bytes()).unwrap();
+        assert_eq!(read, filter);
+        assert!(CountingBloomFilter::from_bytes(&BloomFilter::new(8, 1).to_bytes()).is_err());
+
+        // A count of items that does not match the counters is rejected.
+        let mut bytes = filter.to_bytes();
+        bytes[16..24].copy_from_slice(&0_u64.to_le_bytes());
+        assert_eq!(
+            CountingBloomFilter::from_bytes(&bytes),
+            Err(FilterError::Invalid(
+                "the counters hold more than the number of items"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_stored_bytes() {
+        // Filters stored by any platform or version must be read the same.
+        let mut filter = BloomFilter::new(64, 3);
+        filter.insert("apple");
+        filter.insert(&42_u32);
+        filter.insert(&-7_i64);
+        filter.insert(&usize::MAX);
+        assert_eq!(
+            hex(&filter.to_bytes()),
+            "424c4d314000000000000000030000000400000000000000f100200801280008"
+        );
+
+        let mut filter = CountingBloomFilter::new(16, 2);
+        filter.insert("apple");
+        filter.insert("apple");
+        filter.insert(&'x');
+        assert_eq!(
+            hex(&filter.to_bytes()),
+            "43424631100000000000000002000000030000000000000000000000000202000000010000010000"
+        );
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
@@ -0,0 +1,185 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// Returns the index of the first element of an ordered slice that is not smaller
+/// than the needle, or the length of the slice if there is none. It is the first
+/// position where the needle could be inserted keeping the slice ordered.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::lower_bound;
+/// let arr = [1, 2, 2, 2, 3];
+/// assert_eq!(lower_bound(&arr, &2), 1);
+/// assert_eq!(lower_bound(&arr, &4), 5);
+/// ```
+pub fn lower_bound<T: PartialOrd>(haystack: &[T], needle: &T) -> usize {
+    lower_bound_by(haystack, |e| e.partial_cmp(needle).unwrap())
+}
+
+/// Same as [`lower_bound`] but with a function that compares an element of the
+/// slice with the needle.
+pub fn lower_bound_by<T, F>(haystack: &[T], mut f: F) -> usize
+where
+    F: FnMut(&T) -> Ordering,
+{
+    partition_point(haystack, |e| f(e) == Ordering::Less)
+}
+
+/// Same as [`lower_bound`] but compares a key extracted from each element, the
+/// slice must be sorted by that key.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::lower_bound_by_key;
+/// let arr = [(1, 'a'), (3, 'b'), (3, 'c'), (7, 'd')];
+/// assert_eq!(lower_bound_by_key(&arr, &3, |&(n, _)| n), 1);
+/// ```
+pub fn lower_bound_by_key<T, B, F>(haystack: &[T], key: &B, mut f: F) -> usize
+where
+    B: PartialOrd,
+    F: FnMut(&T) -> B,
+{
+    lower_bound_by(haystack, |e| f(e).partial_cmp(key).unwrap())
+}
+
+/// Returns the index of the first element of an ordered slice that is bigger than
+/// the needle, or the length of the slice if there is none. It is the last position
+/// where the needle could be inserted keeping the slice ordered.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::upper_bound;
+/// let arr = [1, 2, 2, 2, 3];
+/// assert_eq!(upper_bound(&arr, &2), 4);
+/// assert_eq!(upper_bound(&arr, &0), 0);
+/// ```
+pub fn upper_bound<T: PartialOrd>(haystack: &[T], needle: &T) -> usize {
+    upper_bound_by(haystack, |e| e.partial_cmp(needle).unwrap())
+}
+
+/// Same as [`upper_bound`] but with a function that compares an element of the
+/// slice with the needle.
+pub fn upper_bound_by<T, F>(haystack: &[T], mut f: F) -> usize
+where
+    F: FnMut(&T) -> Ordering,
+{
+    partition_point(haystack, |e| f(e) != Ordering::Greater)
+}
+
+/// Same as [`upper_bound`] but compares a key extracted from each element, the
+/// slice must be sorted by that key.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::upper_bound_by_key;
+/// let arr = [(1, 'a'), (3, 'b'), (3, 'c'), (7, 'd')];
+/// assert_eq!(upper_bound_by_key(&arr, &3, |&(n, _)| n), 3);
+/// ```
+pub fn upper_bound_by_key<T, B, F>(haystack: &[T], key: &B, mut f: F) -> usize
+where
+    B: PartialOrd,
+    F: FnMut(&T) -> B,
+{
+    upper_bound_by(haystack, |e| f(e).partial_cmp(key).unwrap())
+}
+
+/// Returns the range of the elements of an ordered slice that are equal to the
+/// needle. If there is none, the range is empty and starts where the needle could
+/// be inserted.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::equal_range;
+/// let arr = [1, 2, 2, 2, 3];
+/// assert_eq!(equal_range(&arr, &2), 1..4);
+/// assert_eq!(&arr[equal_range(&arr, &2)], [2, 2, 2]);
+/// assert!(equal_range(&arr, &0).is_empty());
+/// ```
+pub fn equal_range<T: PartialOrd>(haystack: &[T], needle: &T) -> Range<usize> {
+    equal_range_by(haystack, |e| e.partial_cmp(needle).unwrap())
+}
+
+/// Same as [`equal_range`] but with a function that compares an element of the
+/// slice with the needle.
+pub fn equal_range_by<T, F>(haystack: &[T], mut f: F) -> Range<usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
+    let low = lower_bound_by(haystack, &mut f);
+    // The equal elements can only be after the lower bound.
+    let high = low + upper_bound_by(&haystack[low..], f);
+    low..high
+}
+
+/// Same as [`equal_range`] but compares a key extracted from each element, the
+/// slice must be sorted by that key.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::equal_range_by_key;
+/// let arr = [(1, 'a'), (3, 'b'), (3, 'c'), (7, 'd')];
+/// assert_eq!(equal_range_by_key(&arr, &3, |&(n, _)| n), 1..3);
+/// ```
+pub fn equal_range_by_key<T, B, F>(haystack: &[T], key: &B, mut f: F) -> Range<usize>
+where
+    B: PartialOrd,
+    F: FnMut(&T) -> B,
+{
+    equal_range_by(haystack, |e| f(e).partial_cmp(key).unwrap())
+}
+
+/// Returns the index of the first element for which the predicate is false, for a
+/// slice partitioned so that the predicate is true for every element before it
+/// and false for every element after it.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::partition_point;
+/// let arr = [1, 2, 3, 10, 20];
+/// assert_eq!(partition_point(&arr, |&e| e < 5), 3);
+/// ```
+pub fn partition_point<T, P>(haystack: &[T], mut pred: P) -> usize
+where
+    P: FnMut(&T) -> bool,
+{
+    let mut low = 0;
+    let mut high = haystack.len();
+    // The answer is always in [low, high].
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if pred(&haystack[mid]) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{equal_range, lower_bound, partition_point, upper_bound};
+
+    #[test]
+    fn test_bounds() {
+        let arr = [1, 1, 3, 3, 3, 5, 8];
+        for needle in 0..10 {
+            let lower = arr.iter().position(|&e| e >= needle).unwrap_or(arr.len());
+            let upper = arr.iter().position(|&e| e > needle).unwrap_or(arr.len());
+            assert_eq!(lower_bound(&arr, &needle), lower);
+            assert_eq!(upper_bound(&arr, &needle), upper);
+            assert_eq!(equal_range(&arr, &needle), lower..upper);
+        }
+        assert_eq!(lower_bound(&[], &1), 0);
+        assert_eq!(equal_range(&[], &1), 0..0);
+        assert_eq!(partition_point(&arr, |_| true), arr.len());
+        assert_eq!(partition_point(&arr, |_| false), 0);
+    }
+}
@@ -0,0 +1,65 @@
+/// Binary searches an ordered slice for a needle without branching on the
+/// comparisons, returning `Ok` with the index of the first matching element or
+/// `Err` with the index where the needle could be inserted.
+///
+/// A normal binary search picks the next half with an `if`, and as the CPU can
+/// not guess which half it will be, about half of those guesses are wrong and
+/// each one costs a pipeline flush. This version always halves the same way and
+/// only moves the start of the range with a select, which the compiler turns
+/// into a conditional move. It does a comparison or two more than a binary
+/// search that stops at the first match, but it is usually faster than
+/// [`binary_search_index`](super::binary_search_index).
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::branchless_binary_search;
+/// let arr = [1, 3, 3, 3, 5, 7];
+/// assert_eq!(branchless_binary_search(&arr, &3), Ok(1));
+/// assert_eq!(branchless_binary_search(&arr, &4), Err(4));
+/// assert_eq!(branchless_binary_search(&arr, &9), Err(6));
+/// ```
+pub fn branchless_binary_search<T: PartialOrd>(haystack: &[T], needle: &T) -> Result<usize, usize> {
+    if haystack.is_empty() {
+        return Err(0);
+    }
+    // Everything before 'base' is smaller than the needle, and the answer is in
+    // [base, base + size].
+    let mut base = 0;
+    let mut size = haystack.len();
+    while size > 1 {
+        let half = size / 2;
+        let mid = base + half;
+        // A select instead of a branch, 'size' shrinks the same either way.
+        base = if haystack[mid] < *needle { mid } else { base };
+        size -= half;
+    }
+    // 'base' is the last element smaller than the needle, or the first one.
+    let index = base + (haystack[base] < *needle) as usize;
+    match haystack.get(index) {
+        Some(element) if element == needle => Ok(index),
+        _ => Err(index),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::branchless_binary_search;
+    use crate::search::lower_bound;
+
+    #[test]
+    fn test_branchless_binary_search() {
+        for len in 0..70 {
+            let arr: Vec<i32> = (0..len).map(|i| i / 3 * 2).collect();
+            for needle in -1..len {
+                let expected = lower_bound(&arr, &needle);
+                let result = branchless_binary_search(&arr, &needle);
+                if arr.get(expected) == Some(&needle) {
+                    assert_eq!(result, Ok(expected));
+                } else {
+                    assert_eq!(result, Err(expected));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use super::filter::{hash_with_seed, mix, FilterError, FilterKey, Reader};
+
+const BUCKET_SIZE: usize = 4;
+// How many times an insert moves other items before giving up.
+const MAX_KICKS: usize = 500;
+
+/// A cuckoo filter, a set that can tell if an item is definitely not in it or
+/// probably in it, and that supports removing items.
+///
+/// It keeps a 16 bit fingerprint of each item in one of two buckets of 4 slots.
+/// The second bucket is the first one xor the hash of the fingerprint, so either
+/// bucket can be found from the other and the fingerprint. When both buckets are
+/// full, a random fingerprint is moved to its other bucket, like a cuckoo pushing
+/// out the eggs of a nest, which lets the filter get about 95% full. A lookup
+/// checks only 8 slots, and the false positive rate is about 8 / 2¹⁶ = 0.012%.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::CuckooFilter;
+/// let mut filter = CuckooFilter::with_capacity(1000);
+/// assert!(filter.insert("apple"));
+/// assert!(filter.contains("apple"));
+/// assert!(filter.remove("apple"));
+/// assert!(!filter.contains("apple"));
+///
+/// let stored = filter.to_bytes();
+/// assert_eq!(CuckooFilter::from_bytes(&stored).unwrap(), filter);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CuckooFilter {
+    // The fingerprints of every bucket one after the other, 0 is an empty slot.
+    slots: Vec<u16>,
+    // The number of buckets minus 1, it is a power of two.
+    mask: usize,
+    items: usize,
+    // A fingerprint that did not find a place on the last insert, and its bucket.
+    victim: Option<(usize, u16)>,
+    // The state of the generator that chooses what to move.
+    rng: u64,
+}
+
+impl CuckooFilter {
+    const MAGIC: &'static [u8; 4] = b"CKF1";
+
+    /// Creates a filter that can hold at least `capacity` items.
+    pub fn with_capacity(capacity: usize) -> Self {
+        // Inserts start failing at about 95% full.
+        let buckets = (capacity as f64 / 0.95 / BUCKET_SIZE as f64).ceil() as usize;
+        let buckets = buckets.max(1).next_power_of_two();
+        CuckooFilter {
+            slots: vec![0; buckets * BUCKET_SIZE],
+            mask: buckets - 1,
+            items: 0,
+            victim: None,
+            rng: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+
+    /// Inserts an item, returning false if the filter is too full.
+    ///
+    /// The same item can be inserted more than once, and must then be removed as
+    /// many times.
+    pub fn insert<T: FilterKey + ?Sized>(&mut self, item: &T) -> bool {
+        if self.victim.is_some() {
+            return false;
+        }
+        let (i1, fingerprint) = self.locate(item);
+        let i2 = self.other_bucket(i1, fingerprint);
+        if self.put(i1, fingerprint) || self.put(i2, fingerprint) {
+            self.items += 1;
+            return true;
+        }
+        // Kick a random fingerprint out and put it in its other bucket.
+        let mut bucket = if self.next_random() & 1 == 0 { i1 } else { i2 };
+        let mut fingerprint = fingerprint;
+        for _ in 0..MAX_KICKS {
+            let slot = bucket * BUCKET_SIZE + self.next_random() as usize % BUCKET_SIZE;
+            std::mem::swap(&mut fingerprint, &mut self.slots[slot]);
+            bucket = self.other_bucket(bucket, fingerprint);
+            if self.put(bucket, fingerprint) {
+                self.items += 1;
+                return true;
+            }
+        }
+        // The item is in the filter, but the one it kicked out has no place. Keep
+        // it aside so it is still found, and refuse new items.
+        self.victim = Some((bucket, fingerprint));
+        self.items += 1;
+        true
+    }
+
+    /// Returns false if the item is not in the filter, true if it probably is.
+    pub fn contains<T: FilterKey + ?Sized>(&self, item: &T) -> bool {
+        let (i1, fingerprint) = self.locate(item);
+        let i2 = self.other_bucket(i1, fingerprint);
+        self.bucket(i1).contains(&fingerprint)
+            || self.bucket(i2).contains(&fingerprint)
+            || self
+                .victim
+                .is_some_and(|(bucket, f)| f == fingerprint && (bucket == i1 || bucket == i2))
+    }
+
+    /// Removes an item, returning false if it was not in the filter.
+    ///
+    /// Only items that were inserted must be removed. Removing an item that was
+    /// never inserted corrupts the filter: if it is a false positive it removes
+    /// the fingerprint of another item, which is then not found anymore.
+    pub fn remove<T: FilterKey + ?Sized>(&mut self, item: &T) -> bool {
+        let (i1, fingerprint) = self.locate(item);
+        let i2 = self.other_bucket(i1, fingerprint);
+        if let Some((bucket, f)) = self.victim {
+            if f == fingerprint && (bucket == i1 || bucket == i2) {
+                self.victim = None;
+                self.items = self.items.saturating_sub(1);
+                return true;
+            }
+        }
+        for bucket in [i1, i2] {
+            let start = bucket * BUCKET_SIZE;
+            if let Some(slot) = self.slots[start..start + BUCKET_SIZE]
+                .iter()
+                .position(|&f| f == fingerprint)
+            {
+                self.slots[start + slot] = 0;
+                self.items = self.items.saturating_sub(1);
+                // There is room now for the victim.
+                if let Some((bucket, f)) = self.victim.take() {
+                    let other = self.other_bucket(bucket, f);
+                    if !self.put(bucket, f) && !self.put(other, f) {
+                        self.victim = Some((bucket, f));
+                    }
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn len(&self) -> usize {
+        self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items == 0
+    }
+
+    /// Returns how many items fit in the filter, when it is completely full.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Serializes the filter, it can be read back with [`CuckooFilter::from_bytes`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(40 + self.slots.len() * 2);
+        bytes.extend_from_slice(Self::MAGIC);
+        bytes.extend_from_slice(&((self.mask + 1) as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.items as u64).to_le_bytes());
+        let (bucket, fingerprint) = self.victim.unwrap_or((0, 0));
+        bytes.push(self.victim.is_some() as u8);
+        bytes.extend_from_slice(&(bucket as u64).to_le_bytes());
+        bytes.extend_from_slice(&fingerprint.to_le_bytes());
+        bytes.extend_from_slice(&self.rng.to_le_bytes());
+        for slot in &self.slots {
+            bytes.extend_from_slice(&slot.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FilterError> {
+        let mut reader = Reader::new(bytes, Self::MAGIC, "cuckoo filter")?;
+        let buckets = reader.len()?;
+        let items = reader.len()?;
+        let has_victim = reader.u8()?;
+        let victim = (reader.len()?, reader.u16()?);
+        let rng = reader.u64()?;
+        if !buckets.is_power_of_two() {
+            return Err(FilterError::Invalid(
+                "the number of buckets is not a power of two",
+            ));
+        }
+        let victim = (has_victim == 1).then_some(victim);
+        if has_victim > 1
+            || victim.is_some_and(|(bucket, f)| bucket >= buckets || f == 0)
+            || rng == 0
+        {
+            return Err(FilterError::Invalid(
+                "the victim or the generator are invalid",
+            ));
+        }
+        let slots: Vec<u16> = (0..buckets
+            .checked_mul(BUCKET_SIZE)
+            .ok_or(FilterError::Truncated)?)
+            .map(|_| reader.u16())
+            .collect::<Result<_, _>>()?;
+        reader.finish()?;
+        let stored = slots.iter().filter(|&&f| f != 0).count() + victim.is_some() as usize;
+        if items != stored {
+            return Err(FilterError::Invalid(
+                "the number of items does not match the slots",
+            ));
+        }
+        Ok(CuckooFilter {
+            slots,
+            mask: buckets - 1,
+            items,
+            victim,
+            rng,
+        })
+    }
+
+    /// The first bucket and the fingerprint of an item.
+    fn locate<T: FilterKey + ?Sized>(&self, item: &T) -> (usize, u16) {
+        let hash = hash_with_seed(item, 0);
+        // 0 marks an empty slot, so it is not a valid fingerprint.
+        let fingerprint = ((hash >> 48) as u16).max(1);
+        (hash as usize & self.mask, fingerprint)
+    }
+
+    /// The other bucket of a fingerprint, applying it twice gives back the first.
+    fn other_bucket(&self, bucket: usize, fingerprint: u16) -> usize {
+        (bucket ^ mix(fingerprint as u64) as usize) & self.mask
+    }
+
+    fn bucket(&self, bucket: usize) -> &[u16] {
+        &self.slots[bucket * BUCKET_SIZE..(bucket + 1) * BUCKET_SIZE]
+    }
+
+    /// Puts a fingerprint in a free slot of a bucket, if there is one.
+    fn put(&mut self, bucket: usize, fingerprint: u16) -> bool {
+        let start = bucket * BUCKET_SIZE;
+        match self.slots[start..start + BUCKET_SIZE]
+            .iter()
+            .position(|&f| f == 0)
+        {
+            Some(slot) => {
+                self.slots[start + slot] = fingerprint;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// A xorshift generator.
+    fn next_random(&mut self) -> u64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        self.rng
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CuckooFilter;
+    use crate::search::FilterError;
+
+    #[test]
+    fn test_cuckoo_filter() {
+        let mut filter = CuckooFilter::with_capacity(10_000);
+        assert!(filter.capacity() >= 10_000);
+        for i in 0..10_000 {
+            assert!(filter.insert(&i));
+        }
+        assert!((0..10_000).all(|i| filter.contains(&i)));
+        let false_positives = (10_000..1_010_000).filter(|i| filter.contains(i)).count();
+        // About 0.012%, so around 120.
+        assert!(false_positives < 400, "{false_positives}");
+
+        let read = CuckooFilter::from_bytes(&filter.to_bytes()).unwrap();
+        assert_eq!(read, filter);
+
+        for i in (0..10_000).step_by(2) {
+            assert!(filter.remove(&i));
+        }
+        assert_eq!(filter.len(), 5_000);
+        assert!((1..10_000).step_by(2).all(|i| filter.contains(&i)));
+        let still_there = (0..10_000)
+            .step_by(2)
+            .filter(|i| filter.contains(i))
+            .count();
+        assert!(still_there < 20);
+    }
+
+    #[test]
+    fn test_cuckoo_filter_full() {
+        let mut filter = CuckooFilter::with_capacity(100);
+        let inserted: Vec<u32> = (0..).take_while(|i| filter.insert(i)).collect();
+        // Everything that was accepted is found, even when full.
+        assert!(inserted.len() >= 100);
+        assert!(inserted.iter().all(|i| filter.contains(i)));
+        assert_eq!(filter.len(), inserted.len());
+        // Removing makes room again.
+        let (removed, kept) = inserted.split_at(inserted.len() / 2);
+        assert!(removed.iter().all(|i| filter.remove(i)));
+        assert!(kept.iter().all(|i| filter.contains(i)));
+        assert!(filter.insert(&u32::MAX));
+        assert!(CuckooFilter::from_bytes(&filter.to_bytes()).unwrap() == filter);
+    }
+
+    #[test]
+    fn test_cuckoo_filter_remove_not_inserted() {
+        // An item with the same fingerprint and buckets as 0.
+        let mut filter = CuckooFilter::with_capacity(1);
+        let twin = (1..u32::MAX)
+            .find(|i| filter.locate(i) == filter.locate(&0_u32))
+            .unwrap();
+        assert!(filter.insert(&0_u32));
+        // It removes the fingerprint of 0, but the count stays right.
+        assert!(filter.remove(&twin));
+        assert!(!filter.contains(&0_u32));
+        assert!(!filter.remove(&0_u32));
+        assert_eq!(filter.len(), 0);
+
+        // A count of items that does not match the slots is rejected.
+        filter.insert("apple");
+        let mut bytes = filter.to_bytes();
+        for items in [0_u64, 2] {
+            bytes[12..20].copy_from_slice(&items.to_le_bytes());
+            assert_eq!(
+                CuckooFilter::from_bytes(&bytes),
+                Err(FilterError::Invalid(
+                    "the number of items does not match the slots"
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn test_stored_bytes() {
+        // Filters stored by any platform or version must be read the same.
+        let mut filter = CuckooFilter::with_capacity(8);
+        filter.insert("apple");
+        filter.insert(&42_u32);
+        filter.insert(&(1_u8, "pear"));
+        let hex: String = filter
+            .to_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        assert_eq!(
+            hex,
+            "434b46310400000000000000030000000000000000000000000000000000001d\
+             dd6c4f91f445252c7b00000000000057fd12f600000000000000000000000000\
+             00000000000000"
+        );
+    }
+}
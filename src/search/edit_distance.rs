@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+
+/// Returns the Levenshtein distance between two strings: the smallest number of
+/// chars that must be inserted, removed or replaced to turn one into the other.
+///
+/// It fills the table of the distances between every prefix of both strings one
+/// row at a time, keeping only the last row, in **O (N M)** time and **O (M)**
+/// memory.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::levenshtein;
+/// assert_eq!(levenshtein("kitten", "sitting"), 3);
+/// assert_eq!(levenshtein("flaw", "lawn"), 2);
+/// assert_eq!(levenshtein("ünïcödé", "unicode"), 4);
+/// ```
+pub fn levenshtein<A, B>(a: &A, b: &B) -> usize
+where
+    A: AsRef<str> + ?Sized,
+    B: AsRef<str> + ?Sized,
+{
+    let a: Vec<char> = a.as_ref().chars().collect();
+    let b: Vec<char> = b.as_ref().chars().collect();
+    bounded_levenshtein(&a, &b, usize::MAX).unwrap()
+}
+
+/// Returns the Damerau-Levenshtein distance between two strings: like
+/// [`levenshtein`], but swapping two chars that are next to each other also
+/// counts as one edit.
+///
+/// This is the unrestricted distance, where a substring can be edited after two
+/// of its chars were swapped, so "ca" to "abc" takes 2 edits ("ca", "ac", "abc").
+/// For each cell it looks back to the last row where the char of this column
+/// appeared, and the last column where the char of this row appeared, which takes
+/// **O (N M)** time and memory.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::{damerau_levenshtein, levenshtein};
+/// assert_eq!(damerau_levenshtein("teh", "the"), 1);
+/// assert_eq!(levenshtein("teh", "the"), 2);
+/// assert_eq!(damerau_levenshtein("ca", "abc"), 2);
+/// ```
+pub fn damerau_levenshtein<A, B>(a: &A, b: &B) -> usize
+where
+    A: AsRef<str> + ?Sized,
+    B: AsRef<str> + ?Sized,
+{
+    let a: Vec<char> = a.as_ref().chars().collect();
+    let b: Vec<char> = b.as_ref().chars().collect();
+    let max = a.len() + b.len();
+    // 'd[i + 1][j + 1]' is the distance between a[..i] and b[..j], the first row
+    // and column are a border bigger than any distance.
+    let mut d = vec![vec![0; b.len() + 2]; a.len() + 2];
+    d[0][0] = max;
+    for i in 0..=a.len() {
+        d[i + 1][0] = max;
+        d[i + 1][1] = i;
+    }
+    for j in 0..=b.len() {
+        d[0][j + 1] = max;
+        d[1][j + 1] = j;
+    }
+    // The last row where each char of 'a' was seen.
+    let mut last_row: HashMap<char, usize> = HashMap::new();
+    for i in 1..=a.len() {
+        // The last column of this row where b[j - 1] == a[i - 1].
+        let mut last_col = 0;
+        for j in 1..=b.len() {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_col;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_col = j;
+                0
+            } else {
+                1
+            };
+            d[i + 1][j + 1] = (d[i][j] + cost)
+                .min(d[i + 1][j] + 1)
+                .min(d[i][j + 1] + 1)
+                // Swap a[k - 1] and a[i - 1], removing and inserting what is
+                // between them.
+                .min(d[k][l] + (i - k - 1) + 1 + (j - l - 1));
+        }
+        last_row.insert(a[i - 1], i);
+    }
+    d[a.len() + 1][b.len() + 1]
+}
+
+/// Returns the words of a list that are at most `max_edits` edits away from the
+/// needle, as their index and their [`levenshtein`] distance, from the closest
+/// one. Words at the same distance keep the order of the list.
+///
+/// Words whose length is too different from the needle are skipped, and the
+/// distance of each word stops being computed once every cell of a row is over
+/// `max_edits`, so a small `max_edits` is much faster than computing every
+/// distance.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::approx_search;
+/// let commands = ["open file", "close file", "open folder", "save file"];
+/// assert_eq!(approx_search(&commands, "opn file", 2), [(0, 1)]);
+/// assert_eq!(approx_search(&commands, "save fil", 5), [(3, 1), (0, 5), (1, 5)]);
+/// ```
+pub fn approx_search<S, N>(haystack: &[S], needle: &N, max_edits: usize) -> Vec<(usize, usize)>
+where
+    S: AsRef<str>,
+    N: AsRef<str> + ?Sized,
+{
+    let needle: Vec<char> = needle.as_ref().chars().collect();
+    let mut found: Vec<(usize, usize)> = haystack
+        .iter()
+        .enumerate()
+        .filter_map(|(i, word)| {
+            let word: Vec<char> = word.as_ref().chars().collect();
+            bounded_levenshtein(&word, &needle, max_edits).map(|d| (i, d))
+        })
+        .collect();
+    // A stable sort, so equal distances keep their order.
+    found.sort_by_key(|&(_, d)| d);
+    found
+}
+
+/// The Levenshtein distance between two sequences, or `None` if it is bigger
+/// than `max`.
+pub(super) fn bounded_levenshtein<T: PartialEq>(a: &[T], b: &[T], max: usize) -> Option<usize> {
+    // Each insertion or removal changes the length by one.
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    // The distances between a[..i] and every prefix of b.
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.iter().enumerate() {
+        // The distance between a[..i] and b[..j], before it is overwritten.
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        let mut row_min = row[0];
+        for (j, y) in b.iter().enumerate() {
+            let replace = diagonal + (x != y) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = replace.min(row[j] + 1).min(row[j + 1] + 1);
+            row_min = row_min.min(row[j + 1]);
+        }
+        // The distances never go down in the next rows.
+        if row_min > max {
+            return None;
+        }
+    }
+    let distance = row[b.len()];
+    (distance <= max).then_some(distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, VecDeque};
+
+    use super::{approx_search, damerau_levenshtein, levenshtein};
+
+    /// The distance with every edit tried recursively.
+    fn naive(a: &[u8], b: &[u8], swaps: bool) -> usize {
+        match (a, b) {
+            ([], _) => b.len(),
+            (_, []) => a.len(),
+            ([x, a_rest @ ..], [y, b_rest @ ..]) => {
+                let mut best = (naive(a_rest, b_rest, swaps) + (x != y) as usize)
+                    .min(naive(a_rest, b, swaps) + 1)
+                    .min(naive(a, b_rest, swaps) + 1);
+                if swaps && a.len() > 1 && b.len() > 1 && a[0] == b[1] && a[1] == b[0] {
+                    best = best.min(naive(&a[2..], &b[2..], swaps) + 1);
+                }
+                best
+            }
+        }
+    }
+
+    /// The distances from `start` to every string over `alphabet` of up to
+    /// `max_len` chars, found with a breadth first search where inserting,
+    /// removing, replacing a char or swapping two adjacent ones is one step.
+    fn edit_graph_distances(
+        start: &str,
+        alphabet: &[u8],
+        max_len: usize,
+    ) -> HashMap<Vec<u8>, usize> {
+        let mut distances = HashMap::from([(start.as_bytes().to_vec(), 0)]);
+        let mut queue = VecDeque::from([start.as_bytes().to_vec()]);
+        while let Some(word) = queue.pop_front() {
+            let distance = distances[&word];
+            let mut next = Vec::new();
+            for i in 0..=word.len() {
+                for &c in alphabet {
+                    if word.len() < max_len {
+                        let mut inserted = word.clone();
+                        inserted.insert(i, c);
+                        next.push(inserted);
+                    }
+                    if i < word.len() {
+                        let mut replaced = word.clone();
+                        replaced[i] = c;
+                        next.push(replaced);
+                    }
+                }
+                if i < word.len() {
+                    let mut removed = word.clone();
+                    removed.remove(i);
+                    next.push(removed);
+                }
+                if i + 1 < word.len() {
+                    let mut swapped = word.clone();
+                    swapped.swap(i, i + 1);
+                    next.push(swapped);
+                }
+            }
+            for word in next {
+                distances.entry(word.clone()).or_insert_with(|| {
+                    queue.push_back(word);
+                    distance + 1
+                });
+            }
+        }
+        distances
+    }
+
+    /// Every string over `alphabet` of up to `max_len` chars.
+    fn all_words(alphabet: &[u8], max_len: usize) -> Vec<String> {
+        let mut words = vec![String::new()];
+        let mut last = vec![String::new()];
+        for _ in 0..max_len {
+            last = last
+                .iter()
+                .flat_map(|w| alphabet.iter().map(move |&c| format!("{w}{}", c as char)))
+                .collect();
+            words.extend(last.iter().cloned());
+        }
+        words
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        let words = [
+            "", "a", "ab", "ba", "abc", "acb", "bca", "cab", "abcd", "badc", "dcba",
+        ];
+        for a in words {
+            for b in words {
+                assert_eq!(levenshtein(a, b), naive(a.as_bytes(), b.as_bytes(), false));
+                assert_eq!(levenshtein(a, b), levenshtein(b, a));
+            }
+        }
+        assert_eq!(levenshtein("saturday", "sunday"), 3);
+        // Both sides can be different string types.
+        assert_eq!(levenshtein("kitten", &String::from("sitting")), 3);
+        assert_eq!(damerau_levenshtein(&String::from("teh"), "the"), 1);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        let words = [
+            "", "a", "ab", "ba", "abc", "acb", "bca", "cab", "abcd", "badc", "dcba",
+        ];
+        for a in words {
+            for b in words {
+                let distance = damerau_levenshtein(a, b);
+                // Adjacent swaps that are not edited again are always found, and
+                // the distance is never above Levenshtein.
+                assert!(distance <= naive(a.as_bytes(), b.as_bytes(), true));
+                assert!(distance <= levenshtein(a, b));
+                assert_eq!(distance, damerau_levenshtein(b, a));
+            }
+        }
+        // The exact distance is the shortest path in the graph of the edits.
+        // Going through words longer than both ends never helps, but one more
+        // char is allowed anyway.
+        let max_len = if cfg!(miri) { 2 } else { 4 };
+        let words = all_words(b"abc", max_len);
+        for a in &words {
+            let distances = edit_graph_distances(a, b"abc", max_len + 1);
+            for b in &words {
+                assert_eq!(
+                    damerau_levenshtein(a, b),
+                    distances[b.as_bytes()],
+                    "{a:?} {b:?}"
+                );
+            }
+        }
+        assert_eq!(damerau_levenshtein("abcd", "badc"), 2);
+        assert_eq!(damerau_levenshtein("ca", "abc"), 2);
+        assert_eq!(damerau_levenshtein("a cat", "an act"), 2);
+    }
+
+    #[test]
+    fn test_approx_search() {
+        let words = vec!["apple", "apply", "ample", "maple", "applesauce", "pale"];
+        assert_eq!(approx_search(&words, "appel", 0), []);
+        assert_eq!(approx_search(&words, "apple", 0), [(0, 0)]);
+        assert_eq!(
+            approx_search(&words, "apple", 2),
+            [(0, 0), (1, 1), (2, 1), (3, 2), (5, 2)]
+        );
+        let words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        for max in 0..12 {
+            for (i, d) in approx_search(&words, "aple", max) {
+                assert_eq!(d, levenshtein(words[i].as_str(), "aple"));
+                assert!(d <= max);
+            }
+        }
+    }
+}
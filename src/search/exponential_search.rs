@@ -0,0 +1,132 @@
+use std::cmp::Ordering;
+
+use super::binary_search_index_by;
+
+/// This function takes an ordered slice and a reference to a needle T, returning
+/// the index of the needle if the slice contains it.
+///
+/// Exponential (or galloping) search checks the elements at 1, 2, 4, 8, ... until
+/// it finds one that is not smaller than the needle, and then binary searches the
+/// last jump. It runs in **O (log i)** where i is the position of the needle, so
+/// it is faster than a binary search when the needle is near the start.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::exponential_search;
+/// let arr: Vec<u32> = (0..100).map(|i| i * 2).collect();
+/// assert_eq!(exponential_search(&arr, &6), Some(3));
+/// assert_eq!(exponential_search(&arr, &7), None);
+/// ```
+pub fn exponential_search<T: PartialOrd>(haystack: &[T], needle: &T) -> Option<usize> {
+    exponential_search_unbounded(|i| haystack.get(i).map(|e| e.partial_cmp(needle).unwrap()))
+}
+
+/// Exponential search over sorted data whose length is not known, like a stream
+/// or a sequence that is too big to be measured.
+///
+/// `probe(i)` must return `None` if there is no element at `i`, or how the element
+/// at `i` compares with the needle. Nothing past the first index that is bigger
+/// than the needle, or missing, is ever probed.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::exponential_search_unbounded;
+/// // The squares of every number, without an end.
+/// let index = exponential_search_unbounded(|i| Some((i * i).cmp(&1_000_000)));
+/// assert_eq!(index, Some(1000));
+/// ```
+pub fn exponential_search_unbounded<F>(mut probe: F) -> Option<usize>
+where
+    F: FnMut(usize) -> Option<Ordering>,
+{
+    // Gallop until we find an element that is not smaller than the needle, or
+    // the end of the data.
+    let mut bound = 1;
+    match probe(0)? {
+        Ordering::Equal => return Some(0),
+        Ordering::Greater => return None,
+        Ordering::Less => {}
+    }
+    loop {
+        match probe(bound) {
+            Some(Ordering::Equal) => return Some(bound),
+            Some(Ordering::Less) => bound = bound.checked_mul(2)?,
+            Some(Ordering::Greater) | None => break,
+        }
+    }
+    // The needle can only be in (bound / 2, bound), binary search it. Past the end
+    // of the data counts as bigger than the needle.
+    let mut low = bound / 2 + 1;
+    let mut high = bound;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match probe(mid) {
+            Some(Ordering::Equal) => return Some(mid),
+            Some(Ordering::Less) => low = mid + 1,
+            Some(Ordering::Greater) | None => high = mid,
+        }
+    }
+    None
+}
+
+/// Same as [`exponential_search`] but returns `Err` with the position where the
+/// needle could be inserted when it is not found, like
+/// [`binary_search_index`](super::binary_search_index).
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::exponential_search_index;
+/// let arr = [1, 3, 5, 7];
+/// assert_eq!(exponential_search_index(&arr, &7), Ok(3));
+/// assert_eq!(exponential_search_index(&arr, &4), Err(2));
+/// ```
+pub fn exponential_search_index<T: PartialOrd>(haystack: &[T], needle: &T) -> Result<usize, usize> {
+    let mut bound = 1;
+    while bound < haystack.len() && haystack[bound] < *needle {
+        bound *= 2;
+    }
+    let low = bound / 2;
+    let high = (bound + 1).min(haystack.len());
+    binary_search_index_by(&haystack[low..high], |e| e.partial_cmp(needle).unwrap())
+        .map(|i| i + low)
+        .map_err(|i| i + low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{exponential_search, exponential_search_index, exponential_search_unbounded};
+
+    #[test]
+    fn test_exponential_search() {
+        let arr: Vec<i32> = (0..1000).map(|i| i * 3).collect();
+        for (i, e) in arr.iter().enumerate() {
+            assert_eq!(exponential_search(&arr, e), Some(i));
+            assert_eq!(exponential_search_index(&arr, e), Ok(i));
+            assert_eq!(exponential_search(&arr, &(e + 1)), None);
+            assert_eq!(exponential_search_index(&arr, &(e + 1)), Err(i + 1));
+        }
+        assert_eq!(exponential_search(&arr, &-1), None);
+        assert_eq!(exponential_search_index(&arr, &-1), Err(0));
+        assert_eq!(exponential_search(&[], &1), None);
+        assert_eq!(exponential_search_index(&[], &1), Err(0));
+    }
+
+    #[test]
+    fn test_exponential_search_unbounded() {
+        let mut probes = 0;
+        let index = exponential_search_unbounded(|i| {
+            probes += 1;
+            Some(i.cmp(&37))
+        });
+        assert_eq!(index, Some(37));
+        assert!(probes <= 2 * 6 + 1);
+        // Never found, and the search stops instead of overflowing.
+        assert_eq!(
+            exponential_search_unbounded(|_| Some(std::cmp::Ordering::Less)),
+            None
+        );
+    }
+}
@@ -0,0 +1,182 @@
+/// A static sorted set stored in Eytzinger layout, for fast lookups in tables
+/// that do not change.
+///
+/// The elements are placed in breadth first order of the binary search tree of
+/// the sorted sequence: the root (the median) at 1, and the children of `k` at
+/// `2k` and `2k + 1` (counting from 1). A search goes down the tree with one
+/// comparison per level and no branches, and as the elements it checks first are
+/// all at the start of the array they stay in the cache. The next levels are
+/// prefetched while the current one is compared, hiding most of the memory
+/// latency on tables that do not fit in the cache.
+///
+/// Every query is **O (log N)**, the same as a binary search over a sorted slice.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::EytzingerSet;
+/// let set = EytzingerSet::new(vec![40, 10, 30, 20, 10]);
+/// assert_eq!(set.len(), 4);
+/// assert!(set.contains(&30));
+/// assert!(!set.contains(&25));
+/// assert_eq!(set.lower_bound(&25), Some(&30));
+/// assert_eq!(set.lower_bound(&41), None);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EytzingerSet<T> {
+    // 'data[k - 1]' is the node 'k' of the tree.
+    data: Vec<T>,
+}
+
+impl<T: PartialOrd> EytzingerSet<T> {
+    /// Sorts the values, removes the duplicates and rebuilds them in Eytzinger
+    /// layout.
+    pub fn new(mut values: Vec<T>) -> Self {
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values.dedup_by(|a, b| a == b);
+        Self::from_sorted(values)
+    }
+
+    /// Rebuilds an already sorted sequence without duplicates in Eytzinger
+    /// layout, skipping the sort of [`EytzingerSet::new`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values are not sorted or have duplicates.
+    pub fn from_sorted(values: Vec<T>) -> Self {
+        assert!(
+            values.windows(2).all(|w| w[0] < w[1]),
+            "values must be sorted and without duplicates"
+        );
+        let len = values.len();
+        let mut slots: Vec<Option<T>> = (0..len).map(|_| None).collect();
+        let mut values = values.into_iter();
+        // An in order walk of the tree visits the nodes in sorted order.
+        fill(&mut slots, &mut values, 1);
+        EytzingerSet {
+            data: slots.into_iter().map(Option::unwrap).collect(),
+        }
+    }
+
+    /// Returns true if the set contains the value.
+    pub fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    /// Returns the element of the set that is equal to the value.
+    pub fn get(&self, value: &T) -> Option<&T> {
+        self.lower_bound(value).filter(|e| *e == value)
+    }
+
+    /// Returns the smallest element of the set that is not smaller than the value.
+    pub fn lower_bound(&self, value: &T) -> Option<&T> {
+        let len = self.data.len();
+        let mut k = 1;
+        while k <= len {
+            // The descendants of 'k' four levels down are the 16 nodes from '16k',
+            // load them while this level is compared.
+            prefetch(self.data.as_ptr().wrapping_add(16 * k - 1));
+            // Go left if the node is not smaller than the value, right if it is.
+            k = 2 * k + (self.data[k - 1] < *value) as usize;
+        }
+        // Every right turn adds a 1 bit to 'k', and every left turn a 0. The answer
+        // is the last node where we went left, so drop the right turns after it,
+        // and that left turn itself.
+        k >>= k.trailing_ones() + 1;
+        // 'k' is 0 if we always went right, so every element is smaller.
+        k.checked_sub(1).map(|i| &self.data[i])
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the elements in Eytzinger layout.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Returns the elements in sorted order.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut slots: Vec<Option<T>> = self.data.into_iter().map(Some).collect();
+        let mut sorted = Vec::with_capacity(slots.len());
+        take_in_order(&mut slots, &mut sorted, 1);
+        sorted
+    }
+}
+
+impl<T: PartialOrd> FromIterator<T> for EytzingerSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        EytzingerSet::new(iter.into_iter().collect())
+    }
+}
+
+/// Fills the subtree of the node `k` with the next values, in order.
+fn fill<T>(slots: &mut [Option<T>], values: &mut impl Iterator<Item = T>, k: usize) {
+    if k <= slots.len() {
+        fill(slots, values, 2 * k);
+        slots[k - 1] = values.next();
+        fill(slots, values, 2 * k + 1);
+    }
+}
+
+/// Moves the subtree of the node `k` to `sorted`, in order.
+fn take_in_order<T>(slots: &mut [Option<T>], sorted: &mut Vec<T>, k: usize) {
+    if k <= slots.len() {
+        take_in_order(slots, sorted, 2 * k);
+        sorted.extend(slots[k - 1].take());
+        take_in_order(slots, sorted, 2 * k + 1);
+    }
+}
+
+/// Asks the CPU to load the cache line of `ptr`, it does nothing on targets without
+/// a prefetch instruction. The pointer does not need to be valid.
+#[inline(always)]
+fn prefetch<T>(ptr: *const T) {
+    #[cfg(target_arch = "x86_64")]
+    // SAFETY: a prefetch never faults, even on an invalid address.
+    unsafe {
+        use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+        _mm_prefetch::<_MM_HINT_T0>(ptr as *const i8);
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = ptr;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EytzingerSet;
+    use crate::search::lower_bound;
+
+    #[test]
+    fn test_eytzinger_set() {
+        for len in 0..100 {
+            let sorted: Vec<i32> = (0..len).map(|i| i * 2).collect();
+            let set = EytzingerSet::from_sorted(sorted.clone());
+            assert_eq!(set.len(), sorted.len());
+            for needle in -1..=len * 2 {
+                assert_eq!(set.contains(&needle), sorted.contains(&needle));
+                assert_eq!(
+                    set.lower_bound(&needle),
+                    sorted.get(lower_bound(&sorted, &needle))
+                );
+            }
+            assert_eq!(set.into_sorted_vec(), sorted);
+        }
+        let set = EytzingerSet::new(vec![4, 2, 6, 1, 3, 5, 7]);
+        assert_eq!(set.as_slice(), [4, 2, 6, 1, 3, 5, 7]);
+        let set: EytzingerSet<String> = ["b", "a", "c"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(set.as_slice(), ["b", "a", "c"]);
+        assert_eq!(set.get(&"c".to_string()).map(String::as_str), Some("c"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_eytzinger_set_unsorted() {
+        EytzingerSet::from_sorted(vec![1, 3, 2]);
+    }
+}
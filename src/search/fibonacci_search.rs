@@ -0,0 +1,68 @@
+/// This function takes an ordered slice and a reference to a needle T, returning
+/// the index of the needle if the slice contains it.
+///
+/// Fibonacci search splits the slice in parts whose sizes are consecutive
+/// Fibonacci numbers instead of halves. Like a binary search it runs in
+/// **O (log N)**, but it only needs additions and subtractions to compute the
+/// positions, and the positions it checks get closer to each other as it goes.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::fibonacci_search;
+/// let arr = [10, 22, 35, 40, 45, 50, 80, 82, 85, 90, 100];
+/// assert_eq!(fibonacci_search(&arr, &85), Some(8));
+/// assert_eq!(fibonacci_search(&arr, &84), None);
+/// ```
+pub fn fibonacci_search<T: PartialOrd>(haystack: &[T], needle: &T) -> Option<usize> {
+    let len = haystack.len();
+    // Find the smallest Fibonacci number that is bigger or equal to the length,
+    // and the two numbers before it.
+    let mut fib_2 = 0;
+    let mut fib_1 = 1;
+    let mut fib = fib_1 + fib_2;
+    while fib < len {
+        fib_2 = fib_1;
+        fib_1 = fib;
+        fib = fib_1 + fib_2;
+    }
+    // Everything before 'offset' is smaller than the needle.
+    let mut offset = 0;
+    while fib > 1 {
+        // Check the element fib_2 positions after the offset.
+        let i = (offset + fib_2).min(len) - 1;
+        if haystack[i] < *needle {
+            // Discard everything up to i, going one Fibonacci number down.
+            fib = fib_1;
+            fib_1 = fib_2;
+            fib_2 = fib - fib_1;
+            offset = i + 1;
+        } else if haystack[i] > *needle {
+            // Discard everything after i, going two Fibonacci numbers down.
+            fib = fib_2;
+            fib_1 -= fib_2;
+            fib_2 = fib - fib_1;
+        } else {
+            return Some(i);
+        }
+    }
+    // One element can be left to check.
+    (fib_1 == 1 && offset < len && haystack[offset] == *needle).then_some(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fibonacci_search;
+
+    #[test]
+    fn test_fibonacci_search() {
+        for len in 0..100 {
+            let arr: Vec<i32> = (0..len).map(|i| i * 2).collect();
+            for (i, e) in arr.iter().enumerate() {
+                assert_eq!(fibonacci_search(&arr, e), Some(i), "len {len}");
+                assert_eq!(fibonacci_search(&arr, &(e + 1)), None);
+            }
+            assert_eq!(fibonacci_search(&arr, &-1), None);
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use std::cmp::Ordering;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+
+use super::bisect_int;
+
+/// A line found by [`binary_search_file_by`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRecord {
+    /// The byte offset where the line starts.
+    pub offset: u64,
+    /// The line, without its `\n` or `\r\n`.
+    pub line: Vec<u8>,
+}
+
+/// Binary searches a sorted file of newline delimited records for a line equal to
+/// `needle`, see [`binary_search_file_by`].
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::binary_search_file;
+/// # use std::io::Cursor;
+/// let mut file = Cursor::new("apple\nbanana\ncherry\n");
+/// let record = binary_search_file(&mut file, b"banana").unwrap().unwrap();
+/// assert_eq!(record.offset, 6);
+/// assert!(binary_search_file(&mut file, b"blueberry").unwrap().is_none());
+/// ```
+pub fn binary_search_file<R: Read + Seek>(
+    source: R,
+    needle: &[u8],
+) -> io::Result<Option<FileRecord>> {
+    binary_search_file_by(source, |line| line.cmp(needle))
+}
+
+/// Binary searches a file of newline delimited records, sorted by the function,
+/// without loading it in memory. Returns the first record for which `f` returns
+/// `Equal`, with its offset.
+///
+/// `f` compares a line (without its line break) with the needle, returning `Less`
+/// if the line comes before it. The search is done over the byte offsets of the
+/// file: for an offset it seeks there, skips to the start of the next line and
+/// reads it, so it reads **O (log N)** lines, where N is the size of the file.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::binary_search_file_by;
+/// # use std::io::Cursor;
+/// let mut file = Cursor::new("a,1\nb,2\nb,3\nc,4\n");
+/// let record = binary_search_file_by(&mut file, |line| line[..1].cmp(b"b")).unwrap();
+/// assert_eq!(record.map(|r| (r.offset, r.line)), Some((4, b"b,2".to_vec())));
+/// ```
+pub fn binary_search_file_by<R, F>(source: R, mut f: F) -> io::Result<Option<FileRecord>>
+where
+    R: Read + Seek,
+    F: FnMut(&[u8]) -> Ordering,
+{
+    // Each probe reads a single line, a small buffer avoids reading much more.
+    let mut reader = BufReader::with_capacity(1024, source);
+    let size = reader.seek(SeekFrom::End(0))?;
+    let mut error = None;
+    let mut line = Vec::new();
+    // The first offset whose next line is not smaller than the needle, the end
+    // of the file counts as bigger than anything.
+    let offset = bisect_int(0, size, |offset| {
+        match read_line_after(&mut reader, offset, &mut line) {
+            Ok(Some(_)) => f(&line) != Ordering::Less,
+            Ok(None) => true,
+            Err(e) => {
+                // Stop the search, the error is returned below.
+                error.get_or_insert(e);
+                true
+            }
+        }
+    });
+    if let Some(e) = error {
+        return Err(e);
+    }
+    // 'bisect_int' always finds an offset, as the end of the file is true.
+    let offset = offset.unwrap_or(size);
+    match read_line_after(&mut reader, offset, &mut line)? {
+        Some(start) if f(&line) == Ordering::Equal => Ok(Some(FileRecord {
+            offset: start,
+            line,
+        })),
+        _ => Ok(None),
+    }
+}
+
+/// Same as [`binary_search_file_by`] but compares a key extracted from each line,
+/// the file must be sorted by that key.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::binary_search_file_by_key;
+/// # use std::io::Cursor;
+/// let mut file = Cursor::new("3 three\n10 ten\n200 two hundred\n");
+/// let id = |line: &[u8]| -> u32 {
+///     let end = line.iter().position(|&b| b == b' ').unwrap();
+///     std::str::from_utf8(&line[..end]).unwrap().parse().unwrap()
+/// };
+/// let record = binary_search_file_by_key(&mut file, &10, id).unwrap().unwrap();
+/// assert_eq!((record.offset, record.line), (8, b"10 ten".to_vec()));
+/// ```
+pub fn binary_search_file_by_key<R, B, F>(
+    source: R,
+    key: &B,
+    mut f: F,
+) -> io::Result<Option<FileRecord>>
+where
+    R: Read + Seek,
+    B: PartialOrd,
+    F: FnMut(&[u8]) -> B,
+{
+    binary_search_file_by(source, |line| f(line).partial_cmp(key).unwrap())
+}
+
+/// Reads into `line` the first line that starts at or after `offset`, returning
+/// where it starts, or `None` at the end of the file.
+fn read_line_after<R: Read + Seek>(
+    reader: &mut BufReader<R>,
+    offset: u64,
+    line: &mut Vec<u8>,
+) -> io::Result<Option<u64>> {
+    line.clear();
+    let mut start = offset;
+    if offset > 0 {
+        // If the byte before 'offset' is a line break, a line starts at
+        // 'offset', otherwise skip the rest of the line it is in.
+        reader.seek(SeekFrom::Start(offset - 1))?;
+        start = offset - 1 + reader.read_until(b'\n', line)? as u64;
+        line.clear();
+    } else {
+        reader.seek(SeekFrom::Start(0))?;
+    }
+    if reader.read_until(b'\n', line)? == 0 {
+        return Ok(None);
+    }
+    if line.ends_with(b"\n") {
+        line.pop();
+        if line.ends_with(b"\r") {
+            line.pop();
+        }
+    }
+    Ok(Some(start))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read, Seek, SeekFrom};
+
+    use super::{binary_search_file, binary_search_file_by_key, FileRecord};
+
+    /// Counts the bytes read from the inner reader.
+    struct Counting<R> {
+        inner: R,
+        read: usize,
+    }
+
+    impl<R: Read> Read for Counting<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.read += n;
+            Ok(n)
+        }
+    }
+
+    impl<R: Seek> Seek for Counting<R> {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[test]
+    fn test_binary_search_file() {
+        let lines: Vec<String> = (0..2000).map(|i| format!("{:06}", i * 3)).collect();
+        let text = lines.join("\n");
+        let mut file = Cursor::new(text.as_bytes());
+        for (i, line) in lines.iter().enumerate() {
+            let record = binary_search_file(&mut file, line.as_bytes()).unwrap();
+            assert_eq!(
+                record,
+                Some(FileRecord {
+                    offset: i as u64 * 7,
+                    line: line.as_bytes().to_vec()
+                })
+            );
+            let missing = format!("{:06}", i * 3 + 1);
+            assert_eq!(
+                binary_search_file(&mut file, missing.as_bytes()).unwrap(),
+                None
+            );
+        }
+        assert_eq!(binary_search_file(Cursor::new(""), b"").unwrap(), None);
+        assert_eq!(
+            binary_search_file(Cursor::new("\n"), b"").unwrap(),
+            Some(FileRecord {
+                offset: 0,
+                line: Vec::new()
+            })
+        );
+    }
+
+    #[test]
+    fn test_binary_search_file_by_key() {
+        // Duplicated keys, windows line breaks and lines of different lengths.
+        let mut text = String::new();
+        for i in 0..50_000_u32 {
+            text += &format!("{}\t{}\r\n", i / 3, "x".repeat((i % 17) as usize));
+        }
+        let key = |line: &[u8]| -> u32 {
+            let end = line.iter().position(|&b| b == b'\t').unwrap();
+            std::str::from_utf8(&line[..end]).unwrap().parse().unwrap()
+        };
+        let mut file = Counting {
+            inner: Cursor::new(text.as_bytes()),
+            read: 0,
+        };
+        let record = binary_search_file_by_key(&mut file, &10_000, key)
+            .unwrap()
+            .unwrap();
+        // The first of the three lines with the key.
+        let expected = text.find("\n10000\t").unwrap() as u64 + 1;
+        assert_eq!(record.offset, expected);
+        assert_eq!(
+            record.line,
+            format!("10000\t{}", "x".repeat(30_000 % 17)).into_bytes()
+        );
+        assert!(file.read < text.len() / 20, "read {} bytes", file.read);
+        assert_eq!(
+            binary_search_file_by_key(&mut file, &50_000, key).unwrap(),
+            None
+        );
+        let first = binary_search_file_by_key(&mut file, &0, key).unwrap();
+        assert_eq!(first.map(|r| r.offset), Some(0));
+    }
+}
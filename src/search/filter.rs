@@ -0,0 +1,261 @@
+use thiserror::Error;
+
+/// The error returned when the bytes of a filter can not be read back.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FilterError {
+    #[error("the bytes do not start with the header of a {0}")]
+    WrongHeader(&'static str),
+    #[error("the bytes end before the filter does")]
+    Truncated,
+    #[error("the filter has invalid parameters: {0}")]
+    Invalid(&'static str),
+}
+
+/// An item that can be put in a filter.
+///
+/// Stored filters must give the same answers on every platform, so items are not
+/// hashed with [`Hash`](std::hash::Hash), whose bytes depend on the endianness,
+/// the width of `usize` and the std version. Instead each item writes a fixed
+/// encoding of itself: integers in little endian with their own width (`usize`
+/// and `isize` as 64 bits), `bool` as one byte, `char` as its `u32`, and strings
+/// and slices as their length followed by their items.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::{BloomFilter, FilterKey, KeyHasher};
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl FilterKey for Point {
+///     fn write_key(&self, hasher: &mut KeyHasher) {
+///         self.x.write_key(hasher);
+///         self.y.write_key(hasher);
+///     }
+/// }
+///
+/// let mut filter = BloomFilter::with_rate(100, 0.01);
+/// filter.insert(&Point { x: 1, y: 2 });
+/// assert!(filter.contains(&Point { x: 1, y: 2 }));
+/// ```
+pub trait FilterKey {
+    /// Writes the bytes that identify the item.
+    fn write_key(&self, hasher: &mut KeyHasher);
+}
+
+/// Receives the bytes of a [`FilterKey`]. The hash is FNV-1a with the final mix
+/// of splitmix64 to spread its bits, so it is the same on every run and platform.
+///
+/// It is not a [`Hasher`](std::hash::Hasher) on purpose, so items can not pass it
+/// to the platform dependent [`Hash`](std::hash::Hash) impls by mistake.
+#[derive(Debug)]
+pub struct KeyHasher(u64);
+
+impl KeyHasher {
+    pub fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        mix(self.0)
+    }
+}
+
+/// Hashes an item with a seed.
+pub(super) fn hash_with_seed<T: FilterKey + ?Sized>(item: &T, seed: u64) -> u64 {
+    let mut hasher = KeyHasher(0xcbf2_9ce4_8422_2325 ^ mix(seed));
+    item.write_key(&mut hasher);
+    hasher.finish()
+}
+
+/// The finalizer of splitmix64.
+pub(super) fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+macro_rules! impl_filter_key_int {
+    ($($int:ty),*) => {$(
+        impl FilterKey for $int {
+            fn write_key(&self, hasher: &mut KeyHasher) {
+                hasher.write(&self.to_le_bytes());
+            }
+        }
+    )*};
+}
+
+impl_filter_key_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl FilterKey for usize {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        (*self as u64).write_key(hasher);
+    }
+}
+
+impl FilterKey for isize {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        (*self as i64).write_key(hasher);
+    }
+}
+
+impl FilterKey for bool {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        (*self as u8).write_key(hasher);
+    }
+}
+
+impl FilterKey for char {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        (*self as u32).write_key(hasher);
+    }
+}
+
+impl FilterKey for str {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        // The length keeps ("ab", "c") and ("a", "bc") apart.
+        self.len().write_key(hasher);
+        hasher.write(self.as_bytes());
+    }
+}
+
+impl FilterKey for String {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        self.as_str().write_key(hasher);
+    }
+}
+
+impl<T: FilterKey> FilterKey for [T] {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        self.len().write_key(hasher);
+        for item in self {
+            item.write_key(hasher);
+        }
+    }
+}
+
+impl<T: FilterKey, const N: usize> FilterKey for [T; N] {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        self.as_slice().write_key(hasher);
+    }
+}
+
+impl<T: FilterKey> FilterKey for Vec<T> {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        self.as_slice().write_key(hasher);
+    }
+}
+
+impl<T: FilterKey + ?Sized> FilterKey for &T {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        (**self).write_key(hasher);
+    }
+}
+
+impl<A: FilterKey, B: FilterKey> FilterKey for (A, B) {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        self.0.write_key(hasher);
+        self.1.write_key(hasher);
+    }
+}
+
+impl<A: FilterKey, B: FilterKey, C: FilterKey> FilterKey for (A, B, C) {
+    fn write_key(&self, hasher: &mut KeyHasher) {
+        self.0.write_key(hasher);
+        self.1.write_key(hasher);
+        self.2.write_key(hasher);
+    }
+}
+
+/// Reads the fields of a serialized filter in order.
+pub(super) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Checks the header and starts reading after it.
+    pub(super) fn new(
+        bytes: &'a [u8],
+        magic: &[u8; 4],
+        name: &'static str,
+    ) -> Result<Self, FilterError> {
+        match bytes.strip_prefix(magic) {
+            Some(bytes) => Ok(Reader { bytes }),
+            None => Err(FilterError::WrongHeader(name)),
+        }
+    }
+
+    pub(super) fn take(&mut self, len: usize) -> Result<&'a [u8], FilterError> {
+        if self.bytes.len() < len {
+            return Err(FilterError::Truncated);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8, FilterError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(super) fn u16(&mut self) -> Result<u16, FilterError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    pub(super) fn u64(&mut self) -> Result<u64, FilterError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// A length that must fit in memory.
+    pub(super) fn len(&mut self) -> Result<usize, FilterError> {
+        usize::try_from(self.u64()?).map_err(|_| FilterError::Invalid("length is too big"))
+    }
+
+    /// Fails if there are bytes left.
+    pub(super) fn finish(self) -> Result<(), FilterError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(FilterError::Invalid("unexpected bytes after the end"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_with_seed, FilterError, Reader};
+
+    #[test]
+    fn test_hash_with_seed() {
+        // Stored filters depend on these values being the same everywhere.
+        assert_eq!(hash_with_seed("apple", 0), 0xfd57_aa4c_8f00_53c5);
+        assert_eq!(hash_with_seed("apple", 1), 0xd5ca_1030_6e95_b9c0);
+        assert_eq!(hash_with_seed(&42_u32, 0), 0xf612_5f32_53c4_052d);
+        assert_eq!(hash_with_seed(&42_u64, 0), 0xe15f_07fe_f55b_9454);
+        assert_eq!(hash_with_seed(&-1_i64, 0), 0x9795_737c_4a2d_acd5);
+        // The width of usize does not matter.
+        assert_eq!(hash_with_seed(&42_usize, 0), hash_with_seed(&42_u64, 0));
+        assert_eq!(hash_with_seed(&-1_isize, 0), hash_with_seed(&-1_i64, 0));
+        assert_eq!(
+            hash_with_seed(&String::from("apple"), 0),
+            hash_with_seed("apple", 0)
+        );
+        assert_ne!(
+            hash_with_seed(&("ab", "c"), 0),
+            hash_with_seed(&("a", "bc"), 0)
+        );
+    }
+
+    #[test]
+    fn test_reader() {
+        let mut reader = Reader::new(b"TEST\x01\x02\x00", b"TEST", "test").unwrap();
+        assert_eq!(reader.u8(), Ok(1));
+        assert_eq!(reader.u16(), Ok(2));
+        assert_eq!(reader.u8(), Err(FilterError::Truncated));
+        assert!(Reader::new(b"TES", b"TEST", "test").is_err());
+    }
+}
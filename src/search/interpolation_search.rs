@@ -0,0 +1,101 @@
+/// Numeric types that can be used as keys of an [`interpolation_search`].
+pub trait Interpolate: PartialOrd + Copy {
+    /// The value as a `f64`, used only to estimate positions so it does not need
+    /// to be exact.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_interpolate {
+    ($($t:ty),*) => {
+        $(impl Interpolate for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_interpolate!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// This function takes an ordered slice of numbers and a reference to a needle,
+/// returning the index of the needle if the slice contains it.
+///
+/// Instead of always checking the middle of the slice like a binary search,
+/// interpolation search guesses where the needle should be from its value and the
+/// values at both ends, like looking up a name in a phone book. On uniformly
+/// distributed keys it runs in **O (log log N)**, but it can degrade to **O (N)**
+/// when the keys grow very unevenly.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::interpolation_search;
+/// let arr: Vec<u64> = (0..1000).map(|i| i * 10).collect();
+/// assert_eq!(interpolation_search(&arr, &4200), Some(420));
+/// assert_eq!(interpolation_search(&arr, &4201), None);
+/// ```
+pub fn interpolation_search<T: Interpolate>(haystack: &[T], needle: &T) -> Option<usize> {
+    let needle = *needle;
+    if haystack.is_empty() {
+        return None;
+    }
+    let mut low = 0;
+    let mut high = haystack.len() - 1;
+    // The needle can only be in [low, high] if it is between their values.
+    while low <= high && haystack[low] <= needle && needle <= haystack[high] {
+        let (low_value, high_value) = (haystack[low].to_f64(), haystack[high].to_f64());
+        // Guess the position assuming the values grow linearly from low to high.
+        let mid = if high_value > low_value {
+            let fraction = (needle.to_f64() - low_value) / (high_value - low_value);
+            let guess = low + (fraction * (high - low) as f64) as usize;
+            guess.clamp(low, high)
+        } else {
+            low
+        };
+        if haystack[mid] < needle {
+            low = mid + 1;
+        } else if haystack[mid] > needle {
+            // 'mid' is never 0 here as haystack[low] <= needle.
+            high = mid - 1;
+        } else {
+            return Some(mid);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpolation_search;
+
+    #[test]
+    fn test_interpolation_search() {
+        let arr: Vec<i64> = (-500..500).map(|i| i * 7).collect();
+        for (i, e) in arr.iter().enumerate() {
+            assert_eq!(interpolation_search(&arr, e), Some(i));
+            assert_eq!(interpolation_search(&arr, &(e + 1)), None);
+        }
+        assert_eq!(interpolation_search(&arr, &i64::MIN), None);
+        assert_eq!(interpolation_search(&arr, &i64::MAX), None);
+
+        // Unevenly distributed keys still work, just slower.
+        let squares: Vec<u64> = (0..1000).map(|i| i * i * i).collect();
+        assert_eq!(
+            interpolation_search(&squares, &(999 * 999 * 999)),
+            Some(999)
+        );
+        assert_eq!(interpolation_search(&squares, &8), Some(2));
+
+        let floats = [0.5, 1.25, 1.25, 3.0, 9.75];
+        assert_eq!(interpolation_search(&floats, &3.0), Some(3));
+        assert!(matches!(
+            interpolation_search(&floats, &1.25),
+            Some(1) | Some(2)
+        ));
+        assert_eq!(
+            interpolation_search(&[u64::MAX, u64::MAX], &u64::MAX),
+            Some(0)
+        );
+        assert_eq!(interpolation_search::<u8>(&[], &1), None);
+    }
+}
@@ -0,0 +1,66 @@
+/// This function takes an ordered slice, a reference to a needle T and a block size,
+/// returning the index of the needle if the slice contains it.
+///
+/// Jump search checks the last element of each block until it finds one that is
+/// not smaller than the needle, and then linearly searches that block. It does at
+/// most N / block + block comparisons, which is smallest with a block of √N for
+/// **O (√N)** comparisons. It is useful when jumping back is expensive, as it only
+/// goes back once.
+///
+/// # Panics
+///
+/// Panics if `block` is 0.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::jump_search;
+/// let arr: Vec<u32> = (0..100).collect();
+/// assert_eq!(jump_search(&arr, &42, 10), Some(42));
+/// assert_eq!(jump_search(&arr, &100, 10), None);
+/// ```
+pub fn jump_search<T: PartialOrd>(haystack: &[T], needle: &T, block: usize) -> Option<usize> {
+    assert!(block > 0, "block size must be bigger than 0");
+    // The start of the block that can contain the needle.
+    let mut start: usize = 0;
+    loop {
+        // The last element of this block.
+        let end = start.saturating_add(block).min(haystack.len());
+        if end == start {
+            return None;
+        }
+        if haystack[end - 1] >= *needle {
+            break;
+        }
+        start = end;
+    }
+    // Linear search of the block.
+    let end = start.saturating_add(block).min(haystack.len());
+    (start..end)
+        .take_while(|&i| haystack[i] <= *needle)
+        .find(|&i| haystack[i] == *needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::jump_search;
+
+    #[test]
+    fn test_jump_search() {
+        let arr: Vec<i32> = (0..200).map(|i| i * 2).collect();
+        for block in [1, 3, 14, 200, 1000, usize::MAX] {
+            for (i, e) in arr.iter().enumerate() {
+                assert_eq!(jump_search(&arr, e, block), Some(i));
+                assert_eq!(jump_search(&arr, &(e + 1), block), None);
+            }
+            assert_eq!(jump_search(&arr, &-1, block), None);
+            assert_eq!(jump_search(&[], &1, block), None);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_jump_search_empty_block() {
+        jump_search(&[1, 2, 3], &1, 0);
+    }
+}
@@ -1,3 +1,6 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
 /// This function will linearly search a slice for an ocurrence of a needle T,
 /// and it will return true if find it, and false if not.
 ///
@@ -10,17 +13,119 @@
 /// assert!(linear_search(&arr, needle) == true);
 /// ```
 pub fn linear_search<T: PartialEq>(haystack: &[T], needle: T) -> bool {
-    haystack.iter().any(|e| *e == needle)
+    haystack.contains(&needle)
+}
+
+/// Same as [`linear_search`] but returns the index of the first ocurrence of the
+/// needle.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::linear_search_index;
+/// let arr: [u32; 5] = [2, 4, 5, 1, 3];
+/// assert_eq!(linear_search_index(&arr, &1), Some(3));
+/// assert_eq!(linear_search_index(&arr, &6), None);
+/// ```
+pub fn linear_search_index<T: PartialEq>(haystack: &[T], needle: &T) -> Option<usize> {
+    haystack.iter().position(|e| e == needle)
+}
+
+/// Same as [`linear_search_index`] but splits the slice between the available
+/// threads, see [`parallel_position`].
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::parallel_linear_search;
+/// let arr: Vec<u64> = (0..1_000_000).collect();
+/// assert_eq!(parallel_linear_search(&arr, &765_432), Some(765_432));
+/// ```
+pub fn parallel_linear_search<T: PartialEq + Sync>(haystack: &[T], needle: &T) -> Option<usize> {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    parallel_position(haystack, threads, |e| e == needle)
+}
+
+/// Returns the index of the first element for which `pred` is true, splitting the
+/// slice in one chunk per thread.
+///
+/// Each thread searches its chunk in blocks and stops as soon as a thread with an
+/// earlier chunk found a match, as nothing after it can be the first one. It is
+/// still **O (N)**, but divided by the number of threads. Starting a thread costs
+/// much more than comparing a few thousand elements, so small slices use less
+/// threads.
+///
+/// # Panics
+///
+/// Panics if `threads` is 0.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::parallel_position;
+/// let arr: Vec<i32> = (0..100_000).map(|i| i % 1000).collect();
+/// assert_eq!(parallel_position(&arr, 4, |&e| e == 999), Some(999));
+/// ```
+pub fn parallel_position<T, P>(haystack: &[T], threads: usize, pred: P) -> Option<usize>
+where
+    T: Sync,
+    P: Fn(&T) -> bool + Sync,
+{
+    // Below this many elements per thread, a thread is not worth it.
+    const MIN_CHUNK: usize = 16 * 1024;
+    // How many elements a thread checks between looking at the other threads.
+    const BLOCK: usize = 4 * 1024;
+    assert!(threads > 0, "threads must be bigger than 0");
+    let threads = threads.min(haystack.len().div_ceil(MIN_CHUNK)).max(1);
+    if threads == 1 {
+        return haystack.iter().position(pred);
+    }
+    let chunk_len = haystack.len().div_ceil(threads);
+    // The smallest index found so far.
+    let found = AtomicUsize::new(usize::MAX);
+    thread::scope(|scope| {
+        for (c, chunk) in haystack.chunks(chunk_len).enumerate() {
+            let (found, pred) = (&found, &pred);
+            scope.spawn(move || {
+                for (b, block) in chunk.chunks(BLOCK).enumerate() {
+                    let start = c * chunk_len + b * BLOCK;
+                    if found.load(Ordering::Relaxed) < start {
+                        return;
+                    }
+                    if let Some(i) = block.iter().position(pred) {
+                        found.fetch_min(start + i, Ordering::Relaxed);
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    let found = found.into_inner();
+    (found != usize::MAX).then_some(found)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::linear_search;
+    use super::{linear_search, linear_search_index, parallel_linear_search, parallel_position};
 
     #[test]
     fn test_linear_search() {
         let arr: [i32; 3] = [10, 20, 30];
         assert!(linear_search(&arr, 10));
         assert!(!linear_search(&arr, 40));
+        assert_eq!(linear_search_index(&arr, &30), Some(2));
+    }
+
+    #[test]
+    fn test_parallel_position() {
+        let arr: Vec<u32> = (0..200_000).map(|i| i % 50_000).collect();
+        for needle in [0, 1, 16_383, 16_384, 49_999, 50_000] {
+            let expected = arr.iter().position(|&e| e == needle);
+            for threads in [1, 2, 3, 8, 64] {
+                assert_eq!(parallel_position(&arr, threads, |&e| e == needle), expected);
+            }
+            assert_eq!(parallel_linear_search(&arr, &needle), expected);
+        }
+        assert_eq!(parallel_position(&[] as &[u8], 4, |_| true), None);
     }
 }
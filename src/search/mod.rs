@@ -1,9 +1,57 @@
 #![allow(dead_code)]
 
 mod binary_search;
+mod bisect;
+mod bk_tree;
+mod bloom_filter;
+mod bounds;
+mod branchless_search;
+mod cuckoo_filter;
+mod edit_distance;
+mod exponential_search;
+mod eytzinger;
+mod fibonacci_search;
+mod file_search;
+mod filter;
+mod interpolation_search;
+mod jump_search;
 mod linear_search;
+mod simd_search;
+mod ternary_search;
+mod trace;
 mod two_crystal_balls;
 
-pub use binary_search::binary_search;
-pub use linear_search::linear_search;
-pub use two_crystal_balls::two_crystal_balls;
+pub mod substring;
+
+pub use binary_search::{
+    binary_search, binary_search_by, binary_search_by_key, binary_search_index,
+    binary_search_index_by, binary_search_index_by_key,
+};
+pub use bisect::{bisect_float, bisect_int, BisectInt};
+pub use bk_tree::BkTree;
+pub use bloom_filter::{BloomFilter, CountingBloomFilter};
+pub use bounds::{
+    equal_range, equal_range_by, equal_range_by_key, lower_bound, lower_bound_by,
+    lower_bound_by_key, partition_point, upper_bound, upper_bound_by, upper_bound_by_key,
+};
+pub use branchless_search::branchless_binary_search;
+pub use cuckoo_filter::CuckooFilter;
+pub use edit_distance::{approx_search, damerau_levenshtein, levenshtein};
+pub use exponential_search::{
+    exponential_search, exponential_search_index, exponential_search_unbounded,
+};
+pub use eytzinger::EytzingerSet;
+pub use fibonacci_search::fibonacci_search;
+pub use file_search::{
+    binary_search_file, binary_search_file_by, binary_search_file_by_key, FileRecord,
+};
+pub use filter::{FilterError, FilterKey, KeyHasher};
+pub use interpolation_search::{interpolation_search, Interpolate};
+pub use jump_search::jump_search;
+pub use linear_search::{
+    linear_search, linear_search_index, parallel_linear_search, parallel_position,
+};
+pub use simd_search::{simd_position, simd_position_in_range, SimdElement};
+pub use ternary_search::{golden_section_search, ternary_search, ternary_search_int};
+pub use trace::{trace_search, SearchEvent, Trace, Traced, TracedSource, Tracer};
+pub use two_crystal_balls::{crystal_balls, crystal_balls_drops, two_crystal_balls, BallDrops};
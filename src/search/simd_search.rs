@@ -0,0 +1,720 @@
+use std::ops::RangeInclusive;
+
+use super::Traced;
+
+// How many elements the scalar path compares at once, 32 u8 fill an AVX2
+// register and 32 u32 or f32 fill four of them.
+const LANES: usize = 32;
+
+/// Element types with a SIMD search path: `u8`, `u32` and `f32`.
+///
+/// Their [`Traced`] wrappers are accepted too, so a trace shows the blocks that
+/// are compared, but they are searched with scalar compares.
+pub trait SimdElement: Copy + PartialOrd + private::Sealed {}
+
+impl SimdElement for u8 {}
+impl SimdElement for u32 {}
+impl SimdElement for f32 {}
+impl<T: SimdElement> SimdElement for Traced<'_, T> {}
+
+mod private {
+    use super::{arch, first_match, SimdElement, Traced};
+
+    pub trait Sealed: Sized {
+        fn position(haystack: &[Self], needle: Self) -> Option<usize>;
+        fn position_in_range(haystack: &[Self], low: Self, high: Self) -> Option<usize>;
+    }
+
+    impl Sealed for u8 {
+        fn position(haystack: &[u8], needle: u8) -> Option<usize> {
+            arch::position_u8(haystack, needle)
+        }
+
+        fn position_in_range(haystack: &[u8], low: u8, high: u8) -> Option<usize> {
+            arch::range_u8(haystack, low, high)
+        }
+    }
+
+    impl Sealed for u32 {
+        fn position(haystack: &[u32], needle: u32) -> Option<usize> {
+            arch::position_u32(haystack, needle)
+        }
+
+        fn position_in_range(haystack: &[u32], low: u32, high: u32) -> Option<usize> {
+            arch::range_u32(haystack, low, high)
+        }
+    }
+
+    impl Sealed for f32 {
+        fn position(haystack: &[f32], needle: f32) -> Option<usize> {
+            arch::position_f32(haystack, needle)
+        }
+
+        fn position_in_range(haystack: &[f32], low: f32, high: f32) -> Option<usize> {
+            arch::range_f32(haystack, low, high)
+        }
+    }
+
+    impl<T: SimdElement> Sealed for Traced<'_, T> {
+        fn position(haystack: &[Self], needle: Self) -> Option<usize> {
+            first_match(haystack, |x| x == needle)
+        }
+
+        fn position_in_range(haystack: &[Self], low: Self, high: Self) -> Option<usize> {
+            first_match(haystack, |x| (low <= x) & (x <= high))
+        }
+    }
+}
+
+/// Returns the index of the first element equal to the needle, comparing many
+/// elements at once.
+///
+/// On x86_64 the slice is compared in blocks of 32 bytes with AVX2 when the CPU
+/// has it, and in blocks of 16 bytes with SSE2 otherwise, on aarch64 in blocks
+/// of 16 bytes with NEON. Every compare gives a bit mask of the block, and only
+/// when the mask is not 0 does it branch, to find the first set bit. The
+/// elements that do not fill a block go through a scalar loop, as does the
+/// whole slice on other targets. For `f32`, `NaN` is never found and `-0.0`
+/// equals `0.0`, like with `==`.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::simd_position;
+/// let bytes = b"the quick brown fox jumps over the lazy dog";
+/// assert_eq!(simd_position(bytes, b'z'), Some(37));
+/// assert_eq!(simd_position(&[1.5_f32, 2.5, 3.5], 4.0), None);
+/// ```
+pub fn simd_position<T: SimdElement>(haystack: &[T], needle: T) -> Option<usize> {
+    T::position(haystack, needle)
+}
+
+/// Returns the index of the first element inside a range, comparing many
+/// elements at once like [`simd_position`].
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::simd_position_in_range;
+/// let readings = [12_u32, 18, 25, 31, 22];
+/// assert_eq!(simd_position_in_range(&readings, 30..=40), Some(3));
+/// assert_eq!(simd_position_in_range(b"Hello, World", b'A'..=b'Z'), Some(0));
+/// assert_eq!(simd_position_in_range(&[0.5_f32, f32::NAN], 1.0..=2.0), None);
+/// ```
+pub fn simd_position_in_range<T: SimdElement>(
+    haystack: &[T],
+    range: RangeInclusive<T>,
+) -> Option<usize> {
+    let (low, high) = range.into_inner();
+    T::position_in_range(haystack, low, high)
+}
+
+// The scalar path, every element of a block is compared without branches into a
+// bit mask.
+fn first_match<T: Copy, F: Fn(T) -> bool>(haystack: &[T], matches: F) -> Option<usize> {
+    let blocks = haystack.chunks_exact(LANES);
+    let rest = blocks.remainder();
+    let full = haystack.len() - rest.len();
+    for (b, block) in blocks.enumerate() {
+        let mut mask = 0_u32;
+        for (i, &x) in block.iter().enumerate() {
+            mask |= (matches(x) as u32) << i;
+        }
+        if mask != 0 {
+            return Some(b * LANES + mask.trailing_zeros() as usize);
+        }
+    }
+    rest.iter().position(|&x| matches(x)).map(|i| full + i)
+}
+
+// Compares the haystack in blocks of '$lanes' elements. '$mask' turns the block
+// that starts at '$ptr' into a mask with '$bits' bits for each element, all set
+// when it matches, and the elements that do not fill a block are compared one by
+// one with '$matches'. It has to be expanded inside the function that enables
+// the target features, so the intrinsics are inlined.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+macro_rules! find_in_blocks {
+    ($haystack:expr, $lanes:expr, $bits:expr, |$ptr:ident| $mask:expr, |$x:ident| $matches:expr) => {{
+        let haystack = $haystack;
+        let full = haystack.len() - haystack.len() % $lanes;
+        let mut start = 0;
+        while start < full {
+            let $ptr = haystack[start..start + $lanes].as_ptr();
+            let mask: u64 = $mask;
+            if mask != 0 {
+                return Some(start + mask.trailing_zeros() as usize / $bits);
+            }
+            start += $lanes;
+        }
+        haystack[full..]
+            .iter()
+            .position(|&$x| $matches)
+            .map(|i| full + i)
+    }};
+}
+
+#[cfg(target_arch = "x86_64")]
+mod arch {
+    pub fn position_u8(haystack: &[u8], needle: u8) -> Option<usize> {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::position_u8(haystack, needle) }
+        } else {
+            sse2::position_u8(haystack, needle)
+        }
+    }
+
+    pub fn range_u8(haystack: &[u8], low: u8, high: u8) -> Option<usize> {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::range_u8(haystack, low, high) }
+        } else {
+            sse2::range_u8(haystack, low, high)
+        }
+    }
+
+    pub fn position_u32(haystack: &[u32], needle: u32) -> Option<usize> {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::position_u32(haystack, needle) }
+        } else {
+            sse2::position_u32(haystack, needle)
+        }
+    }
+
+    pub fn range_u32(haystack: &[u32], low: u32, high: u32) -> Option<usize> {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::range_u32(haystack, low, high) }
+        } else {
+            sse2::range_u32(haystack, low, high)
+        }
+    }
+
+    pub fn position_f32(haystack: &[f32], needle: f32) -> Option<usize> {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::position_f32(haystack, needle) }
+        } else {
+            sse2::position_f32(haystack, needle)
+        }
+    }
+
+    pub fn range_f32(haystack: &[f32], low: f32, high: f32) -> Option<usize> {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::range_f32(haystack, low, high) }
+        } else {
+            sse2::range_f32(haystack, low, high)
+        }
+    }
+
+    // SSE2 is part of every x86_64 CPU, so these are safe to call. There are
+    // no unsigned compares, so the ranges are checked with a subtraction: 'x'
+    // is in 'low..=high' when 'x - low' wraps to at most 'high - low'.
+    pub mod sse2 {
+        use std::arch::x86_64::*;
+
+        pub fn position_u8(haystack: &[u8], needle: u8) -> Option<usize> {
+            unsafe {
+                let needle_v = _mm_set1_epi8(needle as i8);
+                find_in_blocks!(
+                    haystack,
+                    16,
+                    1,
+                    |p| {
+                        let eq = _mm_cmpeq_epi8(_mm_loadu_si128(p.cast()), needle_v);
+                        _mm_movemask_epi8(eq) as u32 as u64
+                    },
+                    |x| x == needle
+                )
+            }
+        }
+
+        pub fn range_u8(haystack: &[u8], low: u8, high: u8) -> Option<usize> {
+            if low > high {
+                return None;
+            }
+            unsafe {
+                let low_v = _mm_set1_epi8(low as i8);
+                let width = _mm_set1_epi8((high - low) as i8);
+                find_in_blocks!(
+                    haystack,
+                    16,
+                    1,
+                    |p| {
+                        let offset = _mm_sub_epi8(_mm_loadu_si128(p.cast()), low_v);
+                        let inside = _mm_cmpeq_epi8(_mm_min_epu8(offset, width), offset);
+                        _mm_movemask_epi8(inside) as u32 as u64
+                    },
+                    |x| (low <= x) & (x <= high)
+                )
+            }
+        }
+
+        pub fn position_u32(haystack: &[u32], needle: u32) -> Option<usize> {
+            unsafe {
+                let needle_v = _mm_set1_epi32(needle as i32);
+                find_in_blocks!(
+                    haystack,
+                    4,
+                    1,
+                    |p| {
+                        let eq = _mm_cmpeq_epi32(_mm_loadu_si128(p.cast()), needle_v);
+                        _mm_movemask_ps(_mm_castsi128_ps(eq)) as u32 as u64
+                    },
+                    |x| x == needle
+                )
+            }
+        }
+
+        pub fn range_u32(haystack: &[u32], low: u32, high: u32) -> Option<usize> {
+            if low > high {
+                return None;
+            }
+            unsafe {
+                // The compare is signed, flipping the top bit makes it unsigned.
+                let flip = _mm_set1_epi32(i32::MIN);
+                let low_v = _mm_set1_epi32(low as i32);
+                let width = _mm_xor_si128(_mm_set1_epi32((high - low) as i32), flip);
+                find_in_blocks!(
+                    haystack,
+                    4,
+                    1,
+                    |p| {
+                        let offset = _mm_sub_epi32(_mm_loadu_si128(p.cast()), low_v);
+                        let outside = _mm_cmpgt_epi32(_mm_xor_si128(offset, flip), width);
+                        (!_mm_movemask_ps(_mm_castsi128_ps(outside)) & 0b1111) as u32 as u64
+                    },
+                    |x| (low <= x) & (x <= high)
+                )
+            }
+        }
+
+        pub fn position_f32(haystack: &[f32], needle: f32) -> Option<usize> {
+            unsafe {
+                let needle_v = _mm_set1_ps(needle);
+                find_in_blocks!(
+                    haystack,
+                    4,
+                    1,
+                    |p| _mm_movemask_ps(_mm_cmpeq_ps(_mm_loadu_ps(p), needle_v)) as u32 as u64,
+                    |x| x == needle
+                )
+            }
+        }
+
+        pub fn range_f32(haystack: &[f32], low: f32, high: f32) -> Option<usize> {
+            unsafe {
+                let low_v = _mm_set1_ps(low);
+                let high_v = _mm_set1_ps(high);
+                find_in_blocks!(
+                    haystack,
+                    4,
+                    1,
+                    |p| {
+                        let x = _mm_loadu_ps(p);
+                        let inside = _mm_and_ps(_mm_cmple_ps(low_v, x), _mm_cmple_ps(x, high_v));
+                        _mm_movemask_ps(inside) as u32 as u64
+                    },
+                    |x| (low <= x) & (x <= high)
+                )
+            }
+        }
+    }
+
+    // The callers have to check that the CPU has AVX2. It has unsigned min,
+    // so the ranges are checked like with SSE2 but without flipping bits.
+    pub mod avx2 {
+        use std::arch::x86_64::*;
+
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn position_u8(haystack: &[u8], needle: u8) -> Option<usize> {
+            let needle_v = _mm256_set1_epi8(needle as i8);
+            find_in_blocks!(
+                haystack,
+                32,
+                1,
+                |p| {
+                    let eq = _mm256_cmpeq_epi8(_mm256_loadu_si256(p.cast()), needle_v);
+                    _mm256_movemask_epi8(eq) as u32 as u64
+                },
+                |x| x == needle
+            )
+        }
+
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn range_u8(haystack: &[u8], low: u8, high: u8) -> Option<usize> {
+            if low > high {
+                return None;
+            }
+            let low_v = _mm256_set1_epi8(low as i8);
+            let width = _mm256_set1_epi8((high - low) as i8);
+            find_in_blocks!(
+                haystack,
+                32,
+                1,
+                |p| {
+                    let offset = _mm256_sub_epi8(_mm256_loadu_si256(p.cast()), low_v);
+                    let inside = _mm256_cmpeq_epi8(_mm256_min_epu8(offset, width), offset);
+                    _mm256_movemask_epi8(inside) as u32 as u64
+                },
+                |x| (low <= x) & (x <= high)
+            )
+        }
+
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn position_u32(haystack: &[u32], needle: u32) -> Option<usize> {
+            let needle_v = _mm256_set1_epi32(needle as i32);
+            find_in_blocks!(
+                haystack,
+                8,
+                1,
+                |p| {
+                    let eq = _mm256_cmpeq_epi32(_mm256_loadu_si256(p.cast()), needle_v);
+                    _mm256_movemask_ps(_mm256_castsi256_ps(eq)) as u32 as u64
+                },
+                |x| x == needle
+            )
+        }
+
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn range_u32(haystack: &[u32], low: u32, high: u32) -> Option<usize> {
+            if low > high {
+                return None;
+            }
+            let low_v = _mm256_set1_epi32(low as i32);
+            let width = _mm256_set1_epi32((high - low) as i32);
+            find_in_blocks!(
+                haystack,
+                8,
+                1,
+                |p| {
+                    let offset = _mm256_sub_epi32(_mm256_loadu_si256(p.cast()), low_v);
+                    let inside = _mm256_cmpeq_epi32(_mm256_min_epu32(offset, width), offset);
+                    _mm256_movemask_ps(_mm256_castsi256_ps(inside)) as u32 as u64
+                },
+                |x| (low <= x) & (x <= high)
+            )
+        }
+
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn position_f32(haystack: &[f32], needle: f32) -> Option<usize> {
+            let needle_v = _mm256_set1_ps(needle);
+            find_in_blocks!(
+                haystack,
+                8,
+                1,
+                |p| {
+                    let eq = _mm256_cmp_ps(_mm256_loadu_ps(p), needle_v, _CMP_EQ_OQ);
+                    _mm256_movemask_ps(eq) as u32 as u64
+                },
+                |x| x == needle
+            )
+        }
+
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn range_f32(haystack: &[f32], low: f32, high: f32) -> Option<usize> {
+            let low_v = _mm256_set1_ps(low);
+            let high_v = _mm256_set1_ps(high);
+            find_in_blocks!(
+                haystack,
+                8,
+                1,
+                |p| {
+                    let x = _mm256_loadu_ps(p);
+                    let inside = _mm256_and_ps(
+                        _mm256_cmp_ps(low_v, x, _CMP_LE_OQ),
+                        _mm256_cmp_ps(x, high_v, _CMP_LE_OQ),
+                    );
+                    _mm256_movemask_ps(inside) as u32 as u64
+                },
+                |x| (low <= x) & (x <= high)
+            )
+        }
+    }
+}
+
+// NEON is part of every aarch64 CPU, so there is nothing to detect. It has no
+// movemask, so a compare is narrowed into 4 bits for each u8, or 16 bits for
+// each u32 or f32, of a u64.
+#[cfg(target_arch = "aarch64")]
+mod arch {
+    use std::arch::aarch64::*;
+
+    unsafe fn mask_u8(m: uint8x16_t) -> u64 {
+        vget_lane_u64::<0>(vreinterpret_u64_u8(vshrn_n_u16::<4>(vreinterpretq_u16_u8(
+            m,
+        ))))
+    }
+
+    unsafe fn mask_u32(m: uint32x4_t) -> u64 {
+        vget_lane_u64::<0>(vreinterpret_u64_u16(vmovn_u32(m)))
+    }
+
+    pub fn position_u8(haystack: &[u8], needle: u8) -> Option<usize> {
+        unsafe {
+            let needle_v = vdupq_n_u8(needle);
+            find_in_blocks!(
+                haystack,
+                16,
+                4,
+                |p| mask_u8(vceqq_u8(vld1q_u8(p), needle_v)),
+                |x| x == needle
+            )
+        }
+    }
+
+    pub fn range_u8(haystack: &[u8], low: u8, high: u8) -> Option<usize> {
+        unsafe {
+            let low_v = vdupq_n_u8(low);
+            let high_v = vdupq_n_u8(high);
+            find_in_blocks!(
+                haystack,
+                16,
+                4,
+                |p| {
+                    let x = vld1q_u8(p);
+                    mask_u8(vandq_u8(vcgeq_u8(x, low_v), vcleq_u8(x, high_v)))
+                },
+                |x| (low <= x) & (x <= high)
+            )
+        }
+    }
+
+    pub fn position_u32(haystack: &[u32], needle: u32) -> Option<usize> {
+        unsafe {
+            let needle_v = vdupq_n_u32(needle);
+            find_in_blocks!(
+                haystack,
+                4,
+                16,
+                |p| mask_u32(vceqq_u32(vld1q_u32(p), needle_v)),
+                |x| x == needle
+            )
+        }
+    }
+
+    pub fn range_u32(haystack: &[u32], low: u32, high: u32) -> Option<usize> {
+        unsafe {
+            let low_v = vdupq_n_u32(low);
+            let high_v = vdupq_n_u32(high);
+            find_in_blocks!(
+                haystack,
+                4,
+                16,
+                |p| {
+                    let x = vld1q_u32(p);
+                    mask_u32(vandq_u32(vcgeq_u32(x, low_v), vcleq_u32(x, high_v)))
+                },
+                |x| (low <= x) & (x <= high)
+            )
+        }
+    }
+
+    pub fn position_f32(haystack: &[f32], needle: f32) -> Option<usize> {
+        unsafe {
+            let needle_v = vdupq_n_f32(needle);
+            find_in_blocks!(
+                haystack,
+                4,
+                16,
+                |p| mask_u32(vceqq_f32(vld1q_f32(p), needle_v)),
+                |x| x == needle
+            )
+        }
+    }
+
+    pub fn range_f32(haystack: &[f32], low: f32, high: f32) -> Option<usize> {
+        unsafe {
+            let low_v = vdupq_n_f32(low);
+            let high_v = vdupq_n_f32(high);
+            find_in_blocks!(
+                haystack,
+                4,
+                16,
+                |p| {
+                    let x = vld1q_f32(p);
+                    mask_u32(vandq_u32(vcgeq_f32(x, low_v), vcleq_f32(x, high_v)))
+                },
+                |x| (low <= x) & (x <= high)
+            )
+        }
+    }
+}
+
+// Other targets only have the scalar path.
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod arch {
+    use super::first_match;
+
+    pub fn position_u8(haystack: &[u8], needle: u8) -> Option<usize> {
+        first_match(haystack, |x| x == needle)
+    }
+
+    pub fn range_u8(haystack: &[u8], low: u8, high: u8) -> Option<usize> {
+        first_match(haystack, |x| (low <= x) & (x <= high))
+    }
+
+    pub fn position_u32(haystack: &[u32], needle: u32) -> Option<usize> {
+        first_match(haystack, |x| x == needle)
+    }
+
+    pub fn range_u32(haystack: &[u32], low: u32, high: u32) -> Option<usize> {
+        first_match(haystack, |x| (low <= x) & (x <= high))
+    }
+
+    pub fn position_f32(haystack: &[f32], needle: f32) -> Option<usize> {
+        first_match(haystack, |x| x == needle)
+    }
+
+    pub fn range_f32(haystack: &[f32], low: f32, high: f32) -> Option<usize> {
+        first_match(haystack, |x| (low <= x) & (x <= high))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{simd_position, simd_position_in_range};
+
+    fn check_u8(
+        position: impl Fn(&[u8], u8) -> Option<usize>,
+        in_range: impl Fn(&[u8], u8, u8) -> Option<usize>,
+    ) {
+        let max_len = if cfg!(miri) { 40 } else { 100 };
+        for len in 0..max_len {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 37 % 256) as u8).collect();
+            for needle in [0, 37, 111, 200, 255] {
+                let expected = bytes.iter().position(|&b| b == needle);
+                assert_eq!(position(&bytes, needle), expected);
+            }
+            for (low, high) in [(0, 0), (250, 255), (100, 120), (0, 255), (9, 8), (255, 0)] {
+                let expected = bytes.iter().position(|&b| low <= b && b <= high);
+                assert_eq!(in_range(&bytes, low, high), expected, "{low}..={high}");
+            }
+        }
+    }
+
+    fn check_u32(
+        position: impl Fn(&[u32], u32) -> Option<usize>,
+        in_range: impl Fn(&[u32], u32, u32) -> Option<usize>,
+    ) {
+        let max_len = if cfg!(miri) { 20 } else { 50 };
+        for len in 0..max_len {
+            // Both sides of the top bit, which the signed compares must not mix.
+            let words: Vec<u32> = (0..len)
+                .map(|i| (i as u32).wrapping_mul(0x9E37_79B9).rotate_left(i as u32))
+                .collect();
+            for needle in [0, u32::MAX, words.last().copied().unwrap_or(1)] {
+                let expected = words.iter().position(|&w| w == needle);
+                assert_eq!(position(&words, needle), expected);
+            }
+            for (low, high) in [
+                (0, 1 << 31),
+                (1 << 31, u32::MAX),
+                (0x4000_0000, 0xC000_0000),
+                (0, u32::MAX),
+                (u32::MAX, 0),
+            ] {
+                let expected = words.iter().position(|&w| low <= w && w <= high);
+                assert_eq!(in_range(&words, low, high), expected, "{low}..={high}");
+            }
+        }
+    }
+
+    fn check_f32(
+        position: impl Fn(&[f32], f32) -> Option<usize>,
+        in_range: impl Fn(&[f32], f32, f32) -> Option<usize>,
+    ) {
+        let specials = [f32::NAN, -0.0, 0.0, f32::INFINITY, f32::NEG_INFINITY];
+        let max_len = if cfg!(miri) { 20 } else { 50 };
+        for len in 0..max_len {
+            let floats: Vec<f32> = (0..len)
+                .map(|i| match i % 7 {
+                    0 => specials[i / 7 % specials.len()],
+                    _ => i as f32 / 4.0 - 5.0,
+                })
+                .collect();
+            for needle in [f32::NAN, 0.0, -0.0, f32::INFINITY, 3.0, 7.5] {
+                let expected = floats.iter().position(|&f| f == needle);
+                assert_eq!(position(&floats, needle), expected);
+            }
+            for (low, high) in [
+                (-1.0, 1.0),
+                (f32::NEG_INFINITY, -4.0),
+                (f32::NAN, 1.0),
+                (0.0, f32::NAN),
+                (2.0, 1.0),
+            ] {
+                let expected = floats.iter().position(|&f| low <= f && f <= high);
+                assert_eq!(in_range(&floats, low, high), expected, "{low}..={high}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_simd_paths() {
+        // The path the CPU picks, NEON on aarch64.
+        check_u8(simd_position, |h, low, high| {
+            simd_position_in_range(h, low..=high)
+        });
+        check_u32(simd_position, |h, low, high| {
+            simd_position_in_range(h, low..=high)
+        });
+        check_f32(simd_position, |h, low, high| {
+            simd_position_in_range(h, low..=high)
+        });
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            use super::arch::{avx2, sse2};
+
+            check_u8(sse2::position_u8, sse2::range_u8);
+            check_u32(sse2::position_u32, sse2::range_u32);
+            check_f32(sse2::position_f32, sse2::range_f32);
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    check_u8(
+                        |h, n| avx2::position_u8(h, n),
+                        |h, l, r| avx2::range_u8(h, l, r),
+                    );
+                    check_u32(
+                        |h, n| avx2::position_u32(h, n),
+                        |h, l, r| avx2::range_u32(h, l, r),
+                    );
+                    check_f32(
+                        |h, n| avx2::position_f32(h, n),
+                        |h, l, r| avx2::range_f32(h, l, r),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_simd_position() {
+        for len in 0..100 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 7 % 251) as u8).collect();
+            for needle in [0, 7, 14, 200, 255] {
+                assert_eq!(
+                    simd_position(&bytes, needle),
+                    bytes.iter().position(|&b| b == needle)
+                );
+            }
+            let words: Vec<u32> = (0..len).map(|i| i as u32 * 3).collect();
+            assert_eq!(
+                simd_position(&words, 150),
+                words.iter().position(|&w| w == 150)
+            );
+        }
+        assert_eq!(simd_position(&[f32::NAN, -0.0, 1.0], 0.0), Some(1));
+        assert_eq!(simd_position(&[f32::NAN; 40], f32::NAN), None);
+    }
+
+    #[test]
+    fn test_simd_position_in_range() {
+        let values: Vec<u32> = (0..1000).rev().collect();
+        assert_eq!(simd_position_in_range(&values, 10..=20), Some(979));
+        assert_eq!(simd_position_in_range(&values, 1000..=2000), None);
+        let floats: Vec<f32> = (0..70).map(|i| i as f32 / 10.0).collect();
+        assert_eq!(simd_position_in_range(&floats, 6.55..=7.0), Some(66));
+        assert_eq!(
+            simd_position_in_range(&floats, f32::NEG_INFINITY..=0.0),
+            Some(0)
+        );
+    }
+}
@@ -0,0 +1,472 @@
+use std::io::{self, Read};
+
+use crate::search::binary_search_index_by_key;
+
+/// Which matches an [`AhoCorasick`] automaton returns.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// Every occurrence of every pattern, even if they overlap, in the order of
+    /// their end.
+    #[default]
+    All,
+    /// Matches that do not overlap, choosing the one that starts first and, among
+    /// those, the longest one. This is what a regex like `she|he|hers` would find.
+    LeftmostLongest,
+}
+
+/// A pattern found by an [`AhoCorasick`] automaton.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// The index of the pattern, in the order they were given.
+    pub pattern: usize,
+    /// The position of the first byte of the match.
+    pub start: usize,
+    /// The position after the last byte of the match.
+    pub end: usize,
+}
+
+/// Builds an [`AhoCorasick`] automaton with options.
+#[derive(Debug, Default, Clone)]
+pub struct AhoCorasickBuilder {
+    match_kind: MatchKind,
+    ascii_case_insensitive: bool,
+}
+
+impl AhoCorasickBuilder {
+    pub fn match_kind(&mut self, match_kind: MatchKind) -> &mut Self {
+        self.match_kind = match_kind;
+        self
+    }
+
+    /// Ignores the case of ascii letters, other bytes must match exactly.
+    pub fn ascii_case_insensitive(&mut self, yes: bool) -> &mut Self {
+        self.ascii_case_insensitive = yes;
+        self
+    }
+
+    pub fn build<I, P>(&self, patterns: I) -> AhoCorasick
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        let mut automaton = AhoCorasick {
+            nodes: vec![Node::default()],
+            lengths: Vec::new(),
+            match_kind: self.match_kind,
+            ascii_case_insensitive: self.ascii_case_insensitive,
+        };
+        for (id, pattern) in patterns.into_iter().enumerate() {
+            automaton.insert(id, pattern.as_ref());
+        }
+        automaton.link();
+        automaton
+    }
+}
+
+/// An automaton that finds many patterns at once with the Aho-Corasick algorithm.
+///
+/// The patterns are put in a trie, and every node gets a failure link to the
+/// node of the longest suffix of its text that is also in the trie. The input is
+/// read one byte at a time following the trie, and when a byte can not be
+/// followed the failure links are, so it never goes back in the input. Each node
+/// knows every pattern that ends in it, so the search takes
+/// **O (N + number of matches)** no matter how many patterns there are.
+///
+/// As it never goes back, the input can be given in chunks with
+/// [`AhoCorasick::stream`], finding matches that cross from a chunk to the next.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::substring::{AhoCorasick, MatchKind};
+/// let automaton = AhoCorasick::new(["he", "she", "his", "hers"]);
+/// let found: Vec<_> = automaton.find_all("ushers").iter().map(|m| (m.pattern, m.start)).collect();
+/// assert_eq!(found, [(1, 1), (0, 2), (3, 2)]);
+///
+/// let automaton = AhoCorasick::builder()
+///     .match_kind(MatchKind::LeftmostLongest)
+///     .ascii_case_insensitive(true)
+///     .build(["ERROR", "error: disk", "warn"]);
+/// let found: Vec<_> = automaton.find_all("Error: Disk full, WARN").iter().map(|m| m.pattern).collect();
+/// assert_eq!(found, [1, 2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    // The root is the node 0.
+    nodes: Vec<Node>,
+    // The length of each pattern.
+    lengths: Vec<usize>,
+    match_kind: MatchKind,
+    ascii_case_insensitive: bool,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Node {
+    // The children ordered by their byte.
+    children: Vec<(u8, usize)>,
+    fail: usize,
+    depth: usize,
+    // The patterns that end in this node, including the ones of its suffixes.
+    outputs: Vec<usize>,
+}
+
+impl Node {
+    fn child(&self, byte: u8) -> Option<usize> {
+        binary_search_index_by_key(&self.children, &byte, |&(b, _)| b)
+            .ok()
+            .map(|i| self.children[i].1)
+    }
+}
+
+impl AhoCorasick {
+    /// Builds an automaton that finds every match of the patterns, see
+    /// [`AhoCorasick::builder`] for the options. Empty patterns never match.
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        AhoCorasickBuilder::default().build(patterns)
+    }
+
+    pub fn builder() -> AhoCorasickBuilder {
+        AhoCorasickBuilder::default()
+    }
+
+    /// Returns the number of patterns.
+    pub fn patterns_len(&self) -> usize {
+        self.lengths.len()
+    }
+
+    /// Returns the matches in a haystack.
+    pub fn find_all<H: AsRef<[u8]>>(&self, haystack: H) -> Vec<Match> {
+        let mut stream = self.stream();
+        let mut matches = stream.feed(haystack.as_ref());
+        matches.extend(stream.finish());
+        matches
+    }
+
+    /// Returns the matches in everything read from a reader, reading it in chunks
+    /// so it is never all in memory.
+    pub fn find_read<R: Read>(&self, mut reader: R) -> io::Result<Vec<Match>> {
+        let mut stream = self.stream();
+        let mut matches = Vec::new();
+        let mut buf = [0; 8 * 1024];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => matches.extend(stream.feed(&buf[..n])),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        matches.extend(stream.finish());
+        Ok(matches)
+    }
+
+    /// Starts a search over input given in chunks.
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    /// # use algo_front_end_masters::search::substring::AhoCorasick;
+    /// let automaton = AhoCorasick::new(["timeout"]);
+    /// let mut stream = automaton.stream();
+    /// assert!(stream.feed(b"connection time").is_empty());
+    /// let found = stream.feed(b"out after 30s");
+    /// assert_eq!((found[0].start, found[0].end), (11, 18));
+    /// assert!(stream.finish().is_empty());
+    /// ```
+    pub fn stream(&self) -> Stream<'_> {
+        Stream {
+            automaton: self,
+            state: 0,
+            position: 0,
+            pending: Vec::new(),
+            next_start: 0,
+        }
+    }
+
+    fn insert(&mut self, id: usize, pattern: &[u8]) {
+        self.lengths.push(pattern.len());
+        if pattern.is_empty() {
+            return;
+        }
+        let mut node = 0;
+        for &byte in pattern {
+            let byte = self.normalize(byte);
+            node = match binary_search_index_by_key(&self.nodes[node].children, &byte, |&(b, _)| b)
+            {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node {
+                        depth: self.nodes[node].depth + 1,
+                        ..Node::default()
+                    });
+                    self.nodes[node].children.insert(i, (byte, child));
+                    child
+                }
+            };
+        }
+        self.nodes[node].outputs.push(id);
+    }
+
+    /// Sets the failure links in breadth first order, so the links of every node
+    /// that is less deep are ready.
+    fn link(&mut self) {
+        let mut queue = std::collections::VecDeque::from([0]);
+        while let Some(node) = queue.pop_front() {
+            for i in 0..self.nodes[node].children.len() {
+                let (byte, child) = self.nodes[node].children[i];
+                queue.push_back(child);
+                if node == 0 {
+                    continue;
+                }
+                // The longest suffix of the parent that can be followed by 'byte'.
+                let fail = self.step(self.nodes[node].fail, byte);
+                self.nodes[child].fail = fail;
+                let inherited = self.nodes[fail].outputs.clone();
+                self.nodes[child].outputs.extend(inherited);
+            }
+        }
+    }
+
+    /// Follows a byte from a node, going through the failure links if needed.
+    fn step(&self, mut node: usize, byte: u8) -> usize {
+        loop {
+            if let Some(child) = self.nodes[node].child(byte) {
+                return child;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+
+    fn normalize(&self, byte: u8) -> u8 {
+        if self.ascii_case_insensitive {
+            byte.to_ascii_lowercase()
+        } else {
+            byte
+        }
+    }
+}
+
+/// A search over input given in chunks, created by [`AhoCorasick::stream`].
+///
+/// The positions of the matches count from the start of the first chunk.
+#[derive(Debug, Clone)]
+pub struct Stream<'a> {
+    automaton: &'a AhoCorasick,
+    state: usize,
+    // How many bytes were fed.
+    position: usize,
+    // Leftmost-longest matches that could still lose to a match that is not
+    // found yet.
+    pending: Vec<Match>,
+    // Leftmost-longest matches must not start before the end of the last one.
+    next_start: usize,
+}
+
+impl<'a> Stream<'a> {
+    /// Searches the next chunk of the input, returning the matches that are known
+    /// to be final.
+    ///
+    /// With [`MatchKind::LeftmostLongest`] a match is only returned once no longer
+    /// match can start at the same place, so it may come with a later chunk or
+    /// with [`Stream::finish`].
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Match> {
+        let automaton = self.automaton;
+        let mut matches = Vec::new();
+        for &byte in chunk {
+            self.state = automaton.step(self.state, automaton.normalize(byte));
+            self.position += 1;
+            let node = &automaton.nodes[self.state];
+            for &pattern in &node.outputs {
+                let found = Match {
+                    pattern,
+                    start: self.position - automaton.lengths[pattern],
+                    end: self.position,
+                };
+                match automaton.match_kind {
+                    MatchKind::All => matches.push(found),
+                    MatchKind::LeftmostLongest if found.start >= self.next_start => {
+                        self.pending.push(found)
+                    }
+                    MatchKind::LeftmostLongest => {}
+                }
+            }
+            if automaton.match_kind == MatchKind::LeftmostLongest {
+                // A match found later starts in the text of the current node or
+                // after it.
+                self.resolve(self.position - node.depth, &mut matches);
+            }
+        }
+        matches
+    }
+
+    /// Ends the input, returning the matches that were still pending.
+    pub fn finish(mut self) -> Vec<Match> {
+        let mut matches = Vec::new();
+        self.resolve(usize::MAX, &mut matches);
+        matches
+    }
+
+    /// Moves to `matches` the pending matches that start before `horizon`, where
+    /// no other match can start.
+    fn resolve(&mut self, horizon: usize, matches: &mut Vec<Match>) {
+        // The leftmost match, and the longest of them.
+        while let Some(&best) = self
+            .pending
+            .iter()
+            .min_by_key(|m| (m.start, std::cmp::Reverse(m.end)))
+        {
+            if best.start >= horizon {
+                break;
+            }
+            matches.push(best);
+            self.next_start = best.end;
+            let next_start = self.next_start;
+            self.pending.retain(|m| m.start >= next_start);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AhoCorasick, Match, MatchKind};
+
+    /// Every match of every pattern, checking every position, ordered by their end
+    /// and then by their length.
+    fn naive_all(patterns: &[&str], haystack: &str) -> Vec<(usize, usize, usize)> {
+        let mut matches = Vec::new();
+        for end in 1..=haystack.len() {
+            for (id, pattern) in patterns.iter().enumerate() {
+                if !pattern.is_empty() && haystack[..end].ends_with(pattern) {
+                    matches.push((id, end - pattern.len(), end));
+                }
+            }
+        }
+        matches.sort_by_key(|&(id, start, end)| (end, std::cmp::Reverse(end - start), id));
+        matches
+    }
+
+    fn naive_leftmost_longest(patterns: &[&str], haystack: &str) -> Vec<(usize, usize)> {
+        let mut matches = Vec::new();
+        let mut start = 0;
+        while start < haystack.len() {
+            let longest = patterns
+                .iter()
+                .filter(|p| !p.is_empty() && haystack[start..].starts_with(*p))
+                .map(|p| p.len())
+                .max();
+            match longest {
+                Some(len) => {
+                    matches.push((start, start + len));
+                    start += len;
+                }
+                None => start += 1,
+            }
+        }
+        matches
+    }
+
+    fn spans(matches: &[Match]) -> Vec<(usize, usize)> {
+        matches.iter().map(|m| (m.start, m.end)).collect()
+    }
+
+    /// A xorshift generator for the random inputs.
+    fn random_strings(seed: u64, count: usize, max_len: u64) -> Vec<String> {
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        (0..count)
+            .map(|_| {
+                let len = next() % max_len + 1;
+                (0..len)
+                    .map(|_| (b'a' + (next() % 3) as u8) as char)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_aho_corasick() {
+        let haystacks = random_strings(7, 200, 40);
+        for seed in 1..30 {
+            let patterns = random_strings(seed, 6, 5);
+            let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+            let all = AhoCorasick::new(&patterns);
+            let leftmost = AhoCorasick::builder()
+                .match_kind(MatchKind::LeftmostLongest)
+                .build(&patterns);
+            for haystack in &haystacks {
+                let found: Vec<_> = all
+                    .find_all(haystack)
+                    .iter()
+                    .map(|m| (m.pattern, m.start, m.end))
+                    .collect();
+                assert_eq!(found, naive_all(&patterns, haystack));
+                assert_eq!(
+                    spans(&leftmost.find_all(haystack)),
+                    naive_leftmost_longest(&patterns, haystack)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_aho_corasick_stream() {
+        let haystack = "the needle in the haystack, needles and a needlework";
+        let patterns = ["needle", "needles", "needlework", "the", "hay", "haystack"];
+        for kind in [MatchKind::All, MatchKind::LeftmostLongest] {
+            let automaton = AhoCorasick::builder().match_kind(kind).build(patterns);
+            let expected = automaton.find_all(haystack);
+            // Every split in two chunks, and one byte at a time.
+            for split in 0..=haystack.len() {
+                let mut stream = automaton.stream();
+                let mut found = stream.feed(&haystack.as_bytes()[..split]);
+                found.extend(stream.feed(&haystack.as_bytes()[split..]));
+                found.extend(stream.finish());
+                assert_eq!(found, expected);
+            }
+            let mut stream = automaton.stream();
+            let mut found = Vec::new();
+            for byte in haystack.bytes() {
+                found.extend(stream.feed(&[byte]));
+            }
+            found.extend(stream.finish());
+            assert_eq!(found, expected);
+            let read = automaton.find_read(haystack.as_bytes()).unwrap();
+            assert_eq!(read, expected);
+        }
+        let leftmost = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(patterns);
+        let found: Vec<usize> = leftmost
+            .find_all(haystack)
+            .iter()
+            .map(|m| m.pattern)
+            .collect();
+        assert_eq!(found, [3, 0, 3, 5, 1, 2]);
+    }
+
+    #[test]
+    fn test_aho_corasick_case_insensitive() {
+        let automaton = AhoCorasick::builder()
+            .ascii_case_insensitive(true)
+            .build(["Rust", "ÉTÉ"]);
+        let found = automaton.find_all("rUsT and RUST, ÉTÉ but not été");
+        assert_eq!(spans(&found), [(0, 4), (9, 13), (15, 20)]);
+        let sensitive = AhoCorasick::new(["Rust"]);
+        assert_eq!(spans(&sensitive.find_all("rust Rust")), [(5, 9)]);
+        assert!(AhoCorasick::new([""]).find_all("abc").is_empty());
+        assert_eq!(AhoCorasick::new(["a", ""]).patterns_len(), 2);
+    }
+}
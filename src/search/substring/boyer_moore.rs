@@ -0,0 +1,158 @@
+use super::{after_match, LastPositions, ScanPositions, Text};
+
+/// Finds the positions of a needle in a haystack with the Boyer-Moore algorithm.
+///
+/// Each window of the haystack is compared with the needle from right to left, and
+/// on a mismatch the window moves by the biggest of two rules:
+/// - The bad character rule aligns the item that did not match with its last
+///   occurrence in the needle, or moves past it if there is none.
+/// - The good suffix rule aligns the part that did match with its previous
+///   occurrence in the needle, or with the longest prefix of the needle that is a
+///   suffix of it.
+///
+/// It usually checks a fraction of the haystack, like [`horspool_search`](super::horspool_search),
+/// and the good suffix rule keeps the worst case at **O (N + M)** when looking for
+/// the first match. The items only need to be `Eq`, the bad character rule
+/// finds the last occurrence of an item by scanning the distinct items of the
+/// needle. To find it in **O (1)** use [`BoyerMoore::new`] with
+/// [`BytePositions`](super::BytePositions) for bytes and strings, or
+/// [`HashPositions`](super::HashPositions) for items that are `Hash`.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::substring::boyer_moore_search;
+/// let matches: Vec<usize> = boyer_moore_search("GCATCGCAGAGAGTATACAGTACG", "GCAGAGAG").collect();
+/// assert_eq!(matches, [5]);
+/// ```
+pub fn boyer_moore_search<'a, H, N>(haystack: &'a H, needle: &'a N) -> BoyerMoore<'a, H::Item>
+where
+    H: Text + ?Sized,
+    N: Text<Item = H::Item> + ?Sized,
+    H::Item: Eq,
+{
+    BoyerMoore::new(haystack, needle)
+}
+
+/// The iterator over the match positions returned by [`boyer_moore_search`].
+#[derive(Debug, Clone)]
+pub struct BoyerMoore<'a, T, P = ScanPositions<'a, T>> {
+    haystack: &'a [T],
+    needle: &'a [T],
+    // The last position of each item in the needle.
+    last: P,
+    // The good suffix shift for each position of a mismatch.
+    shift: Vec<usize>,
+    // The start of the next window.
+    position: usize,
+    overlapping: bool,
+}
+
+impl<'a, T: Eq, P: LastPositions<'a, T>> BoyerMoore<'a, T, P> {
+    /// Same as [`boyer_moore_search`], finding the last positions of the items
+    /// of the needle with `P`.
+    pub fn new<H, N>(haystack: &'a H, needle: &'a N) -> Self
+    where
+        H: Text<Item = T> + ?Sized,
+        N: Text<Item = T> + ?Sized,
+    {
+        let needle = needle.as_items();
+        let len = needle.len();
+        let last = P::new(needle);
+        // 'border[i]' is the start of the widest border of needle[i..], a border being
+        // a suffix that is also a prefix. 'shift[j]' is how far to move when the
+        // items before 'j' mismatched and needle[j..] matched.
+        let mut border = vec![0; len + 1];
+        let mut shift = vec![0; len + 1];
+        let (mut i, mut j) = (len, len + 1);
+        border[i] = j;
+        while i > 0 {
+            // Needle[i..] matched but needle[i - 1] did not match needle[j - 1], so
+            // the matching suffix can be moved to the occurrence that starts at 'i'.
+            while j <= len && needle[i - 1] != needle[j - 1] {
+                if shift[j] == 0 {
+                    shift[j] = j - i;
+                }
+                j = border[j];
+            }
+            i -= 1;
+            j -= 1;
+            border[i] = j;
+        }
+        // Where the matching suffix does not occur again, align its widest border
+        // with a prefix of the needle.
+        let mut j = border[0];
+        for (i, shift) in shift.iter_mut().enumerate() {
+            if *shift == 0 {
+                *shift = j;
+            }
+            if i == j {
+                j = border[j];
+            }
+        }
+        BoyerMoore {
+            haystack: haystack.as_items(),
+            needle,
+            last,
+            shift,
+            position: 0,
+            overlapping: false,
+        }
+    }
+
+    /// Also returns the matches that overlap with the previous one.
+    pub fn overlapping(mut self) -> Self {
+        self.overlapping = true;
+        self
+    }
+}
+
+impl<'a, T: Eq, P: LastPositions<'a, T>> Iterator for BoyerMoore<'a, T, P> {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (haystack, needle) = (self.haystack, self.needle);
+        let len = needle.len();
+        while self.position + len <= haystack.len() {
+            let start = self.position;
+            // Compare from the end, 'j' is how many items are left to compare.
+            let mut j = len;
+            while j > 0 && needle[j - 1] == haystack[start + j - 1] {
+                j -= 1;
+            }
+            if j == 0 {
+                // 'shift[0]' moves to the next possible overlapping match.
+                self.position = if self.overlapping && len > 0 {
+                    start + self.shift[0]
+                } else {
+                    after_match(start, len, self.overlapping)
+                };
+                return Some(start);
+            }
+            // Align the bad item with its last occurrence before 'j - 1', or move
+            // past it.
+            let bad = match self.last.get(&haystack[start + j - 1]) {
+                Some(last) if last < j - 1 => j - 1 - last,
+                Some(_) => 1,
+                None => j,
+            };
+            self.position += bad.max(self.shift[j]);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::boyer_moore_search;
+
+    #[test]
+    fn test_boyer_moore_search() {
+        assert!(boyer_moore_search("ABAAABCDABC", "ABC").eq([4, 8]));
+        assert!(boyer_moore_search("aaaaaa", "aaa")
+            .overlapping()
+            .eq([0, 1, 2, 3]));
+        assert!(boyer_moore_search("aaaaaa", "aaa").eq([0, 3]));
+        assert!(boyer_moore_search("abcabcab", "cab").eq([2, 5]));
+        assert!(boyer_moore_search("", "a").eq([]));
+    }
+}
@@ -0,0 +1,124 @@
+use super::{after_match, LastPositions, ScanPositions, Text};
+
+/// Finds the positions of a needle in a haystack with the Boyer-Moore-Horspool
+/// algorithm.
+///
+/// Each window of the haystack is compared with the needle, and then the window
+/// moves based only on its last item: as far as the last occurrence of that item
+/// in the needle (without counting its last position), or the whole length of the
+/// needle if it does not appear in it. On large alphabets most windows skip the
+/// whole needle, so it usually checks only N / M items, but the worst case is
+/// **O (N M)**. The items only need to be `Eq`, the skips come from the last
+/// position of each distinct item of the needle, found by scanning them. To
+/// find them in **O (1)** use [`Horspool::new`] with
+/// [`BytePositions`](super::BytePositions) for bytes and strings, or
+/// [`HashPositions`](super::HashPositions) for items that are `Hash`.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::substring::horspool_search;
+/// let matches: Vec<usize> = horspool_search("here is a simple example", "example").collect();
+/// assert_eq!(matches, [17]);
+/// ```
+pub fn horspool_search<'a, H, N>(haystack: &'a H, needle: &'a N) -> Horspool<'a, H::Item>
+where
+    H: Text + ?Sized,
+    N: Text<Item = H::Item> + ?Sized,
+    H::Item: Eq,
+{
+    Horspool::new(haystack, needle)
+}
+
+/// The iterator over the match positions returned by [`horspool_search`].
+#[derive(Debug, Clone)]
+pub struct Horspool<'a, T, P = ScanPositions<'a, T>> {
+    haystack: &'a [T],
+    needle: &'a [T],
+    // Where each item is last in the needle, without its last position, to know
+    // how far the window moves when the item is the last one of the window.
+    last: P,
+    // The start of the next window.
+    position: usize,
+    overlapping: bool,
+}
+
+impl<'a, T: Eq, P: LastPositions<'a, T>> Horspool<'a, T, P> {
+    /// Same as [`horspool_search`], finding the last positions of the items of
+    /// the needle with `P`.
+    pub fn new<H, N>(haystack: &'a H, needle: &'a N) -> Self
+    where
+        H: Text<Item = T> + ?Sized,
+        N: Text<Item = T> + ?Sized,
+    {
+        let needle = needle.as_items();
+        // The last position of each item is not counted.
+        let last = P::new(needle.split_last().map_or(&[][..], |(_, rest)| rest));
+        Horspool {
+            haystack: haystack.as_items(),
+            needle,
+            last,
+            position: 0,
+            overlapping: false,
+        }
+    }
+
+    /// Also returns the matches that overlap with the previous one.
+    pub fn overlapping(mut self) -> Self {
+        self.overlapping = true;
+        self
+    }
+
+    fn skip_of(&self, last: &T) -> usize {
+        match self.last.get(last) {
+            Some(position) => self.needle.len() - 1 - position,
+            None => self.needle.len(),
+        }
+    }
+}
+
+impl<'a, T: Eq, P: LastPositions<'a, T>> Iterator for Horspool<'a, T, P> {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (haystack, needle) = (self.haystack, self.needle);
+        let len = needle.len();
+        while self.position + len <= haystack.len() {
+            let start = self.position;
+            if len == 0 {
+                self.position = after_match(start, len, self.overlapping);
+                return Some(start);
+            }
+            let window = &haystack[start..start + len];
+            let last = &window[len - 1];
+            // Check the last item first, it was needed anyway to skip.
+            if *last == needle[len - 1] && window[..len - 1] == needle[..len - 1] {
+                // The skip of the last item never jumps over a match, so it works
+                // for overlapping matches too.
+                self.position = if self.overlapping {
+                    start + self.skip_of(last)
+                } else {
+                    start + len
+                };
+                return Some(start);
+            }
+            self.position += self.skip_of(last);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::horspool_search;
+
+    #[test]
+    fn test_horspool_search() {
+        assert!(horspool_search("abracadabra", "abra").eq([0, 7]));
+        assert!(horspool_search("abababab", "abab")
+            .overlapping()
+            .eq([0, 2, 4]));
+        assert!(horspool_search("abababab", "abab").eq([0, 4]));
+        assert!(horspool_search("short", "longer needle").eq([]));
+        assert!(horspool_search(&[3, 1, 4, 1, 5, 9, 2, 6], &[5, 9]).eq([4]));
+    }
+}
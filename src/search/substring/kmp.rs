@@ -0,0 +1,119 @@
+use super::{after_match, Text};
+
+/// Finds the positions of a needle in a haystack with the Knuth-Morris-Pratt
+/// algorithm.
+///
+/// When a naive search finds a mismatch it goes back and tries the next position,
+/// comparing again items it already saw. KMP first computes, for each prefix of the
+/// needle, the longest proper prefix that is also a suffix of it. On a mismatch
+/// the part of the needle that matched is already known, so the search can go on
+/// from that prefix without going back in the haystack. It runs in
+/// **O (N + M)** and only needs `Eq`, so it works with any item type.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::substring::kmp_search;
+/// let matches: Vec<usize> = kmp_search("abababa", "aba").collect();
+/// assert_eq!(matches, [0, 4]);
+/// let matches: Vec<usize> = kmp_search(&[1, 2, 1, 2, 1], &[1, 2, 1]).overlapping().collect();
+/// assert_eq!(matches, [0, 2]);
+/// ```
+pub fn kmp_search<'a, H, N>(haystack: &'a H, needle: &'a N) -> Kmp<'a, H::Item>
+where
+    H: Text + ?Sized,
+    N: Text<Item = H::Item> + ?Sized,
+    H::Item: Eq,
+{
+    let needle = needle.as_items();
+    // 'prefix[i]' is the length of the longest proper prefix of needle[..=i] that is
+    // also a suffix of it.
+    let mut prefix = vec![0; needle.len()];
+    let mut len = 0;
+    for i in 1..needle.len() {
+        while len > 0 && needle[i] != needle[len] {
+            len = prefix[len - 1];
+        }
+        if needle[i] == needle[len] {
+            len += 1;
+        }
+        prefix[i] = len;
+    }
+    Kmp {
+        haystack: haystack.as_items(),
+        needle,
+        prefix,
+        position: 0,
+        matched: 0,
+        overlapping: false,
+    }
+}
+
+/// The iterator over the match positions returned by [`kmp_search`].
+#[derive(Debug, Clone)]
+pub struct Kmp<'a, T> {
+    haystack: &'a [T],
+    needle: &'a [T],
+    prefix: Vec<usize>,
+    // The next item of the haystack to compare.
+    position: usize,
+    // How many items of the needle match the ones before 'position'.
+    matched: usize,
+    overlapping: bool,
+}
+
+impl<'a, T: Eq> Kmp<'a, T> {
+    /// Also returns the matches that overlap with the previous one.
+    pub fn overlapping(mut self) -> Self {
+        self.overlapping = true;
+        self
+    }
+}
+
+impl<'a, T: Eq> Iterator for Kmp<'a, T> {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (haystack, needle) = (self.haystack, self.needle);
+        if needle.is_empty() {
+            let start = self.position;
+            self.position = after_match(start, 0, self.overlapping);
+            return (start <= haystack.len()).then_some(start);
+        }
+        while self.position < haystack.len() {
+            let item = &haystack[self.position];
+            // Fall back to shorter prefixes until the next item matches.
+            while self.matched > 0 && *item != needle[self.matched] {
+                self.matched = self.prefix[self.matched - 1];
+            }
+            if *item == needle[self.matched] {
+                self.matched += 1;
+            }
+            self.position += 1;
+            if self.matched == needle.len() {
+                // An overlapping match can reuse the end of this one.
+                self.matched = if self.overlapping {
+                    self.prefix[self.matched - 1]
+                } else {
+                    0
+                };
+                return Some(self.position - needle.len());
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::kmp_search;
+
+    #[test]
+    fn test_kmp_search() {
+        assert!(kmp_search("ababcabcabababd", "ababd").eq([10]));
+        assert!(kmp_search("aaaaa", "aa").overlapping().eq([0, 1, 2, 3]));
+        assert!(kmp_search("aaaaa", "aa").eq([0, 2]));
+        assert!(kmp_search("abc", "abcd").eq([]));
+        assert!(kmp_search("", "").eq([0]));
+        assert_eq!(kmp_search("abc", "").count(), 4);
+    }
+}
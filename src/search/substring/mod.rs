@@ -0,0 +1,330 @@
+//! Algorithms that find every position where a sequence (the needle) appears in
+//! another one (the haystack).
+//!
+//! Every search works over slices of any type and over `&str`, where the
+//! positions are byte offsets. They return an iterator of the match positions,
+//! that by default does not return overlapping matches: after a match, the next
+//! one starts after its end. Call `overlapping` on the iterator to get them all.
+//! An empty needle matches at every position, from 0 to the length of the
+//! haystack.
+//!
+//! # Examples
+//!
+//!```rust
+//! # use algo_front_end_masters::search::substring::kmp_search;
+//! let matches: Vec<usize> = kmp_search("aaaa", "aa").collect();
+//! assert_eq!(matches, [0, 2]);
+//! let matches: Vec<usize> = kmp_search("aaaa", "aa").overlapping().collect();
+//! assert_eq!(matches, [0, 1, 2]);
+//! ```
+
+mod aho_corasick;
+mod boyer_moore;
+mod horspool;
+mod kmp;
+mod rabin_karp;
+mod suffix_array;
+
+pub use aho_corasick::{AhoCorasick, AhoCorasickBuilder, Match, MatchKind, Stream};
+pub use boyer_moore::{boyer_moore_search, BoyerMoore};
+pub use horspool::{horspool_search, Horspool};
+pub use kmp::{kmp_search, Kmp};
+pub use rabin_karp::{rabin_karp_search, RabinKarp};
+pub use suffix_array::{lcp_array, longest_common_substring, suffix_array, SuffixIndex};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A sequence that can be searched, as a slice of items.
+///
+/// It is implemented for slices, arrays and vectors, and for `str` and `String`
+/// as their bytes.
+pub trait Text {
+    type Item;
+    fn as_items(&self) -> &[Self::Item];
+}
+
+impl<T> Text for [T] {
+    type Item = T;
+    fn as_items(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const N: usize> Text for [T; N] {
+    type Item = T;
+    fn as_items(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> Text for Vec<T> {
+    type Item = T;
+    fn as_items(&self) -> &[T] {
+        self
+    }
+}
+
+impl Text for str {
+    type Item = u8;
+    fn as_items(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Text for String {
+    type Item = u8;
+    fn as_items(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Where the search goes on after a match at `start` of a needle `len` items long.
+fn after_match(start: usize, len: usize, overlapping: bool) -> usize {
+    if overlapping || len == 0 {
+        start + 1
+    } else {
+        start + len
+    }
+}
+
+/// Finds the last position of each distinct item of a needle, which Horspool and
+/// Boyer-Moore use to skip, and Rabin-Karp to hash the items.
+///
+/// [`ScanPositions`] only needs `Eq` and is the default, [`HashPositions`] needs
+/// `Hash` and [`BytePositions`] works on bytes. They are chosen with the `new`
+/// function of each search.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::substring::{BytePositions, HashPositions, Horspool};
+/// let search = Horspool::<_, BytePositions>::new("here is a simple example", "example");
+/// assert!(search.eq([17]));
+///
+/// let words = ["to", "be", "or", "not", "to", "be"];
+/// let search = Horspool::<_, HashPositions<_>>::new(&words, &["to", "be"]);
+/// assert!(search.eq([0, 4]));
+/// ```
+pub trait LastPositions<'a, T> {
+    fn new(needle: &'a [T]) -> Self;
+
+    /// The last position of `item` in the needle, or None if it is not in it.
+    fn get(&self, item: &T) -> Option<usize>;
+
+    /// A number for `item`: 0 if it is not in the needle, and a different one
+    /// for each distinct item otherwise.
+    fn id(&self, item: &T) -> u64 {
+        self.get(item).map_or(0, |position| position as u64 + 1)
+    }
+}
+
+/// Finds the last positions by scanning the distinct items of the needle, so
+/// the items only need to be `Eq`.
+///
+/// Each lookup is **O (d)** for the `d` distinct items of the needle, which are
+/// few for bytes or chars, and building it is **O (M d)**.
+#[derive(Debug, Clone)]
+pub struct ScanPositions<'a, T> {
+    items: Vec<(&'a T, usize)>,
+}
+
+impl<'a, T: Eq> LastPositions<'a, T> for ScanPositions<'a, T> {
+    fn new(needle: &'a [T]) -> Self {
+        let mut items: Vec<(&T, usize)> = Vec::new();
+        for (i, item) in needle.iter().enumerate() {
+            // The later occurrences overwrite the earlier ones.
+            match items.iter_mut().find(|(other, _)| *other == item) {
+                Some(entry) => entry.1 = i,
+                None => items.push((item, i)),
+            }
+        }
+        ScanPositions { items }
+    }
+
+    fn get(&self, item: &T) -> Option<usize> {
+        self.items
+            .iter()
+            .find(|(other, _)| *other == item)
+            .map(|&(_, position)| position)
+    }
+}
+
+/// Finds the last positions in a hash map, in **O (1)** for each lookup and
+/// **O (M)** to build it.
+#[derive(Debug, Clone)]
+pub struct HashPositions<'a, T> {
+    items: HashMap<&'a T, usize>,
+}
+
+impl<'a, T: Hash + Eq> LastPositions<'a, T> for HashPositions<'a, T> {
+    fn new(needle: &'a [T]) -> Self {
+        // The later occurrences overwrite the earlier ones.
+        let items = needle
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (item, i))
+            .collect();
+        HashPositions { items }
+    }
+
+    fn get(&self, item: &T) -> Option<usize> {
+        self.items.get(item).copied()
+    }
+}
+
+/// Finds the last positions of bytes in a table with one entry for each byte,
+/// in **O (1)** for each lookup and **O (M)** to build it. It is the one to use
+/// for strings.
+#[derive(Debug, Clone)]
+pub struct BytePositions {
+    // The last position plus 1 of each byte, 0 if it is not in the needle.
+    table: [usize; 256],
+}
+
+impl LastPositions<'_, u8> for BytePositions {
+    fn new(needle: &[u8]) -> Self {
+        let mut table = [0; 256];
+        for (i, &byte) in needle.iter().enumerate() {
+            table[byte as usize] = i + 1;
+        }
+        BytePositions { table }
+    }
+
+    fn get(&self, item: &u8) -> Option<usize> {
+        self.table[*item as usize].checked_sub(1)
+    }
+
+    fn id(&self, item: &u8) -> u64 {
+        self.table[*item as usize] as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        boyer_moore_search, horspool_search, kmp_search, rabin_karp_search, BoyerMoore,
+        BytePositions, HashPositions, Horspool, LastPositions, RabinKarp, ScanPositions,
+    };
+
+    /// Checks every position, the expected result of every search.
+    fn naive_search(haystack: &[u8], needle: &[u8], overlapping: bool) -> Vec<usize> {
+        let mut matches = Vec::new();
+        let mut start = 0;
+        while start + needle.len() <= haystack.len() {
+            if haystack[start..].starts_with(needle) {
+                matches.push(start);
+                start += if overlapping { 1 } else { needle.len().max(1) };
+            } else {
+                start += 1;
+            }
+        }
+        matches
+    }
+
+    /// Every string of `a` and `b` up to `len` long.
+    fn strings(len: usize) -> Vec<Vec<u8>> {
+        let mut strings = vec![Vec::new()];
+        for len in 1..=len {
+            for bits in 0..1 << len {
+                strings.push((0..len).map(|i| b'a' + (bits >> i & 1) as u8).collect());
+            }
+        }
+        strings
+    }
+
+    #[test]
+    fn test_substring_search() {
+        let haystacks = strings(9);
+        let needles = strings(4);
+        for haystack in &haystacks {
+            for needle in &needles {
+                for overlapping in [false, true] {
+                    let expected = naive_search(haystack, needle, overlapping);
+                    let (h, n) = (haystack.as_slice(), needle.as_slice());
+                    macro_rules! check {
+                        ($search:ident) => {
+                            let search = $search(h, n);
+                            let matches: Vec<usize> = if overlapping {
+                                search.overlapping().collect()
+                            } else {
+                                search.collect()
+                            };
+                            assert_eq!(matches, expected, "{}", stringify!($search));
+                        };
+                    }
+                    check!(kmp_search);
+                    check!(horspool_search);
+                    check!(boyer_moore_search);
+                    check!(rabin_karp_search);
+                    // The same searches with the other ways to find the positions.
+                    macro_rules! check_with {
+                        ($search:ident, $positions:ty) => {
+                            let search = $search::<_, $positions>::new(h, n);
+                            let matches: Vec<usize> = if overlapping {
+                                search.overlapping().collect()
+                            } else {
+                                search.collect()
+                            };
+                            assert_eq!(matches, expected, "{}", stringify!($search));
+                        };
+                    }
+                    check_with!(Horspool, BytePositions);
+                    check_with!(BoyerMoore, BytePositions);
+                    check_with!(RabinKarp, BytePositions);
+                    check_with!(Horspool, HashPositions<_>);
+                    check_with!(BoyerMoore, HashPositions<_>);
+                    check_with!(RabinKarp, HashPositions<_>);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_substring_search_text() {
+        let haystack = String::from("ünïcödé ünïcödé");
+        let expected = [0, 12];
+        assert!(kmp_search(&haystack, "ünï").eq(expected));
+        assert!(horspool_search(&haystack, "ünï").eq(expected));
+        assert!(boyer_moore_search(&haystack, "ünï").eq(expected));
+        assert!(rabin_karp_search(&haystack, "ünï").eq(expected));
+
+        let words = vec!["to", "be", "or", "not", "to", "be"];
+        assert!(kmp_search(&words, &["to", "be"]).eq([0, 4]));
+        assert!(boyer_moore_search(&words[..], &["be"]).eq([1, 5]));
+        assert!(rabin_karp_search(&[1, 2, 3], &[]).eq([0, 1, 2, 3]));
+
+        // The items only need to be `Eq`.
+        #[derive(PartialEq, Eq)]
+        struct Note(&'static str);
+        let song = [Note("do"), Note("re"), Note("mi"), Note("do"), Note("re")];
+        let motif = [Note("do"), Note("re")];
+        assert!(kmp_search(&song, &motif).eq([0, 3]));
+        assert!(horspool_search(&song, &motif).eq([0, 3]));
+        assert!(boyer_moore_search(&song, &motif).eq([0, 3]));
+        assert!(rabin_karp_search(&song, &motif).eq([0, 3]));
+
+        let words = ["to", "be", "or", "not", "to", "be"];
+        assert!(RabinKarp::<_, HashPositions<_>>::new(&words, &["be", "or"]).eq([1]));
+        assert!(BoyerMoore::<_, BytePositions>::new(&haystack, "ünï").eq(expected));
+    }
+
+    #[test]
+    fn test_last_positions() {
+        let needle = b"abcab";
+        let scan = ScanPositions::new(&needle[..]);
+        let hash = HashPositions::new(&needle[..]);
+        let bytes = BytePositions::new(&needle[..]);
+        for item in 0..=u8::MAX {
+            let expected = needle.iter().rposition(|&b| b == item);
+            assert_eq!(scan.get(&item), expected);
+            assert_eq!(hash.get(&item), expected);
+            assert_eq!(bytes.get(&item), expected);
+            let id = expected.map_or(0, |position| position as u64 + 1);
+            assert_eq!(
+                (scan.id(&item), hash.id(&item), bytes.id(&item)),
+                (id, id, id)
+            );
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use super::{after_match, LastPositions, ScanPositions, Text};
+
+// The hashes are polynomials of the items modulo this prime (2⁶¹ - 1).
+const MODULUS: u64 = (1 << 61) - 1;
+const BASE: u64 = 1_000_003;
+
+/// Finds the positions of a needle in a haystack with the Rabin-Karp algorithm.
+///
+/// Rabin-Karp compares the hash of each window of the haystack with the hash of
+/// the needle, and only compares the items when both hashes are equal. The hash is
+/// a rolling hash, so moving the window one item only removes the first item and
+/// adds the next one in **O (1)**, for **O (N + M)** on average. The same idea
+/// finds many needles of the same length at once, by keeping their hashes in a set.
+///
+/// The items only need to be `Eq`: each one is hashed as a number for its
+/// distinct item in the needle, or 0 if it is not in the needle, which gives
+/// equal windows the same hash. Finding that number scans the `d` distinct items
+/// of the needle, so each roll is **O (d)** and the search **O ((N + M) d)** on
+/// average. Use [`RabinKarp::new`] with [`BytePositions`](super::BytePositions)
+/// for bytes and strings, or [`HashPositions`](super::HashPositions) for items
+/// that are `Hash`, to find it in **O (1)**.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::substring::rabin_karp_search;
+/// let matches: Vec<usize> = rabin_karp_search("the cat sat on the mat", "at").collect();
+/// assert_eq!(matches, [5, 9, 20]);
+/// ```
+pub fn rabin_karp_search<'a, H, N>(haystack: &'a H, needle: &'a N) -> RabinKarp<'a, H::Item>
+where
+    H: Text + ?Sized,
+    N: Text<Item = H::Item> + ?Sized,
+    H::Item: Eq,
+{
+    RabinKarp::new(haystack, needle)
+}
+
+/// The iterator over the match positions returned by [`rabin_karp_search`].
+#[derive(Debug, Clone)]
+pub struct RabinKarp<'a, T, P = ScanPositions<'a, T>> {
+    haystack: &'a [T],
+    needle: &'a [T],
+    // Gives each item the number it is hashed as.
+    ids: P,
+    needle_hash: u64,
+    first_power: u64,
+    // The hash of the window that starts at 'start', once it is computed.
+    window_hash: Option<u64>,
+    start: usize,
+    // Matches before this position overlap with the previous one.
+    next_match: usize,
+    overlapping: bool,
+}
+
+impl<'a, T: Eq, P: LastPositions<'a, T>> RabinKarp<'a, T, P> {
+    /// Same as [`rabin_karp_search`], finding the number of each item with `P`.
+    pub fn new<H, N>(haystack: &'a H, needle: &'a N) -> Self
+    where
+        H: Text<Item = T> + ?Sized,
+        N: Text<Item = T> + ?Sized,
+    {
+        let needle = needle.as_items();
+        let mut search = RabinKarp {
+            haystack: haystack.as_items(),
+            needle,
+            ids: P::new(needle),
+            needle_hash: 0,
+            // BASE ^ (len - 1), to remove the first item of a window.
+            first_power: 1,
+            window_hash: None,
+            start: 0,
+            next_match: 0,
+            overlapping: false,
+        };
+        for _ in 1..needle.len() {
+            search.first_power = mul_mod(search.first_power, BASE);
+        }
+        search.needle_hash = search.hash(needle);
+        search
+    }
+
+    /// Also returns the matches that overlap with the previous one.
+    pub fn overlapping(mut self) -> Self {
+        self.overlapping = true;
+        self
+    }
+
+    fn hash(&self, items: &[T]) -> u64 {
+        items.iter().fold(0, |hash, item| {
+            add_mod(mul_mod(hash, BASE), self.ids.id(item))
+        })
+    }
+}
+
+impl<'a, T: Eq, P: LastPositions<'a, T>> Iterator for RabinKarp<'a, T, P> {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (haystack, needle) = (self.haystack, self.needle);
+        let len = needle.len();
+        while self.start + len <= haystack.len() {
+            let start = self.start;
+            let hash = match self.window_hash {
+                Some(hash) => hash,
+                None => self.hash(&haystack[start..start + len]),
+            };
+            let found = start >= self.next_match
+                && hash == self.needle_hash
+                && haystack[start..start + len] == *needle;
+            // Roll the hash to the next window: remove the first item and add the
+            // one after the end. An empty window always hashes to 0.
+            self.start += 1;
+            self.window_hash = haystack.get(start + len).filter(|_| len > 0).map(|next| {
+                let first = mul_mod(self.ids.id(&haystack[start]), self.first_power);
+                let hash = add_mod(hash, MODULUS - first);
+                add_mod(mul_mod(hash, BASE), self.ids.id(next))
+            });
+            if found {
+                self.next_match = after_match(start, len, self.overlapping);
+                return Some(start);
+            }
+        }
+        None
+    }
+}
+
+fn add_mod(a: u64, b: u64) -> u64 {
+    (a + b) % MODULUS
+}
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % MODULUS as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rabin_karp_search;
+
+    #[test]
+    fn test_rabin_karp_search() {
+        assert!(rabin_karp_search("mississippi", "issi").eq([1]));
+        assert!(rabin_karp_search("mississippi", "issi")
+            .overlapping()
+            .eq([1, 4]));
+        assert!(rabin_karp_search("mississippi", "i").eq([1, 4, 7, 10]));
+        let big: Vec<u64> = (0..1000).map(|i| i % 7).collect();
+        assert_eq!(rabin_karp_search(&big, &[5, 6, 0]).count(), 142);
+    }
+}
@@ -0,0 +1,326 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use super::Text;
+use crate::search::equal_range_by;
+
+/// Returns the suffix array of a text: the start of every suffix, ordered by the
+/// suffixes.
+///
+/// It uses prefix doubling: the suffixes are first ordered by their first item,
+/// and then each round orders them by their first 2k items using the ranks of
+/// the previous round, as the first 2k items of a suffix are the first k items of
+/// it and the first k items of the suffix k positions after. It takes
+/// **O (log N)** rounds of a sort, **O (N log² N)** in total.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::substring::suffix_array;
+/// // a, ana, anana, banana, na, nana
+/// assert_eq!(suffix_array("banana"), [5, 3, 1, 0, 4, 2]);
+/// ```
+pub fn suffix_array<H>(text: &H) -> Vec<usize>
+where
+    H: Text + ?Sized,
+    H::Item: Ord,
+{
+    let text = text.as_items();
+    let len = text.len();
+    let mut suffixes: Vec<usize> = (0..len).collect();
+    suffixes.sort_by(|&a, &b| text[a].cmp(&text[b]));
+    // Suffixes with the same first k items have the same rank.
+    let mut rank = vec![0; len];
+    for i in 1..len {
+        let (a, b) = (suffixes[i - 1], suffixes[i]);
+        rank[b] = rank[a] + (text[a] != text[b]) as usize;
+    }
+    let mut k = 1;
+    while k < len && rank[suffixes[len - 1]] < len - 1 {
+        // The rank of the first k items and of the next k, where a suffix that
+        // ends before them comes first.
+        let key = |i: usize| (rank[i], rank.get(i + k).map_or(0, |r| r + 1));
+        suffixes.sort_by_key(|&i| key(i));
+        let mut next_rank = vec![0; len];
+        for i in 1..len {
+            let (a, b) = (suffixes[i - 1], suffixes[i]);
+            next_rank[b] = next_rank[a] + (key(a) != key(b)) as usize;
+        }
+        rank = next_rank;
+        k *= 2;
+    }
+    suffixes
+}
+
+/// Returns the longest common prefix array of a text and its suffix array with
+/// Kasai's algorithm, in **O (N)**.
+///
+/// `lcp[i]` is the length of the longest common prefix of the suffixes at
+/// `suffixes[i - 1]` and `suffixes[i]`, and `lcp[0]` is 0.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::substring::{lcp_array, suffix_array};
+/// let suffixes = suffix_array("banana");
+/// assert_eq!(lcp_array("banana", &suffixes), [0, 1, 3, 0, 0, 2]);
+/// ```
+pub fn lcp_array<H>(text: &H, suffixes: &[usize]) -> Vec<usize>
+where
+    H: Text + ?Sized,
+    H::Item: Eq,
+{
+    let text = text.as_items();
+    let len = text.len();
+    let mut rank = vec![0; len];
+    for (i, &suffix) in suffixes.iter().enumerate() {
+        rank[suffix] = i;
+    }
+    let mut lcp = vec![0; len];
+    // If the suffix at i shares h items with the one before it in the suffix
+    // array, the suffix at i + 1 shares at least h - 1, so 'h' only goes down by
+    // one each step and the loop is linear.
+    let mut h: usize = 0;
+    for i in 0..len {
+        if rank[i] == 0 {
+            h = 0;
+            continue;
+        }
+        let j = suffixes[rank[i] - 1];
+        while i + h < len && j + h < len && text[i + h] == text[j + h] {
+            h += 1;
+        }
+        lcp[rank[i]] = h;
+        h = h.saturating_sub(1);
+    }
+    lcp
+}
+
+/// Returns where the longest sequence that appears in both texts is, as a range
+/// of each text, or `None` if they have nothing in common.
+///
+/// Both texts are joined with a separator that matches nothing, and the longest
+/// common substring is the longest common prefix of two suffixes next to each
+/// other in the suffix array that start in different texts.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::substring::longest_common_substring;
+/// let (a, b) = longest_common_substring("xabxac", "abcabxabcd").unwrap();
+/// assert_eq!((&"xabxac"[a], &"abcabxabcd"[b]), ("abxa", "abxa"));
+/// assert_eq!(longest_common_substring("abc", "xyz"), None);
+/// ```
+pub fn longest_common_substring<A, B>(a: &A, b: &B) -> Option<(Range<usize>, Range<usize>)>
+where
+    A: Text + ?Sized,
+    B: Text<Item = A::Item> + ?Sized,
+    A::Item: Ord,
+{
+    let (a, b) = (a.as_items(), b.as_items());
+    // 'None' is the separator, it appears once so no common prefix goes over it.
+    let joined: Vec<Option<&A::Item>> = a
+        .iter()
+        .map(Some)
+        .chain([None])
+        .chain(b.iter().map(Some))
+        .collect();
+    let suffixes = suffix_array(&joined);
+    let lcp = lcp_array(&joined, &suffixes);
+    let mut best: Option<(usize, usize, usize)> = None;
+    for i in 1..joined.len() {
+        let (x, y) = (suffixes[i - 1], suffixes[i]);
+        let (start_a, start_b) = match (x < a.len(), y < a.len()) {
+            (true, false) => (x, y),
+            (false, true) => (y, x),
+            _ => continue,
+        };
+        if lcp[i] > best.map_or(0, |(_, _, len)| len) {
+            best = Some((start_a, start_b - a.len() - 1, lcp[i]));
+        }
+    }
+    best.map(|(a, b, len)| (a..a + len, b..b + len))
+}
+
+/// An index of a text for fast substring queries, built from its suffix array
+/// and its LCP array.
+///
+/// Every suffix that starts with a pattern is next to the others in the suffix
+/// array, so the occurrences of a pattern are found with a lower and an upper
+/// bound over it, in **O (M log N)**. For a `str` the positions and ranges are in
+/// bytes, and the ranges of the repeated substrings may not be on char
+/// boundaries.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::substring::SuffixIndex;
+/// let text = "mississippi";
+/// let index = SuffixIndex::new(text);
+/// assert!(index.contains("ssi"));
+/// assert_eq!(index.count("ss"), 2);
+/// assert_eq!(index.positions("i"), [1, 4, 7, 10]);
+/// assert_eq!(&text[index.longest_repeated_substring().unwrap()], "issi");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SuffixIndex<'a, T> {
+    text: &'a [T],
+    suffixes: Vec<usize>,
+    lcp: Vec<usize>,
+}
+
+impl<'a, T: Ord> SuffixIndex<'a, T> {
+    pub fn new<H: Text<Item = T> + ?Sized>(text: &'a H) -> Self {
+        let text = text.as_items();
+        let suffixes = suffix_array(text);
+        let lcp = lcp_array(text, &suffixes);
+        SuffixIndex {
+            text,
+            suffixes,
+            lcp,
+        }
+    }
+
+    /// Returns true if the pattern appears in the text.
+    pub fn contains<P: Text<Item = T> + ?Sized>(&self, pattern: &P) -> bool {
+        self.count(pattern) > 0
+    }
+
+    /// Returns how many times the pattern appears in the text, overlapping
+    /// occurrences included.
+    pub fn count<P: Text<Item = T> + ?Sized>(&self, pattern: &P) -> usize {
+        match pattern.as_items() {
+            // Like the other searches, an empty pattern is at every position.
+            [] => self.text.len() + 1,
+            pattern => self.range(pattern).len(),
+        }
+    }
+
+    /// Returns every position of the pattern in the text, in order.
+    pub fn positions<P: Text<Item = T> + ?Sized>(&self, pattern: &P) -> Vec<usize> {
+        if pattern.as_items().is_empty() {
+            return (0..=self.text.len()).collect();
+        }
+        let mut positions = self.suffixes[self.range(pattern.as_items())].to_vec();
+        positions.sort_unstable();
+        positions
+    }
+
+    /// Returns where the longest sequence that appears more than once in the
+    /// text is, or `None` if no item repeats. The occurrences may overlap.
+    pub fn longest_repeated_substring(&self) -> Option<Range<usize>> {
+        // The longest common prefix of two suffixes next to each other.
+        let (i, &len) = self
+            .lcp
+            .iter()
+            .enumerate()
+            .max_by_key(|&(i, len)| (len, !i))?;
+        (len > 0).then(|| self.suffixes[i]..self.suffixes[i] + len)
+    }
+
+    pub fn suffix_array(&self) -> &[usize] {
+        &self.suffixes
+    }
+
+    pub fn lcp_array(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// The range of the suffix array with the suffixes that start with the pattern.
+    fn range(&self, pattern: &[T]) -> Range<usize> {
+        equal_range_by(&self.suffixes, |&suffix| {
+            let suffix = &self.text[suffix..];
+            // Suffixes that are a prefix of the pattern are smaller than it.
+            let prefix = &suffix[..suffix.len().min(pattern.len())];
+            match prefix.cmp(pattern) {
+                Ordering::Equal if prefix.len() < pattern.len() => Ordering::Less,
+                ordering => ordering,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lcp_array, longest_common_substring, suffix_array, SuffixIndex};
+
+    /// Every string of `a`, `b` and `c` of a length.
+    fn strings(len: u32) -> impl Iterator<Item = Vec<u8>> {
+        (0..3_usize.pow(len)).map(move |mut n| {
+            (0..len)
+                .map(|_| {
+                    let c = b'a' + (n % 3) as u8;
+                    n /= 3;
+                    c
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_suffix_array() {
+        for len in 0..7 {
+            for text in strings(len) {
+                let suffixes = suffix_array(&text);
+                let mut expected: Vec<usize> = (0..text.len()).collect();
+                expected.sort_by_key(|&i| &text[i..]);
+                assert_eq!(suffixes, expected);
+                let lcp = lcp_array(&text, &suffixes);
+                for i in 1..text.len() {
+                    let (a, b) = (&text[suffixes[i - 1]..], &text[suffixes[i]..]);
+                    let common = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+                    assert_eq!(lcp[i], common);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_suffix_index() {
+        let text = b"abracadabra abracadabra";
+        let index = SuffixIndex::new(text);
+        for start in 0..text.len() {
+            for end in start..=text.len() {
+                let pattern = &text[start..end];
+                let expected: Vec<usize> = (0..=text.len() - pattern.len())
+                    .filter(|&i| text[i..].starts_with(pattern))
+                    .collect();
+                assert_eq!(index.positions(pattern), expected);
+                assert_eq!(index.count(pattern), expected.len());
+            }
+        }
+        assert!(!index.contains(b"abrax"));
+        assert_eq!(index.count(b"dabra abracadabra!"), 0);
+        assert_eq!(index.longest_repeated_substring(), Some(0..11));
+
+        let index = SuffixIndex::new("abcd");
+        assert_eq!(index.longest_repeated_substring(), None);
+        let index = SuffixIndex::new(&[1, 2, 1, 2, 1]);
+        assert_eq!(index.longest_repeated_substring(), Some(0..3));
+        assert_eq!(SuffixIndex::new("").count(""), 1);
+        assert!(!SuffixIndex::new("").contains("a"));
+    }
+
+    #[test]
+    fn test_longest_common_substring() {
+        for a in strings(4) {
+            for b in strings(3) {
+                // The longest window of 'b' that is also a window of 'a'.
+                let expected = (1..=b.len())
+                    .rev()
+                    .find(|&len| b.windows(len).any(|w| a.windows(len).any(|x| x == w)))
+                    .unwrap_or(0);
+                match longest_common_substring(&a, &b) {
+                    Some((x, y)) => {
+                        assert_eq!(a[x.clone()], b[y.clone()]);
+                        assert_eq!(x.len(), expected);
+                    }
+                    None => assert_eq!(expected, 0),
+                }
+            }
+        }
+        let (a, b) = longest_common_substring("the quick fox", "a quick dog").unwrap();
+        assert_eq!(&"the quick fox"[a], " quick ");
+        assert_eq!(&"a quick dog"[b], " quick ");
+    }
+}
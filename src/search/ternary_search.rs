@@ -0,0 +1,181 @@
+use super::{bisect_int, BisectInt};
+
+/// This function finds the value in `[lo, hi]` where a unimodal function is the
+/// smallest, up to `eps`.
+///
+/// The function must decrease until its minimum and increase after it. Each step
+/// evaluates it at one and two thirds of the range, and as the minimum can not be
+/// on the side of the bigger result, it discards that third. It takes
+/// **O (log ((hi - lo) / eps))** steps. To find a maximum, negate the function.
+///
+/// # Panics
+///
+/// Panics if `eps` is negative or NaN, or if an end is NaN.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::ternary_search;
+/// let x = ternary_search(-10.0, 10.0, 1e-9, |x| (x - 3.0) * (x - 3.0) + 1.0);
+/// assert!((x - 3.0).abs() < 1e-6);
+/// ```
+pub fn ternary_search<F>(mut lo: f64, mut hi: f64, eps: f64, mut f: F) -> f64
+where
+    F: FnMut(f64) -> f64,
+{
+    assert!(eps >= 0.0, "eps must be a positive number");
+    assert!(!lo.is_nan() && !hi.is_nan(), "the ends can not be NaN");
+    while hi / 2.0 - lo / 2.0 > eps / 2.0 {
+        // A third of the width, divided first so it does not overflow.
+        let third = hi / 3.0 - lo / 3.0;
+        let (m1, m2) = (lo + third, hi - third);
+        // Stop when the floats are too close to split.
+        if m1 <= lo || m2 >= hi || m1 >= m2 {
+            break;
+        }
+        if f(m1) < f(m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+    lo / 2.0 + hi / 2.0
+}
+
+/// Same as [`ternary_search`], but reuses one of the two evaluations of each step.
+///
+/// The two points split the range following the golden ratio, so after discarding
+/// a part one of them is at the right place for the next step. It needs one
+/// evaluation per step, and each step keeps 61.8% of the range, against two
+/// evaluations to keep 66.7% in a ternary search, which is the best to do when the
+/// function is expensive.
+///
+/// # Panics
+///
+/// Panics if `eps` is negative or NaN, or if an end is NaN.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::golden_section_search;
+/// let mut calls = 0;
+/// let x = golden_section_search(0.0, 4.0, 1e-9, |x| {
+///     calls += 1;
+///     (x - 1.5_f64).powi(2)
+/// });
+/// assert!((x - 1.5).abs() < 1e-6);
+/// assert!(calls < 50);
+/// ```
+pub fn golden_section_search<F>(mut lo: f64, mut hi: f64, eps: f64, mut f: F) -> f64
+where
+    F: FnMut(f64) -> f64,
+{
+    assert!(eps >= 0.0, "eps must be a positive number");
+    assert!(!lo.is_nan() && !hi.is_nan(), "the ends can not be NaN");
+    // 1 / φ, where φ is the golden ratio.
+    const INV_PHI: f64 = 0.618_033_988_749_894_9;
+    // The point at a fraction of the way from lo to hi, as a weighted average so it
+    // does not overflow.
+    let at = |lo: f64, hi: f64, t: f64| lo * (1.0 - t) + hi * t;
+    let (mut m1, mut m2) = (at(lo, hi, 1.0 - INV_PHI), at(lo, hi, INV_PHI));
+    let (mut f1, mut f2) = (f(m1), f(m2));
+    while hi / 2.0 - lo / 2.0 > eps / 2.0 {
+        if f1 < f2 {
+            // The minimum is in [lo, m2], the old m1 becomes the new m2.
+            hi = m2;
+            (m2, f2) = (m1, f1);
+            m1 = at(lo, hi, 1.0 - INV_PHI);
+            if m1 <= lo || m1 >= m2 {
+                break;
+            }
+            f1 = f(m1);
+        } else {
+            // The minimum is in [m1, hi], the old m2 becomes the new m1.
+            lo = m1;
+            (m1, f1) = (m2, f2);
+            m2 = at(lo, hi, INV_PHI);
+            if m2 >= hi || m2 <= m1 {
+                break;
+            }
+            f2 = f(m2);
+        }
+    }
+    lo / 2.0 + hi / 2.0
+}
+
+/// This function finds the smallest value in `[lo, hi]` where a unimodal function
+/// is the smallest.
+///
+/// The function must strictly decrease until its minimum and not decrease after
+/// it, so the minimum is the first value that is not bigger than the next one,
+/// which is found with [`bisect_int`] in **O (log (hi - lo))** evaluations.
+///
+/// # Panics
+///
+/// Panics if `lo` is bigger than `hi`.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::ternary_search_int;
+/// assert_eq!(ternary_search_int(-100, 100, |x: i32| (x - 17).abs()), 17);
+/// assert_eq!(ternary_search_int(0, u64::MAX, |x| x.abs_diff(1 << 40)), 1 << 40);
+/// ```
+pub fn ternary_search_int<T, B, F>(lo: T, hi: T, mut f: F) -> T
+where
+    T: BisectInt,
+    B: PartialOrd,
+    F: FnMut(T) -> B,
+{
+    assert!(lo <= hi, "lo must not be bigger than hi");
+    // The first value that is not bigger than the next, 'x' is smaller than 'hi'
+    // so 'x + 1' can not overflow. If there is none, the function decreases until
+    // the end.
+    bisect_int(lo, hi, |x| x == hi || f(x) <= f(x.plus_one())).unwrap_or(hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{golden_section_search, ternary_search, ternary_search_int};
+
+    #[test]
+    fn test_ternary_search() {
+        for target in [-7.5, 0.0, 0.25, 9.0] {
+            let x = ternary_search(-10.0, 10.0, 1e-10, |x| (x - target).abs());
+            assert!((x - target).abs() < 1e-8);
+            let x = golden_section_search(-10.0, 10.0, 1e-10, |x| (x - target).abs());
+            assert!((x - target).abs() < 1e-8);
+        }
+        // A maximum, by negating the function.
+        let x = golden_section_search(0.0, std::f64::consts::PI, 1e-10, |x| -x.sin());
+        assert!((x - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+        // The minimum at an end.
+        let x = ternary_search(0.0, 1.0, 0.0, |x| x);
+        assert!(x < 1e-300);
+        let x = golden_section_search(f64::MIN, f64::MAX, 1.0, |x| (x - 1e300).abs());
+        assert!((x - 1e300).abs() <= 1e285);
+    }
+
+    #[test]
+    fn test_ternary_search_int() {
+        for lo in -5..5 {
+            for hi in lo..5 {
+                for min in -7..7 {
+                    let expected = min.clamp(lo, hi);
+                    assert_eq!(
+                        ternary_search_int(lo, hi, |x: i8| (x - min).abs()),
+                        expected
+                    );
+                }
+            }
+        }
+        assert_eq!(ternary_search_int(i64::MIN, i64::MAX, |x| x), i64::MIN);
+        assert_eq!(
+            ternary_search_int(i64::MIN, i64::MAX, |x| -(x as i128)),
+            i64::MAX
+        );
+        // A plateau after the minimum returns the start of it.
+        assert_eq!(ternary_search_int(0, 10, |x: u8| x.max(4).abs_diff(4)), 0);
+        assert_eq!(ternary_search_int(0, 10, |x: u8| 6_u8.saturating_sub(x)), 6);
+    }
+}
@@ -0,0 +1,579 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Write as _};
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Mutex, PoisonError};
+
+use super::Interpolate;
+
+/// An operation done by a search, as captured by a [`Tracer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchEvent {
+    /// The element at `index` of the haystack was read. For a traced file it is
+    /// the byte offset the search jumped to.
+    Probe { index: usize },
+    /// Two values were compared. `None` is a value that is not in the haystack,
+    /// like the needle.
+    Compare { a: Option<usize>, b: Option<usize> },
+    /// The searched function was called at `at`, for the searches over a range
+    /// of values like [`bisect_float`](super::bisect_float).
+    Evaluate { at: f64 },
+}
+
+/// Records what a search does, to check how many steps it takes or to draw them.
+///
+/// Searches are generic over the element type, so instead of a traced copy of
+/// each of them the tracer wraps the data: [`haystack`](Tracer::haystack) and
+/// [`needle`](Tracer::needle) return [`Traced`] values that log every read and
+/// compare, and any search of this module can be called with them. The
+/// searches that call a function instead of reading a slice are traced with
+/// [`probe_fn`](Tracer::probe_fn) or [`eval_fn`](Tracer::eval_fn), and the file
+/// searches with [`source`](Tracer::source).
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::{exponential_search, Tracer};
+/// let arr: Vec<u32> = (0..1000).collect();
+/// let tracer = Tracer::new();
+/// let haystack = tracer.haystack(&arr);
+/// assert_eq!(exponential_search(&haystack, &tracer.needle(&3)), Some(3));
+/// let trace = tracer.take();
+/// assert_eq!(trace.comparisons(), 5);
+/// assert_eq!(trace.probes(), [0, 1, 2, 4, 3]);
+/// ```
+#[derive(Debug, Default)]
+pub struct Tracer {
+    events: Mutex<Vec<SearchEvent>>,
+    len: AtomicUsize,
+}
+
+impl Tracer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps each element of the haystack with its index, so reading or
+    /// comparing it is recorded.
+    pub fn haystack<'t, T>(&'t self, haystack: &'t [T]) -> Vec<Traced<'t, T>> {
+        self.len.fetch_max(haystack.len(), AtomicOrdering::Relaxed);
+        haystack
+            .iter()
+            .enumerate()
+            .map(|(index, value)| Traced {
+                value,
+                index: Some(index),
+                tracer: self,
+            })
+            .collect()
+    }
+
+    /// Wraps a value that is not in the haystack, so it can be compared with
+    /// its elements.
+    pub fn needle<'t, T>(&'t self, needle: &'t T) -> Traced<'t, T> {
+        Traced {
+            value: needle,
+            index: None,
+            tracer: self,
+        }
+    }
+
+    /// Wraps a function of an index, like the one taken by
+    /// [`crystal_balls`](super::crystal_balls), so each call is recorded as a probe.
+    pub fn probe_fn<'t, R>(
+        &'t self,
+        mut f: impl FnMut(usize) -> R + 't,
+    ) -> impl FnMut(usize) -> R + 't {
+        move |index| {
+            self.record(SearchEvent::Probe { index });
+            f(index)
+        }
+    }
+
+    /// Wraps a function of a number, like the one taken by
+    /// [`bisect_int`](super::bisect_int), so each call is recorded with its argument.
+    pub fn eval_fn<'t, X, R>(&'t self, mut f: impl FnMut(X) -> R + 't) -> impl FnMut(X) -> R + 't
+    where
+        X: Interpolate,
+    {
+        move |at| {
+            self.record(SearchEvent::Evaluate { at: at.to_f64() });
+            f(at)
+        }
+    }
+
+    /// Wraps a file or any other `Read + Seek` source, so each jump of
+    /// [`binary_search_file_by`](super::binary_search_file_by) is recorded as a
+    /// probe of the byte offset.
+    pub fn source<R>(&self, source: R) -> TracedSource<'_, R> {
+        TracedSource {
+            source,
+            tracer: self,
+        }
+    }
+
+    /// Returns what was recorded since the last call, so the tracer can be
+    /// reused for the next search over the same values.
+    pub fn take(&self) -> Trace {
+        let events =
+            std::mem::take(&mut *self.events.lock().unwrap_or_else(PoisonError::into_inner));
+        Trace {
+            len: self.len.load(AtomicOrdering::Relaxed),
+            events,
+        }
+    }
+
+    fn record(&self, event: SearchEvent) {
+        // A panic in another thread cannot leave the list half written.
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(event);
+    }
+}
+
+/// Runs a search over a traced haystack and needle, returning its result and
+/// what it did.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::{binary_search, trace_search};
+/// let arr: Vec<u32> = (0..256).collect();
+/// let (found, trace) = trace_search(&arr, &200, |haystack, needle| binary_search(haystack, needle));
+/// assert!(found);
+/// assert!(trace.probes().len() <= 9);
+/// ```
+pub fn trace_search<T, R, F>(haystack: &[T], needle: &T, search: F) -> (R, Trace)
+where
+    F: FnOnce(&[Traced<'_, T>], &Traced<'_, T>) -> R,
+{
+    let tracer = Tracer::new();
+    let result = search(&tracer.haystack(haystack), &tracer.needle(needle));
+    (result, tracer.take())
+}
+
+/// A value handed out by a [`Tracer`], that records when it is read or compared.
+///
+/// It implements the traits the searches need when the wrapped type does, and
+/// derefs to it. Reading it through [`get`](Traced::get) is not recorded.
+pub struct Traced<'t, T> {
+    value: &'t T,
+    index: Option<usize>,
+    tracer: &'t Tracer,
+}
+
+impl<'t, T> Traced<'t, T> {
+    /// The wrapped value.
+    pub fn get(&self) -> &'t T {
+        self.value
+    }
+
+    /// The index in the haystack, or `None` for a needle.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    fn probe(&self) {
+        if let Some(index) = self.index {
+            self.tracer.record(SearchEvent::Probe { index });
+        }
+    }
+
+    fn compare(&self, other: &Self) {
+        self.tracer.record(SearchEvent::Compare {
+            a: self.index,
+            b: other.index,
+        });
+    }
+}
+
+impl<T> Clone for Traced<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Traced<'_, T> {}
+
+impl<T: fmt::Debug> fmt::Debug for Traced<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T> Deref for Traced<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.probe();
+        self.value
+    }
+}
+
+impl<T: PartialEq> PartialEq for Traced<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.compare(other);
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Traced<'_, T> {}
+
+impl<T: PartialOrd> PartialOrd for Traced<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.compare(other);
+        self.value.partial_cmp(other.value)
+    }
+}
+
+impl<T: Ord> Ord for Traced<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other);
+        self.value.cmp(other.value)
+    }
+}
+
+impl<T: Hash> Hash for Traced<'_, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.probe();
+        self.value.hash(state);
+    }
+}
+
+impl<T: AsRef<U>, U: ?Sized> AsRef<U> for Traced<'_, T> {
+    fn as_ref(&self) -> &U {
+        self.probe();
+        self.value.as_ref()
+    }
+}
+
+impl<T: Interpolate> Interpolate for Traced<'_, T> {
+    fn to_f64(self) -> f64 {
+        self.probe();
+        self.value.to_f64()
+    }
+}
+
+/// A `Read + Seek` source handed out by [`Tracer::source`].
+pub struct TracedSource<'t, R> {
+    source: R,
+    tracer: &'t Tracer,
+}
+
+impl<R: Read> Read for TracedSource<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.source.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for TracedSource<'_, R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let offset = self.source.seek(pos)?;
+        self.tracer.record(SearchEvent::Probe {
+            index: offset as usize,
+        });
+        Ok(offset)
+    }
+}
+
+/// The events recorded by a [`Tracer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    len: usize,
+    events: Vec<SearchEvent>,
+}
+
+impl Trace {
+    /// The length of the biggest traced haystack.
+    pub fn haystack_len(&self) -> usize {
+        self.len
+    }
+
+    pub fn events(&self) -> &[SearchEvent] {
+        &self.events
+    }
+
+    /// The indices of the haystack that were read, in order. An element that
+    /// is compared is read, so each comparison adds its haystack elements.
+    pub fn probes(&self) -> Vec<usize> {
+        let mut probes = Vec::new();
+        for event in &self.events {
+            match *event {
+                SearchEvent::Probe { index } => probes.push(index),
+                SearchEvent::Compare { a, b } => probes.extend(a.into_iter().chain(b)),
+                SearchEvent::Evaluate { .. } => {}
+            }
+        }
+        probes
+    }
+
+    pub fn comparisons(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|event| matches!(event, SearchEvent::Compare { .. }))
+            .count()
+    }
+
+    /// The arguments the searched function was called with, in order.
+    pub fn evaluations(&self) -> Vec<f64> {
+        self.events
+            .iter()
+            .filter_map(|event| match *event {
+                SearchEvent::Evaluate { at } => Some(at),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Exports the trace as JSON, with the length of the haystack, the counters
+    /// and the list of events. A `null` operand of a compare is the needle.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        // Writing to a String never fails.
+        let _ = write!(
+            json,
+            r#"{{"len":{},"probes":{},"comparisons":{},"events":["#,
+            self.len,
+            self.probes().len(),
+            self.comparisons()
+        );
+        let operand = |x: Option<usize>| x.map_or_else(|| "null".to_string(), |x| x.to_string());
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            let _ = match *event {
+                SearchEvent::Probe { index } => write!(json, r#"{{"op":"probe","index":{index}}}"#),
+                SearchEvent::Compare { a, b } => write!(
+                    json,
+                    r#"{{"op":"compare","a":{},"b":{}}}"#,
+                    operand(a),
+                    operand(b)
+                ),
+                // JSON has no infinity or NaN.
+                SearchEvent::Evaluate { at } if !at.is_finite() => {
+                    write!(json, r#"{{"op":"evaluate","at":null}}"#)
+                }
+                SearchEvent::Evaluate { at } => write!(json, r#"{{"op":"evaluate","at":{at}}}"#),
+            };
+        }
+        json.push_str("]}");
+        json
+    }
+
+    /// Exports the path of the search as an SVG, with one row per probe or
+    /// evaluation from top to bottom, and its position in the haystack (or the
+    /// range of values) from left to right.
+    pub fn to_svg(&self) -> String {
+        const WIDTH: f64 = 400.0;
+        const ROW: usize = 16;
+        const MARGIN: f64 = 8.0;
+        let steps: Vec<f64> = self
+            .events
+            .iter()
+            .flat_map(|event| -> Vec<f64> {
+                match *event {
+                    SearchEvent::Probe { index } => vec![index as f64],
+                    SearchEvent::Compare { a, b } => {
+                        a.into_iter().chain(b).map(|index| index as f64).collect()
+                    }
+                    SearchEvent::Evaluate { at } if at.is_finite() => vec![at],
+                    SearchEvent::Evaluate { .. } => vec![],
+                }
+            })
+            .collect();
+        let (mut lo, mut hi) = if self.len > 0 {
+            (0.0, (self.len - 1) as f64)
+        } else {
+            (f64::INFINITY, f64::NEG_INFINITY)
+        };
+        for &step in &steps {
+            lo = lo.min(step);
+            hi = hi.max(step);
+        }
+        // With a single position every step goes in the middle.
+        let span = if hi > lo { hi - lo } else { 0.0 };
+        let x = |step: f64| {
+            if span == 0.0 {
+                MARGIN + WIDTH / 2.0
+            } else {
+                MARGIN + (step - lo) / span * WIDTH
+            }
+        };
+        let y = |row: usize| (row * ROW + ROW / 2) as f64;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            WIDTH + 2.0 * MARGIN,
+            steps.len().max(1) * ROW,
+            WIDTH + 2.0 * MARGIN,
+            steps.len().max(1) * ROW
+        );
+        let _ = writeln!(
+            svg,
+            "<title>{} probes, {} comparisons</title>",
+            self.probes().len(),
+            self.comparisons()
+        );
+        let points: Vec<String> = steps
+            .iter()
+            .enumerate()
+            .map(|(row, &step)| format!("{:.1},{:.1}", x(step), y(row)))
+            .collect();
+        let _ = writeln!(
+            svg,
+            r#"<polyline points="{}" fill="none" stroke="lightgray"/>"#,
+            points.join(" ")
+        );
+        for (row, &step) in steps.iter().enumerate() {
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="4" fill="steelblue"><title>{}: {}</title></circle>"#,
+                x(step),
+                y(row),
+                row,
+                step
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{trace_search, SearchEvent, Tracer};
+    use crate::search::{
+        binary_search, binary_search_file, bisect_int, branchless_binary_search, crystal_balls,
+        exponential_search, interpolation_search, jump_search, linear_search_index,
+        parallel_linear_search, simd_position, substring::kmp_search, EytzingerSet,
+    };
+
+    #[test]
+    fn test_binary_search_probes() {
+        let arr: Vec<u32> = (0..256).map(|x| x * 2).collect();
+        // Every element, and every gap between them.
+        for needle in 0..=512 {
+            let (found, trace) = trace_search(&arr, &needle, |h, n| binary_search(h, n));
+            assert_eq!(found, needle % 2 == 0 && needle < 512);
+            assert!(trace.probes().len() <= 9, "{needle}: {:?}", trace.probes());
+            assert_eq!(trace.comparisons(), trace.probes().len());
+
+            let (_, trace) = trace_search(&arr, &needle, |h, n| branchless_binary_search(h, n));
+            assert!(trace.probes().len() <= 10);
+        }
+    }
+
+    #[test]
+    fn test_linear_searches_probes() {
+        let arr: Vec<u8> = (0..100).collect();
+        let (index, trace) = trace_search(&arr, &41, |h, n| linear_search_index(h, n));
+        assert_eq!(index, Some(41));
+        assert_eq!(trace.probes(), (0..=41).collect::<Vec<_>>());
+
+        let (index, trace) = trace_search(&arr, &40, |h, &n| simd_position(h, n));
+        assert_eq!(index, Some(40));
+        // Whole blocks of 32 are compared.
+        assert_eq!(trace.probes(), (0..64).collect::<Vec<_>>());
+
+        let (index, trace) = trace_search(&arr, &99, |h, n| parallel_linear_search(h, n));
+        assert_eq!(index, Some(99));
+        assert!(trace.comparisons() >= 100);
+    }
+
+    #[test]
+    fn test_sorted_searches_probes() {
+        let arr: Vec<u32> = (0..10_000).collect();
+        for needle in [0, 1, 5000, 9999, 10_000] {
+            let (_, trace) = trace_search(&arr, &needle, |h, n| exponential_search(h, n));
+            // 2 log N for the galloping and the binary search.
+            assert!(trace.comparisons() <= 2 * 14 + 2);
+
+            // Uniform data is found with the first guess, after checking both ends.
+            let (_, trace) = trace_search(&arr, &needle, |h, n| interpolation_search(h, n));
+            assert!(trace.comparisons() <= 4, "{:?}", trace.events());
+
+            let (_, trace) = trace_search(&arr, &needle, |h, n| jump_search(h, n, 100));
+            // One compare per block, and two per element of the last block.
+            assert!(trace.comparisons() <= 10_000 / 100 + 2 * 100);
+        }
+
+        let tracer = Tracer::new();
+        let set: EytzingerSet<_> = tracer.haystack(&arr).into_iter().collect();
+        // Building sorts the values.
+        assert!(tracer.take().comparisons() >= arr.len() - 1);
+        assert!(set.contains(&tracer.needle(&1234)));
+        assert!(tracer.take().comparisons() <= 15);
+    }
+
+    #[test]
+    fn test_substring_probes() {
+        let text = b"abababababababababab";
+        let tracer = Tracer::new();
+        let haystack = tracer.haystack(text);
+        let needle: Vec<_> = b"abab".iter().map(|b| tracer.needle(b)).collect();
+        assert_eq!(kmp_search(&haystack, &needle).count(), 5);
+        // KMP never compares more than 2N times.
+        assert!(tracer.take().comparisons() <= 2 * text.len());
+    }
+
+    #[test]
+    fn test_function_probes() {
+        let tracer = Tracer::new();
+        let drops = crystal_balls(100, 2, tracer.probe_fn(|floor| floor >= 37));
+        assert_eq!(drops.index, Some(37));
+        assert_eq!(tracer.take().probes().len(), drops.drops);
+
+        let tracer = Tracer::new();
+        assert_eq!(bisect_int(0, u64::MAX, tracer.eval_fn(|x| x >= 5)), Some(5));
+        let trace = tracer.take();
+        assert!(trace.evaluations().len() <= 64);
+        assert_eq!(trace.probes(), []);
+
+        let mut file = String::new();
+        for i in 0..1000 {
+            file.push_str(&format!("{i:04}\n"));
+        }
+        let tracer = Tracer::new();
+        let record = binary_search_file(tracer.source(Cursor::new(file)), b"0420").unwrap();
+        assert_eq!(record.unwrap().offset, 2100);
+        assert!(tracer.take().probes().len() <= 2 * 13);
+    }
+
+    #[test]
+    fn test_export() {
+        let arr = [1, 3, 5];
+        let (_, trace) = trace_search(&arr, &5, |h, n| binary_search(h, n));
+        assert_eq!(
+            trace.events(),
+            [
+                SearchEvent::Compare {
+                    a: Some(1),
+                    b: None
+                },
+                SearchEvent::Compare {
+                    a: Some(2),
+                    b: None
+                },
+            ]
+        );
+        assert_eq!(
+            trace.to_json(),
+            r#"{"len":3,"probes":2,"comparisons":2,"events":[{"op":"compare","a":1,"b":null},{"op":"compare","a":2,"b":null}]}"#
+        );
+        let svg = trace.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<circle").count(), 2);
+
+        let tracer = Tracer::new();
+        bisect_int(0, 10, tracer.eval_fn(|x| x > 3));
+        let json = tracer.take().to_json();
+        assert!(json.contains(r#"{"op":"evaluate","at":5}"#), "{json}");
+    }
+}
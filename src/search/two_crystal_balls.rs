@@ -1,5 +1,8 @@
 /// This function takes a slice &[bool] and returns the index of the first occurrence of true.
 ///
+/// The slice must be monotone: once an element is true, every element after it
+/// must be true too. It is [`crystal_balls`] with two balls.
+///
 /// # Examples
 ///
 ///```rust
@@ -8,37 +11,128 @@
 /// assert_eq!(two_crystal_balls(&arr_bool), Some(2));
 ///```
 pub fn two_crystal_balls(breaks: &[bool]) -> Option<usize> {
-    // Calculate the jump distance based on the square root of the number of elements.
-    let jmp = f32::sqrt(breaks.len() as f32) as usize;
-    // Initialize the address (index) to 0.
-    let mut addr: usize = 0;
-    // Perform the first part of the search using a while loop.
-    while addr < breaks.len() {
-        // Check if the ball breaks at the current index.
-        if breaks[addr] {
-            // If it breaks, move back by the jump distance and exit the loop.
-            addr -= jmp;
-            break;
+    crystal_balls(breaks.len(), 2, |i| breaks[i]).index
+}
+
+/// The result of a [`crystal_balls`] search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BallDrops {
+    /// The first index where the ball breaks, or `None` if it never does.
+    pub index: Option<usize>,
+    /// How many times the ball was dropped, that is, how many times the predicate
+    /// was called.
+    pub drops: usize,
+}
+
+/// This function finds the first index in `0..len` where `breaks` returns true,
+/// using at most `balls` breaks, and returns it with the number of drops it took.
+///
+/// `breaks` must be monotone: once it returns true for an index, it must return
+/// true for every index after it. Each call is a drop, and each true is a ball
+/// that breaks and can not be used again, so the last ball has to be dropped one
+/// index at a time.
+///
+/// The drops follow the optimal schedule of the egg drop problem: with `d` drops
+/// and `k` balls left we can search `f(d, k) = f(d - 1, k - 1) + f(d - 1, k) + 1`
+/// indices, so the ball is dropped `f(d - 1, k - 1)` indices ahead. If it breaks,
+/// the rest can be searched with one ball less, and if it does not, with the same
+/// balls. This never takes more than [`crystal_balls_drops`] drops, which is
+/// **O (√N)** with two balls and **O (log N)**, like a binary search, with enough balls.
+///
+/// # Panics
+///
+/// Panics if `balls` is 0 and `len` is not.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::crystal_balls;
+/// // Floors of a 100 story building, the ball breaks from floor 73 up.
+/// let result = crystal_balls(100, 2, |floor| floor >= 73);
+/// assert_eq!(result.index, Some(73));
+/// assert!(result.drops <= 14);
+/// ```
+pub fn crystal_balls<F>(len: usize, balls: usize, mut breaks: F) -> BallDrops
+where
+    F: FnMut(usize) -> bool,
+{
+    assert!(balls > 0 || len == 0, "at least one ball is needed");
+    let mut balls = balls;
+    let mut drops_left = crystal_balls_drops(len, balls);
+    // The first true is in [low, high], where high == len means none.
+    let mut low = 0;
+    let mut high = len;
+    let mut drops = 0;
+    while low < high {
+        // If the ball breaks, what is left before 'i' must be searched with one
+        // ball less, so drop it as far as that allows.
+        let ahead = max_indices(drops_left - 1, balls - 1);
+        let i = low + ahead.min(high - low - 1);
+        drops += 1;
+        drops_left -= 1;
+        if breaks(i) {
+            high = i;
+            balls -= 1;
+        } else {
+            low = i + 1;
         }
-        // If it doesn't break, move forward by the jump distance.
-        addr += jmp;
-    }
-    // Perform the second part of the search using a for loop
-    for e in breaks.iter().skip(addr) {
-        // Check if the ball breaks at the current index.
-        if *e {
-            // If it breaks, return the current index as Some.
-            return Some(addr);
+    }
+    BallDrops {
+        index: (high < len).then_some(high),
+        drops,
+    }
+}
+
+/// Returns the number of drops [`crystal_balls`] needs in the worst case to search
+/// `len` indices with `balls` balls.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::search::crystal_balls_drops;
+/// assert_eq!(crystal_balls_drops(100, 1), 100);
+/// assert_eq!(crystal_balls_drops(100, 2), 14);
+/// assert_eq!(crystal_balls_drops(100, 7), 7);
+/// ```
+pub fn crystal_balls_drops(len: usize, balls: usize) -> usize {
+    // Binary search the smallest number of drops that can search 'len' indices,
+    // 'len' drops are always enough with one ball or more.
+    let mut low = 0;
+    let mut high = len;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if max_indices(mid, balls) >= len {
+            high = mid;
+        } else {
+            low = mid + 1;
         }
-        addr += 1;
     }
-    // If no breaking point is found, return None.
-    None
+    low
+}
+
+/// Returns how many indices can be searched with `drops` drops and `balls` balls,
+/// saturating at `usize::MAX`.
+///
+/// It is the sum of the binomial coefficients C(drops, i) for i in 1..=balls,
+/// which is the closed form of f(d, k) = f(d - 1, k - 1) + f(d - 1, k) + 1.
+fn max_indices(drops: usize, balls: usize) -> usize {
+    let mut total: u128 = 0;
+    let mut binomial: u128 = 1;
+    for i in 1..=balls.min(drops) as u128 {
+        // C(d, i) = C(d, i - 1) * (d - i + 1) / i is exact, and does not overflow
+        // as C(d, i - 1) is at most usize::MAX here.
+        binomial = binomial * (drops as u128 - i + 1) / i;
+        total += binomial;
+        if total >= usize::MAX as u128 {
+            return usize::MAX;
+        }
+    }
+    total as usize
 }
 
 #[cfg(test)]
 mod tests {
-    use super::two_crystal_balls;
+    use super::{crystal_balls, crystal_balls_drops, max_indices, two_crystal_balls};
 
     #[test]
     fn test_binary_search() {
@@ -52,5 +146,56 @@ mod tests {
         ];
         let index = two_crystal_balls(&arr);
         assert_eq!(index, None);
+        // Breaks at the first index, and lengths that are not perfect squares.
+        for len in 0..50 {
+            for first in 0..=len {
+                let arr: Vec<bool> = (0..len).map(|i| i >= first).collect();
+                let expected = (first < len).then_some(first);
+                assert_eq!(two_crystal_balls(&arr), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_crystal_balls() {
+        for len in 0..80 {
+            for balls in 1..8 {
+                let worst = crystal_balls_drops(len, balls);
+                let mut worst_seen = 0;
+                for first in 0..=len {
+                    let mut broken = 0;
+                    let result = crystal_balls(len, balls, |i| {
+                        broken += (i >= first) as usize;
+                        i >= first
+                    });
+                    assert_eq!(result.index, (first < len).then_some(first));
+                    assert!(broken <= balls);
+                    assert!(result.drops <= worst);
+                    worst_seen = worst_seen.max(result.drops);
+                }
+                // The schedule is optimal, so some index needs every drop.
+                assert_eq!(worst_seen, worst, "len {len} balls {balls}");
+            }
+        }
+        let result = crystal_balls(usize::MAX, 64, |i| i >= usize::MAX / 3);
+        assert_eq!(result.index, Some(usize::MAX / 3));
+        assert!(result.drops <= 64);
+        assert_eq!(crystal_balls(0, 0, |_| true).index, None);
+    }
+
+    #[test]
+    fn test_max_indices() {
+        assert_eq!(max_indices(14, 2), 105);
+        assert_eq!(max_indices(10, 1), 10);
+        assert_eq!(max_indices(10, 20), 1023);
+        assert_eq!(max_indices(0, 3), 0);
+        assert_eq!(max_indices(usize::MAX, 2), usize::MAX);
+        assert_eq!(max_indices(200, 100), usize::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_crystal_balls_no_balls() {
+        crystal_balls(3, 0, |_| true);
     }
 }
@@ -0,0 +1,91 @@
+use std::cmp::Ordering;
+
+use super::stats::{partial_cmp, Counter, SortStats};
+
+/// This function takes a mut reference to a slice and reorder it with
+/// binary insertion sort.
+///
+/// Like insertion sort, but the position of each new element is found with a
+/// binary search over the sorted prefix, so it only needs **O (N log N)**
+/// comparisons, while it still does **O (N²)** writes to shift the elements.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::binary_insertion_sort;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// binary_insertion_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 3, 4, 5]);
+/// ```
+pub fn binary_insertion_sort<T: PartialOrd>(arr: &mut [T]) -> SortStats {
+    binary_insertion_sort_by(arr, partial_cmp)
+}
+
+/// Same as [`binary_insertion_sort`] but orders the elements with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::binary_insertion_sort_by;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// binary_insertion_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [5, 4, 3, 2, 1]);
+/// ```
+pub fn binary_insertion_sort_by<T, F>(arr: &mut [T], compare: F) -> SortStats
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut arr = Counter::new(arr, compare);
+    run(&mut arr);
+    arr.stats()
+}
+
+/// Runs binary insertion sort over a [`Counter`], shared by [`binary_insertion_sort_by`] and the recorder.
+pub(super) fn run<T, F>(arr: &mut Counter<T, F>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for i in 1..arr.len() {
+        // Binary search the sorted prefix [0, i) for the first element bigger than
+        // arr[i]. Going past equal elements keeps the sort stable.
+        let mut low = 0;
+        let mut high = i;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if arr.less(i, mid) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        // Shift [low, i) one position to the right and write the element at low.
+        if low < i {
+            arr.rotate_right(low, i);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{binary_insertion_sort, binary_insertion_sort_by};
+
+    #[test]
+    fn test_binary_insertion_sort() {
+        let mut arr = [5, 1, 4, 2, 8, 0, 2];
+        binary_insertion_sort(&mut arr);
+        assert_eq!(arr, [0, 1, 2, 2, 4, 5, 8]);
+
+        // Stable: pairs with the same key keep their original order.
+        let mut pairs = [(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+        binary_insertion_sort_by(&mut pairs, |a, b| a.0.cmp(&b.0));
+        assert_eq!(pairs, [(0, 'b'), (0, 'd'), (1, 'a'), (1, 'c')]);
+
+        let mut reversed: Vec<i32> = (0..256).rev().collect();
+        let stats = binary_insertion_sort(&mut reversed);
+        assert_eq!(reversed, (0..256).collect::<Vec<_>>());
+        assert!(stats.comparisons <= 256 * 8);
+        assert_eq!(stats.swaps, 0);
+        // Each element but the first is moved to the front, shifting the ones before it.
+        assert_eq!(stats.writes, (2..=256).sum::<usize>());
+    }
+}
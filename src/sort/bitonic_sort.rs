@@ -0,0 +1,112 @@
+use std::cmp::Ordering;
+
+use super::stats::{partial_cmp, Counter, SortStats};
+
+/// This function takes a mut reference to a slice whose length is a power of
+/// two and reorder it with bitonic sort.
+///
+/// Bitonic sort is a sorting network that works for any power of two length.
+/// It sorts the two halves in opposite directions, making a bitonic sequence
+/// (one that goes up and then down), and then merges it by comparing elements
+/// half the distance apart. It always does **O (N log² N)** comparisons, and
+/// the comparisons of each step are independent, which makes it easy to run
+/// in parallel.
+///
+/// # Panics
+///
+/// Panics if the length of the slice is not a power of two. Empty slices are
+/// accepted.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::bitonic_sort;
+/// let mut arr: [i32; 8] = [2, 3, 5, 4, 1, 8, 7, 6];
+/// bitonic_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 3, 4, 5, 6, 7, 8]);
+/// ```
+pub fn bitonic_sort<T: PartialOrd>(arr: &mut [T]) -> SortStats {
+    bitonic_sort_by(arr, partial_cmp)
+}
+
+/// Same as [`bitonic_sort`] but orders the elements with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::bitonic_sort_by;
+/// let mut arr: [i32; 4] = [2, 3, 4, 1];
+/// bitonic_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [4, 3, 2, 1]);
+/// ```
+pub fn bitonic_sort_by<T, F>(arr: &mut [T], compare: F) -> SortStats
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut arr = Counter::new(arr, compare);
+    run(&mut arr);
+    arr.stats()
+}
+
+/// Runs bitonic sort over a [`Counter`], shared by [`bitonic_sort_by`] and the recorder.
+pub(super) fn run<T, F>(arr: &mut Counter<T, F>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = arr.len();
+    assert!(
+        len == 0 || len.is_power_of_two(),
+        "length must be a power of two"
+    );
+    // 'block' is the size of the bitonic sequences being merged.
+    let mut block = 2;
+    while block <= len {
+        // 'dist' is the distance between the compared elements.
+        let mut dist = block / 2;
+        while dist > 0 {
+            for i in 0..len {
+                let j = i ^ dist;
+                if j > i {
+                    // Blocks alternate between ascending and descending order.
+                    let ascending = i & block == 0;
+                    let out_of_order = if ascending {
+                        arr.less(j, i)
+                    } else {
+                        arr.less(i, j)
+                    };
+                    if out_of_order {
+                        arr.swap(i, j);
+                    }
+                }
+            }
+            dist /= 2;
+        }
+        block *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bitonic_sort;
+
+    #[test]
+    fn test_bitonic_sort() {
+        for pow in 0..11 {
+            let len = 1 << pow;
+            let mut arr: Vec<u32> = (0..len).map(|i| (i * 7919) % 1031).collect();
+            let mut expected = arr.clone();
+            expected.sort();
+            let stats = bitonic_sort(&mut arr);
+            assert_eq!(arr, expected);
+            // N/2 * log N * (log N + 1) / 2 comparisons, whatever the input.
+            assert_eq!(stats.comparisons, (len as usize / 2) * pow * (pow + 1) / 2);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bitonic_sort_not_power_of_two() {
+        let mut arr = [3, 2, 1];
+        bitonic_sort(&mut arr);
+    }
+}
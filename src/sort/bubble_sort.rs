@@ -1,3 +1,7 @@
+use std::cmp::Ordering;
+
+use super::stats::{partial_cmp, Counter, SortStats};
+
 /// This function takes a mut reference to a slice and reorder it with
 /// bubble sort.
 ///
@@ -9,33 +13,80 @@
 /// bubble_sort(&mut arr);
 /// assert_eq!(arr, [1, 2, 3, 4, 5]);
 /// ```
-pub fn bubble_sort<T: PartialOrd>(arr: &mut [T]) {
+pub fn bubble_sort<T: PartialOrd>(arr: &mut [T]) -> SortStats {
+    bubble_sort_by(arr, partial_cmp)
+}
+
+/// Same as [`bubble_sort`] but orders the elements with a comparator function.
+///
+/// If a pass over the slice does not swap anything the slice is already sorted
+/// and the function returns early, so the best case is **O (N)**.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::bubble_sort_by;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// bubble_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [5, 4, 3, 2, 1]);
+/// ```
+pub fn bubble_sort_by<T, F>(arr: &mut [T], compare: F) -> SortStats
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut arr = Counter::new(arr, compare);
+    run(&mut arr);
+    arr.stats()
+}
+
+/// Runs bubble sort over a [`Counter`], shared by [`bubble_sort_by`] and the recorder.
+pub(super) fn run<T, F>(arr: &mut Counter<T, F>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
     let len = arr.len(); // Get the length of the input array 'arr'.
                          // The outer loop iterates from 0 to 'len - 1'.
     for i in 0..len {
+        // Keep track if this pass moved anything.
+        let mut swapped = false;
         // 'i' represents the number of elements that are already in their final sorted position.
         // The inner loop iterates from 0 to '(len - 1 - i)'.
         // It compares adjacent elements and swaps them if they are out of order.
         for j in 0..(len - 1 - i) {
-            // Compare the adjacent elements using 'partial_cmp' and handle the result.
-            match arr[j].partial_cmp(&arr[j + 1]).unwrap() {
-                // If the current element is greater than the next element, swap them.
-                std::cmp::Ordering::Greater => arr.swap(j, j + 1),
-                // If the current element is equal to or less than the next element, continue to the next pair.
-                std::cmp::Ordering::Equal | std::cmp::Ordering::Less => continue,
+            // If the next element is smaller than the current element, swap them.
+            if arr.less(j + 1, j) {
+                arr.swap(j, j + 1);
+                swapped = true;
             }
         }
+        // No swaps means every pair is in order, so the slice is sorted.
+        if !swapped {
+            break;
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::bubble_sort;
+    use super::{bubble_sort, bubble_sort_by};
 
     #[test]
     fn test_bubble_sort() {
         let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
         bubble_sort(&mut arr);
         assert_eq!(arr, [1, 2, 3, 4, 5]);
+
+        let mut sorted: Vec<i32> = (0..100).collect();
+        let stats = bubble_sort(&mut sorted);
+        assert_eq!(stats.comparisons, 99);
+        assert_eq!(stats.swaps, 0);
+
+        let mut reversed: Vec<i32> = (0..10).rev().collect();
+        let stats = bubble_sort_by(&mut reversed, |a, b| a.cmp(b));
+        assert_eq!(reversed, (0..10).collect::<Vec<_>>());
+        assert_eq!(stats.swaps, 45);
+
+        let mut empty: [i32; 0] = [];
+        bubble_sort(&mut empty);
     }
 }
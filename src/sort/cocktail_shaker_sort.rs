@@ -0,0 +1,101 @@
+use std::cmp::Ordering;
+
+use super::stats::{partial_cmp, Counter, SortStats};
+
+/// This function takes a mut reference to a slice and reorder it with
+/// cocktail shaker sort.
+///
+/// Cocktail shaker sort is a bubble sort that alternates the direction of
+/// each pass, so small elements at the end of the slice ("turtles") move to
+/// the front as fast as big elements move to the back.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::cocktail_shaker_sort;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// cocktail_shaker_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 3, 4, 5]);
+/// ```
+pub fn cocktail_shaker_sort<T: PartialOrd>(arr: &mut [T]) -> SortStats {
+    cocktail_shaker_sort_by(arr, partial_cmp)
+}
+
+/// Same as [`cocktail_shaker_sort`] but orders the elements with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::cocktail_shaker_sort_by;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// cocktail_shaker_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [5, 4, 3, 2, 1]);
+/// ```
+pub fn cocktail_shaker_sort_by<T, F>(arr: &mut [T], compare: F) -> SortStats
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut arr = Counter::new(arr, compare);
+    run(&mut arr);
+    arr.stats()
+}
+
+/// Runs cocktail shaker sort over a [`Counter`], shared by [`cocktail_shaker_sort_by`] and the recorder.
+pub(super) fn run<T, F>(arr: &mut Counter<T, F>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if arr.len() < 2 {
+        return;
+    }
+    // Everything outside [start, end] is already in its final position.
+    let mut start = 0;
+    let mut end = arr.len() - 1;
+    while start < end {
+        let mut swapped = false;
+        // Forward pass, bubbles the biggest element to 'end'.
+        for j in start..end {
+            if arr.less(j + 1, j) {
+                arr.swap(j, j + 1);
+                swapped = true;
+            }
+        }
+        if !swapped {
+            break;
+        }
+        end -= 1;
+        swapped = false;
+        // Backward pass, bubbles the smallest element to 'start'.
+        for j in (start..end).rev() {
+            if arr.less(j + 1, j) {
+                arr.swap(j, j + 1);
+                swapped = true;
+            }
+        }
+        if !swapped {
+            break;
+        }
+        start += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cocktail_shaker_sort;
+
+    #[test]
+    fn test_cocktail_shaker_sort() {
+        let mut arr = [5, 1, 4, 2, 8, 0, 2];
+        cocktail_shaker_sort(&mut arr);
+        assert_eq!(arr, [0, 1, 2, 2, 4, 5, 8]);
+
+        // A single turtle is fixed in one round trip.
+        let mut turtle = [2, 3, 4, 5, 6, 7, 1];
+        let stats = cocktail_shaker_sort(&mut turtle);
+        assert_eq!(turtle, [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(stats.swaps, 6);
+
+        let mut single = [1];
+        assert_eq!(cocktail_shaker_sort(&mut single).comparisons, 0);
+    }
+}
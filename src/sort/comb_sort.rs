@@ -0,0 +1,82 @@
+use std::cmp::Ordering;
+
+use super::stats::{partial_cmp, Counter, SortStats};
+
+/// This function takes a mut reference to a slice and reorder it with
+/// comb sort.
+///
+/// Comb sort is a bubble sort that compares elements that are `gap` positions
+/// apart. The gap starts as the length of the slice and shrinks by a factor of
+/// 1.3 each pass, until it reaches 1 and the sort becomes a plain bubble sort.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::comb_sort;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// comb_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 3, 4, 5]);
+/// ```
+pub fn comb_sort<T: PartialOrd>(arr: &mut [T]) -> SortStats {
+    comb_sort_by(arr, partial_cmp)
+}
+
+/// Same as [`comb_sort`] but orders the elements with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::comb_sort_by;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// comb_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [5, 4, 3, 2, 1]);
+/// ```
+pub fn comb_sort_by<T, F>(arr: &mut [T], compare: F) -> SortStats
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut arr = Counter::new(arr, compare);
+    run(&mut arr);
+    arr.stats()
+}
+
+/// Runs comb sort over a [`Counter`], shared by [`comb_sort_by`] and the recorder.
+pub(super) fn run<T, F>(arr: &mut Counter<T, F>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = arr.len();
+    let mut gap = len;
+    let mut swapped = true;
+    // Keep going until a pass with gap 1 does not swap anything.
+    while gap > 1 || swapped {
+        // Shrink the gap by 1.3, using integers only.
+        gap = (gap * 10 / 13).max(1);
+        swapped = false;
+        for i in 0..len.saturating_sub(gap) {
+            if arr.less(i + gap, i) {
+                arr.swap(i, i + gap);
+                swapped = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::comb_sort;
+
+    #[test]
+    fn test_comb_sort() {
+        let mut arr = [5, 1, 4, 2, 8, 0, 2];
+        comb_sort(&mut arr);
+        assert_eq!(arr, [0, 1, 2, 2, 4, 5, 8]);
+
+        let mut reversed: Vec<i32> = (0..1000).rev().collect();
+        comb_sort(&mut reversed);
+        assert_eq!(reversed, (0..1000).collect::<Vec<_>>());
+
+        let mut empty: [i32; 0] = [];
+        assert_eq!(comb_sort(&mut empty).comparisons, 0);
+    }
+}
@@ -0,0 +1,76 @@
+use std::cmp::Ordering;
+
+use super::stats::{partial_cmp, Counter, SortStats};
+
+/// This function takes a mut reference to a slice and reorder it with
+/// gnome sort.
+///
+/// Gnome sort walks the slice with a single index, moving forward while
+/// the pair behind it is in order, and swapping and stepping back when it
+/// is not.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::gnome_sort;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// gnome_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 3, 4, 5]);
+/// ```
+pub fn gnome_sort<T: PartialOrd>(arr: &mut [T]) -> SortStats {
+    gnome_sort_by(arr, partial_cmp)
+}
+
+/// Same as [`gnome_sort`] but orders the elements with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::gnome_sort_by;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// gnome_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [5, 4, 3, 2, 1]);
+/// ```
+pub fn gnome_sort_by<T, F>(arr: &mut [T], compare: F) -> SortStats
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut arr = Counter::new(arr, compare);
+    run(&mut arr);
+    arr.stats()
+}
+
+/// Runs gnome sort over a [`Counter`], shared by [`gnome_sort_by`] and the recorder.
+pub(super) fn run<T, F>(arr: &mut Counter<T, F>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut pos = 0;
+    while pos < arr.len() {
+        if pos == 0 || !arr.less(pos, pos - 1) {
+            // The pair is in order, step forward.
+            pos += 1;
+        } else {
+            // Out of order, swap and step back to check the previous pair.
+            arr.swap(pos, pos - 1);
+            pos -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::gnome_sort;
+
+    #[test]
+    fn test_gnome_sort() {
+        let mut arr = [5, 1, 4, 2, 8, 0, 2];
+        gnome_sort(&mut arr);
+        assert_eq!(arr, [0, 1, 2, 2, 4, 5, 8]);
+
+        let mut sorted: Vec<i32> = (0..20).collect();
+        let stats = gnome_sort(&mut sorted);
+        assert_eq!(stats.comparisons, 19);
+        assert_eq!(stats.swaps, 0);
+    }
+}
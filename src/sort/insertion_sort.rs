@@ -0,0 +1,70 @@
+use std::cmp::Ordering;
+
+use super::stats::{partial_cmp, Counter, SortStats};
+
+/// This function takes a mut reference to a slice and reorder it with
+/// insertion sort.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::insertion_sort;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// insertion_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 3, 4, 5]);
+/// ```
+pub fn insertion_sort<T: PartialOrd>(arr: &mut [T]) -> SortStats {
+    insertion_sort_by(arr, partial_cmp)
+}
+
+/// Same as [`insertion_sort`] but orders the elements with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::insertion_sort_by;
+/// let mut arr = ["ccc", "a", "bb"];
+/// insertion_sort_by(&mut arr, |a, b| a.len().cmp(&b.len()));
+/// assert_eq!(arr, ["a", "bb", "ccc"]);
+/// ```
+pub fn insertion_sort_by<T, F>(arr: &mut [T], compare: F) -> SortStats
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut arr = Counter::new(arr, compare);
+    run(&mut arr);
+    arr.stats()
+}
+
+/// Runs insertion sort over a [`Counter`], shared by [`insertion_sort_by`] and the recorder.
+pub(super) fn run<T, F>(arr: &mut Counter<T, F>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    // Everything to the left of 'i' is already sorted.
+    for i in 1..arr.len() {
+        let mut j = i;
+        // Walk the new element to the left until the one before it is not bigger.
+        while j > 0 && arr.less(j, j - 1) {
+            arr.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::insertion_sort;
+
+    #[test]
+    fn test_insertion_sort() {
+        let mut arr = [5, 1, 4, 2, 8, 0, 2];
+        insertion_sort(&mut arr);
+        assert_eq!(arr, [0, 1, 2, 2, 4, 5, 8]);
+
+        let mut sorted: Vec<i32> = (0..50).collect();
+        let stats = insertion_sort(&mut sorted);
+        assert_eq!(stats.comparisons, 49);
+        assert_eq!(stats.swaps, 0);
+    }
+}
@@ -1,3 +1,33 @@
+mod binary_insertion_sort;
+mod bitonic_sort;
 mod bubble_sort;
+mod cocktail_shaker_sort;
+mod comb_sort;
+mod gnome_sort;
+mod insertion_sort;
+mod recorder;
+mod select;
+mod selection_sort;
+mod shell_sort;
+mod sorting_network;
+mod stats;
+mod string_order;
+mod top_k;
 
-pub use bubble_sort::bubble_sort;
+pub use binary_insertion_sort::{binary_insertion_sort, binary_insertion_sort_by};
+pub use bitonic_sort::{bitonic_sort, bitonic_sort_by};
+pub use bubble_sort::{bubble_sort, bubble_sort_by};
+pub use cocktail_shaker_sort::{cocktail_shaker_sort, cocktail_shaker_sort_by};
+pub use comb_sort::{comb_sort, comb_sort_by};
+pub use gnome_sort::{gnome_sort, gnome_sort_by};
+pub use insertion_sort::{insertion_sort, insertion_sort_by};
+pub use recorder::{record, record_by, Algorithm, Recording, States};
+pub use select::{partial_sort, partial_sort_by, select_nth, select_nth_by};
+pub use selection_sort::{selection_sort, selection_sort_by};
+pub use shell_sort::{shell_sort, shell_sort_by, GapSequence};
+pub use sorting_network::{
+    network, network_sort, network_sort_by, verify_network, MAX_NETWORK_LEN,
+};
+pub use stats::{SortEvent, SortStats};
+pub use string_order::{case_insensitive_cmp, natural_cmp, natural_cmp_ignore_case, version_cmp};
+pub use top_k::{top_k, top_k_by};
@@ -0,0 +1,433 @@
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+use super::stats::{partial_cmp, Counter, SortEvent, SortStats};
+use super::{
+    binary_insertion_sort, bitonic_sort, bubble_sort, cocktail_shaker_sort, comb_sort, gnome_sort,
+    insertion_sort, select, selection_sort, shell_sort, sorting_network, GapSequence,
+};
+
+/// The in place algorithms of this module that can be recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Bubble,
+    Insertion,
+    BinaryInsertion,
+    Selection,
+    CocktailShaker,
+    Gnome,
+    Comb,
+    Shell(GapSequence),
+    Bitonic,
+    /// The built in sorting network for the length of the slice.
+    Network,
+    /// [`select_nth`](super::select_nth) with the given index.
+    SelectNth(usize),
+    /// [`partial_sort`](super::partial_sort) of the given number of positions.
+    PartialSort(usize),
+}
+
+impl Algorithm {
+    /// The name of the function that implements the algorithm.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Bubble => "bubble_sort",
+            Algorithm::Insertion => "insertion_sort",
+            Algorithm::BinaryInsertion => "binary_insertion_sort",
+            Algorithm::Selection => "selection_sort",
+            Algorithm::CocktailShaker => "cocktail_shaker_sort",
+            Algorithm::Gnome => "gnome_sort",
+            Algorithm::Comb => "comb_sort",
+            Algorithm::Shell(_) => "shell_sort",
+            Algorithm::Bitonic => "bitonic_sort",
+            Algorithm::Network => "network_sort",
+            Algorithm::SelectNth(_) => "select_nth",
+            Algorithm::PartialSort(_) => "partial_sort",
+        }
+    }
+}
+
+/// Every operation an algorithm did over a slice, in order.
+///
+/// Elements are identified by their position in the input, so a recording does
+/// not hold any `T` and can be replayed over a copy of the input to rebuild each
+/// intermediate state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recording {
+    algorithm: Algorithm,
+    stats: SortStats,
+    events: Vec<SortEvent>,
+    ranks: Vec<usize>,
+}
+
+/// Sorts the slice with the given algorithm while recording every compare, swap
+/// and write it does.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::{record, Algorithm, SortEvent};
+/// let input = [2, 1];
+/// let mut arr = input;
+/// let recording = record(&mut arr, Algorithm::Bubble);
+/// assert_eq!(arr, [1, 2]);
+/// assert_eq!(
+///     recording.events(),
+///     [SortEvent::Compare { a: 1, b: 0 }, SortEvent::Swap { a: 0, b: 1 }]
+/// );
+/// let states: Vec<Vec<i32>> = recording.replay(&input).collect();
+/// assert_eq!(states, [vec![2, 1], vec![2, 1], vec![1, 2]]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if two elements can not be compared, like a NaN, or if the algorithm
+/// does not accept the slice:
+/// - [`Algorithm::Network`] if the slice is longer than 16.
+/// - [`Algorithm::Bitonic`] if the length of the slice is not a power of two.
+/// - [`Algorithm::SelectNth`] if the index is not smaller than the length.
+pub fn record<T: PartialOrd>(arr: &mut [T], algorithm: Algorithm) -> Recording {
+    record_by(arr, algorithm, partial_cmp)
+}
+
+/// Same as [`record`] but orders the elements with a comparator function.
+///
+/// # Panics
+///
+/// Panics if the algorithm does not accept the slice, see [`record`].
+pub fn record_by<T, F>(arr: &mut [T], algorithm: Algorithm, compare: F) -> Recording
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut counter = Counter::recording(arr, compare);
+    match algorithm {
+        Algorithm::Bubble => bubble_sort::run(&mut counter),
+        Algorithm::Insertion => insertion_sort::run(&mut counter),
+        Algorithm::BinaryInsertion => binary_insertion_sort::run(&mut counter),
+        Algorithm::Selection => selection_sort::run(&mut counter),
+        Algorithm::CocktailShaker => cocktail_shaker_sort::run(&mut counter),
+        Algorithm::Gnome => gnome_sort::run(&mut counter),
+        Algorithm::Comb => comb_sort::run(&mut counter),
+        Algorithm::Shell(gaps) => shell_sort::run(&mut counter, gaps),
+        Algorithm::Bitonic => bitonic_sort::run(&mut counter),
+        Algorithm::Network => sorting_network::run(&mut counter),
+        Algorithm::SelectNth(n) => select::run_select_nth(&mut counter, n),
+        Algorithm::PartialSort(k) => select::run_partial_sort(&mut counter, k),
+    }
+    let stats = counter.stats();
+    let (events, ids, mut compare) = counter.into_recording();
+    // The rank of each element is its position in the sorted input. It is used
+    // for the bar heights of the SVG, so it is not recorded as comparisons.
+    let mut order: Vec<usize> = (0..arr.len()).collect();
+    order.sort_by(|&a, &b| compare(&arr[a], &arr[b]));
+    let mut ranks = vec![0; arr.len()];
+    for (rank, position) in order.into_iter().enumerate() {
+        ranks[ids[position]] = rank;
+    }
+    Recording {
+        algorithm,
+        stats,
+        events,
+        ranks,
+    }
+}
+
+impl Recording {
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    pub fn stats(&self) -> SortStats {
+        self.stats
+    }
+
+    pub fn events(&self) -> &[SortEvent] {
+        &self.events
+    }
+
+    /// The rank of each input element, that is its position in the sorted input.
+    pub fn ranks(&self) -> &[usize] {
+        &self.ranks
+    }
+
+    /// Returns an iterator over the intermediate states of the slice. Each state
+    /// holds, for every position, the input position of the element in it.
+    ///
+    /// The first state is the input and there is one more state after each event.
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    /// # use algo_front_end_masters::sort::{record, Algorithm};
+    /// let recording = record(&mut [3, 1, 2], Algorithm::Selection);
+    /// let last = recording.states().last();
+    /// assert_eq!(last, Some(vec![1, 2, 0]));
+    /// ```
+    pub fn states(&self) -> States<'_> {
+        States {
+            state: Some((0..self.ranks.len()).collect()),
+            events: self.events.iter(),
+        }
+    }
+
+    /// Same as [`Recording::states`] but with the values of the input instead of
+    /// their positions. `input` must be a copy of the slice before it was sorted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` does not have the length of the recorded slice.
+    pub fn replay<'a, T: Clone>(&'a self, input: &'a [T]) -> impl Iterator<Item = Vec<T>> + 'a {
+        assert_eq!(input.len(), self.ranks.len(), "input length mismatch");
+        self.states()
+            .map(move |state| state.into_iter().map(|id| input[id].clone()).collect())
+    }
+
+    /// Exports the recording as JSON.
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    /// # use algo_front_end_masters::sort::{record, Algorithm};
+    /// let recording = record(&mut [2, 1], Algorithm::Insertion);
+    /// assert_eq!(
+    ///     recording.to_json(),
+    ///     r#"{"algorithm":"insertion_sort","len":2,"stats":{"comparisons":1,"swaps":1,"writes":0},"ranks":[1,0],"events":[{"op":"compare","a":1,"b":0},{"op":"swap","a":1,"b":0}]}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        let SortStats {
+            comparisons,
+            swaps,
+            writes,
+        } = self.stats;
+        // Writing to a String never fails.
+        let _ = write!(
+            json,
+            r#"{{"algorithm":"{}","len":{},"stats":{{"comparisons":{},"swaps":{},"writes":{}}},"ranks":["#,
+            self.algorithm.name(),
+            self.ranks.len(),
+            comparisons,
+            swaps,
+            writes
+        );
+        for (i, rank) in self.ranks.iter().enumerate() {
+            let _ = write!(json, "{}{}", if i == 0 { "" } else { "," }, rank);
+        }
+        json.push_str(r#"],"events":["#);
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            let _ = match *event {
+                SortEvent::Compare { a, b } => {
+                    write!(json, r#"{{"op":"compare","a":{a},"b":{b}}}"#)
+                }
+                SortEvent::Swap { a, b } => write!(json, r#"{{"op":"swap","a":{a},"b":{b}}}"#),
+                SortEvent::Write { index, element } => write!(
+                    json,
+                    r#"{{"op":"write","index":{index},"element":{element}}}"#
+                ),
+            };
+        }
+        json.push_str("]}");
+        json
+    }
+
+    /// Exports the recording as an animated SVG, with one bar per position whose
+    /// height is the rank of the element in it. Each frame lasts 100ms and the
+    /// positions touched by the event of the frame are highlighted: compares in
+    /// gold, swaps in crimson and writes in seagreen.
+    ///
+    /// The states are replayed once, adding each frame to the values of the
+    /// animations as it goes, so only one state is kept in memory.
+    pub fn to_svg(&self) -> String {
+        const BAR_WIDTH: usize = 10;
+        const HEIGHT: usize = 200;
+        const FRAME_MS: usize = 100;
+        let len = self.ranks.len();
+        let dur = (self.events.len() + 1) * FRAME_MS;
+        let bar_height = |id: usize| (self.ranks[id] + 1) * HEIGHT / len.max(1);
+
+        // The values of the 'y', 'height' and 'fill' animations of each bar.
+        let mut values = vec![[String::new(), String::new(), String::new()]; len];
+        let mut state: Vec<usize> = (0..len).collect();
+        // The first frame is the input, frame i + 1 shows event i.
+        let frames = std::iter::once(None).chain(self.events.iter().map(Some));
+        for (frame, event) in frames.enumerate() {
+            if let Some(event) = event {
+                apply(&mut state, event);
+            }
+            let separator = if frame == 0 { "" } else { ";" };
+            for (position, [ys, heights, fills]) in values.iter_mut().enumerate() {
+                let height = bar_height(state[position]);
+                let fill = match event {
+                    Some(&SortEvent::Compare { a, b }) if a == position || b == position => "gold",
+                    Some(&SortEvent::Swap { a, b }) if a == position || b == position => "crimson",
+                    Some(&SortEvent::Write { index, .. }) if index == position => "seagreen",
+                    _ => "steelblue",
+                };
+                // Writing to a String never fails.
+                let _ = write!(ys, "{separator}{}", HEIGHT - height);
+                let _ = write!(heights, "{separator}{height}");
+                let _ = write!(fills, "{separator}{fill}");
+            }
+        }
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            len * BAR_WIDTH,
+            HEIGHT,
+            len * BAR_WIDTH,
+            HEIGHT
+        );
+        let _ = writeln!(svg, "<title>{}</title>", self.algorithm.name());
+        for (position, [ys, heights, fills]) in values.iter().enumerate() {
+            // The bar starts with the element that was in its position in the input.
+            let height = bar_height(position);
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="steelblue">"#,
+                position * BAR_WIDTH,
+                HEIGHT - height,
+                BAR_WIDTH - 1,
+                height
+            );
+            for (attribute, values) in [("y", ys), ("height", heights), ("fill", fills)] {
+                let _ = writeln!(
+                    svg,
+                    r#"<animate attributeName="{attribute}" values="{values}" dur="{dur}ms" calcMode="discrete" repeatCount="indefinite"/>"#
+                );
+            }
+            svg.push_str("</rect>\n");
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Applies an event to a state, that holds the input position of the element in
+/// each position.
+fn apply(state: &mut [usize], event: &SortEvent) {
+    match *event {
+        SortEvent::Compare { .. } => {}
+        SortEvent::Swap { a, b } => state.swap(a, b),
+        SortEvent::Write { index, element } => state[index] = element,
+    }
+}
+
+/// Iterator over the intermediate states of a [`Recording`].
+pub struct States<'a> {
+    state: Option<Vec<usize>>,
+    events: std::slice::Iter<'a, SortEvent>,
+}
+
+impl Iterator for States<'_> {
+    type Item = Vec<usize>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.state.take()?;
+        if let Some(event) = self.events.next() {
+            let mut next = current.clone();
+            apply(&mut next, event);
+            self.state = Some(next);
+        }
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record, record_by, Algorithm};
+    use crate::sort::{GapSequence, SortEvent};
+
+    const ALGORITHMS: [Algorithm; 13] = [
+        Algorithm::Bubble,
+        Algorithm::Insertion,
+        Algorithm::BinaryInsertion,
+        Algorithm::Selection,
+        Algorithm::CocktailShaker,
+        Algorithm::Gnome,
+        Algorithm::Comb,
+        Algorithm::Shell(GapSequence::Shell),
+        Algorithm::Shell(GapSequence::Ciura),
+        Algorithm::Bitonic,
+        Algorithm::Network,
+        Algorithm::SelectNth(5),
+        Algorithm::PartialSort(16),
+    ];
+
+    #[test]
+    fn test_record() {
+        let input = [9, 3, 14, 1, 7, 7, 0, 12, 5, 11, 2, 15, 8, 4, 13, 6];
+        let mut sorted = input;
+        sorted.sort();
+        for algorithm in ALGORITHMS {
+            let mut arr = input;
+            let recording = record(&mut arr, algorithm);
+            if algorithm != Algorithm::SelectNth(5) {
+                assert_eq!(arr, sorted, "{}", algorithm.name());
+            }
+            // Replaying the events ends with the output of the sort.
+            let last = recording.replay(&input).last().unwrap();
+            assert_eq!(last, arr, "{}", algorithm.name());
+            assert_eq!(recording.states().count(), recording.events().len() + 1);
+
+            let stats = recording.stats();
+            let count =
+                |f: fn(&SortEvent) -> bool| recording.events().iter().filter(|e| f(e)).count();
+            assert_eq!(
+                count(|e| matches!(e, SortEvent::Compare { .. })),
+                stats.comparisons
+            );
+            assert_eq!(count(|e| matches!(e, SortEvent::Swap { .. })), stats.swaps);
+            assert_eq!(
+                count(|e| matches!(e, SortEvent::Write { .. })),
+                stats.writes
+            );
+        }
+    }
+
+    #[test]
+    fn test_record_writes() {
+        let input = ["c", "a", "b"];
+        let mut arr = input;
+        let recording = record_by(&mut arr, Algorithm::BinaryInsertion, |a, b| a.cmp(b));
+        assert_eq!(arr, ["a", "b", "c"]);
+        assert_eq!(recording.ranks(), [2, 0, 1]);
+        let states: Vec<Vec<&str>> = recording.replay(&input).collect();
+        // While shifting, the moved element shows up twice.
+        assert!(states.contains(&vec!["c", "c", "b"]));
+        assert_eq!(states.last().unwrap(), &["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_export() {
+        let recording = record(&mut [3, 1, 2], Algorithm::Gnome);
+        let json = recording.to_json();
+        assert!(json.starts_with(r#"{"algorithm":"gnome_sort","len":3,"#));
+        assert!(json.contains(r#"{"op":"swap","a":1,"b":0}"#));
+
+        let svg = recording.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect").count(), 3);
+        let frames = recording.events().len() + 1;
+        let fill = svg
+            .lines()
+            .find(|l| l.contains(r#"attributeName="fill""#))
+            .unwrap();
+        assert_eq!(fill.matches(';').count(), frames - 1);
+
+        let empty = record(&mut [0u8; 0], Algorithm::Comb);
+        assert_eq!(empty.states().count(), 1);
+        assert!(empty.to_svg().contains("<svg"));
+    }
+
+    #[test]
+    #[should_panic(expected = "there is no sorting network for this length")]
+    fn test_record_network_too_long() {
+        record(&mut [0; 17], Algorithm::Network);
+    }
+}
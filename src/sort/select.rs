@@ -0,0 +1,342 @@
+use std::cmp::Ordering;
+
+use super::stats::{partial_cmp, Counter, SortStats};
+
+/// Reorders the slice so that the element at index `n` is the one that would
+/// be there if the slice was sorted. Everything before `n` is smaller or equal
+/// to it and everything after `n` is bigger or equal to it.
+///
+/// This is a quickselect: it partitions the slice around a pivot and keeps
+/// going only on the side that contains `n`, so it runs in **O (N)** on average.
+/// If the partitions keep coming out unbalanced it falls back to the median of
+/// medians pivot, which makes the worst case **O (N)** too.
+///
+/// # Panics
+///
+/// Panics if `n >= arr.len()`.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::select_nth;
+/// let mut arr = [9, 1, 8, 2, 7, 3, 6, 4, 5];
+/// select_nth(&mut arr, 4);
+/// assert_eq!(arr[4], 5);
+/// assert!(arr[..4].iter().all(|e| *e < 5));
+/// assert!(arr[5..].iter().all(|e| *e > 5));
+/// ```
+pub fn select_nth<T: PartialOrd>(arr: &mut [T], n: usize) -> SortStats {
+    select_nth_by(arr, n, partial_cmp)
+}
+
+/// Same as [`select_nth`] but orders the elements with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::select_nth_by;
+/// let mut arr = [9, 1, 8, 2, 7, 3, 6, 4, 5];
+/// // The biggest element.
+/// select_nth_by(&mut arr, 0, |a, b| b.cmp(a));
+/// assert_eq!(arr[0], 9);
+/// ```
+pub fn select_nth_by<T, F>(arr: &mut [T], n: usize, compare: F) -> SortStats
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut arr = Counter::new(arr, compare);
+    run_select_nth(&mut arr, n);
+    arr.stats()
+}
+
+/// Runs [`select_nth_by`] over a [`Counter`], shared with the recorder.
+pub(super) fn run_select_nth<T, F>(arr: &mut Counter<T, F>, n: usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    assert!(n < arr.len(), "index out of bounds");
+    let len = arr.len();
+    select(arr, 0, len, n, false);
+}
+
+/// Sorts only the first `k` positions of the slice, they will hold the `k`
+/// smallest elements in order. The order of the rest of the slice is unspecified.
+/// If `k` is bigger than the slice the whole slice is sorted.
+///
+/// It selects the `k` smallest elements with [`select_nth`] and heap sorts them,
+/// for a **O (N + K log K)** running time.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::partial_sort;
+/// let mut arr = [9, 1, 8, 2, 7, 3, 6, 4, 5];
+/// partial_sort(&mut arr, 3);
+/// assert_eq!(arr[..3], [1, 2, 3]);
+/// ```
+pub fn partial_sort<T: PartialOrd>(arr: &mut [T], k: usize) -> SortStats {
+    partial_sort_by(arr, k, partial_cmp)
+}
+
+/// Same as [`partial_sort`] but orders the elements with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::partial_sort_by;
+/// let mut arr = [9, 1, 8, 2, 7, 3, 6, 4, 5];
+/// partial_sort_by(&mut arr, 2, |a, b| b.cmp(a));
+/// assert_eq!(arr[..2], [9, 8]);
+/// ```
+pub fn partial_sort_by<T, F>(arr: &mut [T], k: usize, compare: F) -> SortStats
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut arr = Counter::new(arr, compare);
+    run_partial_sort(&mut arr, k);
+    arr.stats()
+}
+
+/// Runs [`partial_sort_by`] over a [`Counter`], shared with the recorder.
+pub(super) fn run_partial_sort<T, F>(arr: &mut Counter<T, F>, k: usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = arr.len();
+    let k = k.min(len);
+    if k == 0 {
+        return;
+    }
+    // Move the k smallest elements to [0, k).
+    if k < len {
+        select(arr, 0, len, k - 1, false);
+    }
+    heap_sort(arr, k);
+}
+
+/// Quickselect over [low, high) for the index `n`. When `median_of_medians` is
+/// set every pivot is chosen with the median of medians.
+fn select<T, F>(
+    arr: &mut Counter<T, F>,
+    mut low: usize,
+    mut high: usize,
+    n: usize,
+    mut median_of_medians: bool,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    // Number of elements we are allowed to partition before giving up on the
+    // cheap pivot. Balanced partitions add up to about 2N.
+    let mut budget = 4 * (high - low);
+    loop {
+        if high - low <= 5 {
+            insertion_sort(arr, low, high);
+            return;
+        }
+        if !median_of_medians {
+            budget = budget.saturating_sub(high - low);
+            median_of_medians = budget == 0;
+        }
+        let pivot = if median_of_medians {
+            pivot_median_of_medians(arr, low, high)
+        } else {
+            pivot_median_of_three(arr, low, high)
+        };
+        // [equal_low, equal_high) holds the elements equal to the pivot.
+        let (equal_low, equal_high) = partition(arr, low, high, pivot);
+        if n < equal_low {
+            high = equal_low;
+        } else if n >= equal_high {
+            low = equal_high;
+        } else {
+            return;
+        }
+    }
+}
+
+/// Three way partition of [low, high) around the element at `pivot`, returns the
+/// range of elements equal to the pivot.
+fn partition<T, F>(arr: &mut Counter<T, F>, low: usize, high: usize, pivot: usize) -> (usize, usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    arr.swap(low, pivot);
+    // [low, lt) is smaller than the pivot, [lt, i) is equal to it and
+    // [gt, high) is bigger. The pivot itself always sits at lt.
+    let mut lt = low;
+    let mut i = low + 1;
+    let mut gt = high;
+    while i < gt {
+        if arr.less(i, lt) {
+            arr.swap(i, lt);
+            lt += 1;
+            i += 1;
+        } else if arr.less(lt, i) {
+            gt -= 1;
+            arr.swap(i, gt);
+        } else {
+            i += 1;
+        }
+    }
+    (lt, gt)
+}
+
+fn pivot_median_of_three<T, F>(arr: &mut Counter<T, F>, low: usize, high: usize) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let (a, b, c) = (low, low + (high - low) / 2, high - 1);
+    if arr.less(a, b) {
+        if arr.less(b, c) {
+            b
+        } else if arr.less(a, c) {
+            c
+        } else {
+            a
+        }
+    } else if arr.less(a, c) {
+        a
+    } else if arr.less(b, c) {
+        c
+    } else {
+        b
+    }
+}
+
+/// Moves the median of each group of 5 to the front of [low, high) and returns
+/// the index of the median of those medians.
+fn pivot_median_of_medians<T, F>(arr: &mut Counter<T, F>, low: usize, high: usize) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut medians = 0;
+    let mut group = low;
+    while group < high {
+        let end = (group + 5).min(high);
+        insertion_sort(arr, group, end);
+        arr.swap(low + medians, group + (end - group) / 2);
+        medians += 1;
+        group = end;
+    }
+    let mid = low + medians / 2;
+    select(arr, low, low + medians, mid, true);
+    mid
+}
+
+fn insertion_sort<T, F>(arr: &mut Counter<T, F>, low: usize, high: usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for i in (low + 1)..high {
+        let mut j = i;
+        while j > low && arr.less(j, j - 1) {
+            arr.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+/// Heap sorts [0, len).
+fn heap_sort<T, F>(arr: &mut Counter<T, F>, len: usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    // Build a max heap.
+    for i in (0..len / 2).rev() {
+        sift_down(arr, i, len);
+    }
+    // Move the biggest element to the end and fix the heap.
+    for end in (1..len).rev() {
+        arr.swap(0, end);
+        sift_down(arr, 0, end);
+    }
+}
+
+fn sift_down<T, F>(arr: &mut Counter<T, F>, mut node: usize, len: usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    loop {
+        let mut child = 2 * node + 1;
+        if child >= len {
+            return;
+        }
+        if child + 1 < len && arr.less(child, child + 1) {
+            child += 1;
+        }
+        if !arr.less(node, child) {
+            return;
+        }
+        arr.swap(node, child);
+        node = child;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{partial_sort, select_nth, select_nth_by};
+
+    fn pseudo_random(len: usize) -> Vec<u64> {
+        let mut x = 0x2545_f491_4f6c_dd1d_u64;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x % 1000
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select_nth() {
+        let arr = pseudo_random(1000);
+        let mut sorted = arr.clone();
+        sorted.sort();
+        for n in [0, 1, 250, 499, 500, 998, 999] {
+            let mut arr = arr.clone();
+            select_nth(&mut arr, n);
+            assert_eq!(arr[n], sorted[n]);
+            assert!(arr[..n].iter().all(|e| *e <= arr[n]));
+            assert!(arr[n + 1..].iter().all(|e| *e >= arr[n]));
+        }
+
+        let mut equal = vec![7; 1000];
+        let stats = select_nth(&mut equal, 500);
+        assert!(stats.comparisons < 5000);
+
+        let mut single = [1];
+        select_nth_by(&mut single, 0, |a: &i32, b| a.cmp(b));
+        assert_eq!(single, [1]);
+    }
+
+    #[test]
+    fn test_select_nth_linear() {
+        // An organ pipe input is a bad case for the median of three pivot.
+        for len in [1_000, 10_000, 100_000] {
+            let mut arr: Vec<usize> = (0..len / 2).chain((0..len / 2).rev()).collect();
+            let stats = select_nth(&mut arr, len / 2);
+            assert!(stats.comparisons < 40 * len);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_select_nth_out_of_bounds() {
+        let mut arr = [1, 2, 3];
+        select_nth(&mut arr, 3);
+    }
+
+    #[test]
+    fn test_partial_sort() {
+        let arr = pseudo_random(500);
+        let mut sorted = arr.clone();
+        sorted.sort();
+        for k in [0, 1, 10, 499, 500, 600] {
+            let mut arr = arr.clone();
+            partial_sort(&mut arr, k);
+            let k = k.min(arr.len());
+            assert_eq!(arr[..k], sorted[..k]);
+        }
+    }
+}
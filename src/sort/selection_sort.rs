@@ -0,0 +1,77 @@
+use std::cmp::Ordering;
+
+use super::stats::{partial_cmp, Counter, SortStats};
+
+/// This function takes a mut reference to a slice and reorder it with
+/// selection sort.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::selection_sort;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// selection_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 3, 4, 5]);
+/// ```
+pub fn selection_sort<T: PartialOrd>(arr: &mut [T]) -> SortStats {
+    selection_sort_by(arr, partial_cmp)
+}
+
+/// Same as [`selection_sort`] but orders the elements with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::selection_sort_by;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// selection_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [5, 4, 3, 2, 1]);
+/// ```
+pub fn selection_sort_by<T, F>(arr: &mut [T], compare: F) -> SortStats
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut arr = Counter::new(arr, compare);
+    run(&mut arr);
+    arr.stats()
+}
+
+/// Runs selection sort over a [`Counter`], shared by [`selection_sort_by`] and the recorder.
+pub(super) fn run<T, F>(arr: &mut Counter<T, F>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = arr.len();
+    for i in 0..len {
+        // Find the smallest element of the unsorted part [i, len).
+        let mut min = i;
+        for j in (i + 1)..len {
+            if arr.less(j, min) {
+                min = j;
+            }
+        }
+        // And put it at the end of the sorted part.
+        if min != i {
+            arr.swap(i, min);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::selection_sort;
+
+    #[test]
+    fn test_selection_sort() {
+        let mut arr = [5, 1, 4, 2, 8, 0, 2];
+        selection_sort(&mut arr);
+        assert_eq!(arr, [0, 1, 2, 2, 4, 5, 8]);
+
+        // Selection sort always does N(N-1)/2 comparisons and at most N - 1 swaps.
+        let mut reversed: Vec<i32> = (0..10).rev().collect();
+        let stats = selection_sort(&mut reversed);
+        assert_eq!(reversed, (0..10).collect::<Vec<_>>());
+        assert_eq!(stats.comparisons, 45);
+        assert!(stats.swaps <= 9);
+    }
+}
@@ -0,0 +1,185 @@
+use std::cmp::Ordering;
+
+use super::stats::{partial_cmp, Counter, SortStats};
+
+/// The gap sequences that can be used by [`shell_sort`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapSequence {
+    /// Shell's original sequence: N/2, N/4, ..., 1. **O (N²)** worst case.
+    Shell,
+    /// Knuth's sequence: 1, 4, 13, 40, ... ((3^k - 1) / 2). **O (N^3/2)** worst case.
+    Knuth,
+    /// Ciura's empirically found sequence: 1, 4, 10, 23, 57, 132, 301, 701, 1750,
+    /// extended by multiplying the last gap by 2.25.
+    Ciura,
+    /// Sedgewick's sequence: 1, 8, 23, 77, 281, ... (4^k + 3 * 2^(k-1) + 1).
+    /// **O (N^4/3)** worst case.
+    Sedgewick,
+}
+
+impl GapSequence {
+    /// Returns the gaps smaller than `len` for this sequence, in decreasing order.
+    /// The last gap is always 1 so the final pass is a plain insertion sort.
+    ///
+    /// # Examples
+    ///
+    ///```rust
+    /// # use algo_front_end_masters::sort::GapSequence;
+    /// assert_eq!(GapSequence::Knuth.gaps(100), vec![40, 13, 4, 1]);
+    /// assert_eq!(GapSequence::Shell.gaps(100), vec![50, 25, 12, 6, 3, 1]);
+    /// ```
+    pub fn gaps(self, len: usize) -> Vec<usize> {
+        let mut gaps = Vec::new();
+        match self {
+            GapSequence::Shell => {
+                let mut gap = len / 2;
+                while gap > 0 {
+                    gaps.push(gap);
+                    gap /= 2;
+                }
+                gaps.reverse();
+            }
+            GapSequence::Knuth => {
+                let mut gap = Some(1usize);
+                while let Some(g) = gap.filter(|&g| g < len) {
+                    gaps.push(g);
+                    gap = g.checked_mul(3).and_then(|g| g.checked_add(1));
+                }
+            }
+            GapSequence::Ciura => {
+                const CIURA: [usize; 9] = [1, 4, 10, 23, 57, 132, 301, 701, 1750];
+                gaps.extend(CIURA.iter().copied().take_while(|&gap| gap < len));
+                if gaps.len() == CIURA.len() {
+                    let mut gap = CIURA[CIURA.len() - 1];
+                    // gap * 2.25
+                    while let Some(next) = gap.checked_mul(9).map(|g| g / 4) {
+                        if next >= len {
+                            break;
+                        }
+                        gaps.push(next);
+                        gap = next;
+                    }
+                }
+            }
+            GapSequence::Sedgewick => {
+                if len > 1 {
+                    gaps.push(1);
+                }
+                let mut k = 1;
+                while let Some(gap) = 4usize
+                    .checked_pow(k)
+                    .and_then(|p| p.checked_add(3 * (1 << (k - 1)) + 1))
+                {
+                    if gap >= len {
+                        break;
+                    }
+                    gaps.push(gap);
+                    k += 1;
+                }
+            }
+        }
+        if gaps.is_empty() && len > 1 {
+            gaps.push(1);
+        }
+        gaps.reverse();
+        gaps
+    }
+}
+
+/// This function takes a mut reference to a slice and reorder it with
+/// Shell sort, using the given gap sequence.
+///
+/// Shell sort is an insertion sort done over elements `gap` positions apart,
+/// starting with a big gap and finishing with a gap of 1. Far away elements
+/// move to their place in a few big jumps, so the last pass has little to do.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::{shell_sort, GapSequence};
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// shell_sort(&mut arr, GapSequence::Ciura);
+/// assert_eq!(arr, [1, 2, 3, 4, 5]);
+/// ```
+pub fn shell_sort<T: PartialOrd>(arr: &mut [T], gaps: GapSequence) -> SortStats {
+    shell_sort_by(arr, gaps, partial_cmp)
+}
+
+/// Same as [`shell_sort`] but orders the elements with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::{shell_sort_by, GapSequence};
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// shell_sort_by(&mut arr, GapSequence::Knuth, |a, b| b.cmp(a));
+/// assert_eq!(arr, [5, 4, 3, 2, 1]);
+/// ```
+pub fn shell_sort_by<T, F>(arr: &mut [T], gaps: GapSequence, compare: F) -> SortStats
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut arr = Counter::new(arr, compare);
+    run(&mut arr, gaps);
+    arr.stats()
+}
+
+/// Runs Shell sort over a [`Counter`], shared by [`shell_sort_by`] and the recorder.
+pub(super) fn run<T, F>(arr: &mut Counter<T, F>, gaps: GapSequence)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for gap in gaps.gaps(arr.len()) {
+        // Gapped insertion sort.
+        for i in gap..arr.len() {
+            let mut j = i;
+            while j >= gap && arr.less(j, j - gap) {
+                arr.swap(j, j - gap);
+                j -= gap;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{shell_sort, GapSequence};
+
+    const SEQUENCES: [GapSequence; 4] = [
+        GapSequence::Shell,
+        GapSequence::Knuth,
+        GapSequence::Ciura,
+        GapSequence::Sedgewick,
+    ];
+
+    #[test]
+    fn test_gaps() {
+        assert_eq!(GapSequence::Ciura.gaps(50), vec![23, 10, 4, 1]);
+        assert_eq!(
+            GapSequence::Ciura.gaps(5000),
+            vec![3937, 1750, 701, 301, 132, 57, 23, 10, 4, 1]
+        );
+        assert_eq!(GapSequence::Sedgewick.gaps(300), vec![281, 77, 23, 8, 1]);
+        for seq in SEQUENCES {
+            assert!(seq.gaps(0).is_empty());
+            assert!(seq.gaps(1).is_empty());
+            assert_eq!(seq.gaps(2), vec![1]);
+            assert!(seq.gaps(usize::MAX).windows(2).all(|w| w[0] > w[1]));
+        }
+    }
+
+    #[test]
+    fn test_shell_sort() {
+        for seq in SEQUENCES {
+            let mut arr = [5, 1, 4, 2, 8, 0, 2];
+            shell_sort(&mut arr, seq);
+            assert_eq!(arr, [0, 1, 2, 2, 4, 5, 8]);
+
+            let mut arr: Vec<u64> = (0..2000).map(|i| (i * 7919) % 2003).collect();
+            let mut expected = arr.clone();
+            expected.sort();
+            shell_sort(&mut arr, seq);
+            assert_eq!(arr, expected);
+        }
+    }
+}
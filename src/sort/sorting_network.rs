@@ -0,0 +1,352 @@
+use std::cmp::Ordering;
+
+use super::stats::{partial_cmp, Counter, SortStats};
+
+/// The biggest array length that has a built in sorting network.
+pub const MAX_NETWORK_LEN: usize = 16;
+
+/// Sorts a fixed size array with a sorting network.
+///
+/// A sorting network is a fixed sequence of compare-exchange operations
+/// (comparators) that sorts any input of a given length. The sequence does not
+/// depend on the data, and each compare-exchange is done with selects instead of
+/// branches, so this is a good fit for small arrays sorted in a hot loop.
+///
+/// The networks for N ≤ 12 use the smallest number of comparators possible, and
+/// the ones for 13 to 16 the smallest known.
+///
+/// Only compiles for `N <= 16`.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::network_sort;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// network_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 3, 4, 5]);
+/// ```
+///
+///```compile_fail
+/// # use algo_front_end_masters::sort::network_sort;
+/// let mut arr = [0; 17];
+/// network_sort(&mut arr);
+/// ```
+pub fn network_sort<T: PartialOrd + Copy, const N: usize>(arr: &mut [T; N]) -> SortStats {
+    network_sort_by(arr, partial_cmp)
+}
+
+/// Same as [`network_sort`] but orders the elements with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::network_sort_by;
+/// let mut arr: [i32; 5] = [2, 3, 5, 4, 1];
+/// network_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [5, 4, 3, 2, 1]);
+/// ```
+pub fn network_sort_by<T, F, const N: usize>(arr: &mut [T; N], compare: F) -> SortStats
+where
+    T: Copy,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    const {
+        assert!(
+            N <= MAX_NETWORK_LEN,
+            "there is no sorting network for this length"
+        )
+    };
+    let mut arr = Counter::new(arr, compare);
+    for &(i, j) in network(N).unwrap_or_default() {
+        arr.compare_exchange(i, j);
+    }
+    arr.stats()
+}
+
+/// Runs the sorting network for the length of the slice over a [`Counter`],
+/// with a compare and a swap for each comparator so it works for any `T`. Used
+/// by the recorder.
+///
+/// # Panics
+///
+/// Panics if the slice is longer than 16.
+pub(super) fn run<T, F>(arr: &mut Counter<T, F>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let comparators = network(arr.len()).expect("there is no sorting network for this length");
+    for &(i, j) in comparators {
+        if arr.less(j, i) {
+            arr.swap(i, j);
+        }
+    }
+}
+
+/// Returns the comparators of the built in sorting network for `len` elements,
+/// or `None` if `len > 16`. Each comparator `(i, j)` has `i < j` and puts the
+/// smaller element in `i`.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::network;
+/// assert_eq!(network(3), Some(&[(0, 2), (0, 1), (1, 2)][..]));
+/// assert_eq!(network(16).map(|n| n.len()), Some(60));
+/// ```
+pub fn network(len: usize) -> Option<&'static [(usize, usize)]> {
+    Some(match len {
+        0 | 1 => &[],
+        2 => &NETWORK_2,
+        3 => &NETWORK_3,
+        4 => &NETWORK_4,
+        5 => &NETWORK_5,
+        6 => &NETWORK_6,
+        7 => &NETWORK_7,
+        8 => &NETWORK_8,
+        9 => &NETWORK_9,
+        10 => &NETWORK_10,
+        11 => &NETWORK_11,
+        12 => &NETWORK_12,
+        13 => &NETWORK_13,
+        14 => &NETWORK_14,
+        15 => &NETWORK_15,
+        16 => &NETWORK_16,
+        _ => return None,
+    })
+}
+
+/// Checks if a list of comparators sorts every input of length `len`.
+///
+/// By the 0-1 principle a network sorts every input if and only if it sorts every
+/// input made only of zeros and ones, so it is enough to try the 2<sup>len</sup>
+/// binary inputs. Each input is kept as the bits of an integer.
+///
+/// # Panics
+///
+/// Panics if `len > 30` or if a comparator points outside of `len`.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::verify_network;
+/// assert!(verify_network(3, &[(0, 2), (0, 1), (1, 2)]));
+/// assert!(!verify_network(3, &[(0, 1), (1, 2)]));
+/// ```
+pub fn verify_network(len: usize, comparators: &[(usize, usize)]) -> bool {
+    assert!(len <= 30, "too many inputs to verify");
+    assert!(
+        comparators.iter().all(|&(i, j)| i < len && j < len),
+        "comparator out of bounds"
+    );
+    let all = (1u32 << len) - 1;
+    for input in 0..=all {
+        let mut bits = input;
+        for &(i, j) in comparators {
+            // A 1 at i and a 0 at j is out of order, move the 1 up.
+            if (bits >> i) & 1 == 1 && (bits >> j) & 1 == 0 {
+                bits ^= (1 << i) | (1 << j);
+            }
+        }
+        // Sorted means all the zeros at the bottom and all the ones at the top.
+        let zeros = len as u32 - bits.count_ones();
+        if bits != all & !((1u32 << zeros) - 1) {
+            return false;
+        }
+    }
+    true
+}
+
+// Each line of a network is a layer, the comparators in a layer touch different
+// elements so they could run at the same time.
+#[rustfmt::skip]
+const NETWORK_2: [(usize, usize); 1] = [
+    (0, 1),
+];
+#[rustfmt::skip]
+const NETWORK_3: [(usize, usize); 3] = [
+    (0, 2),
+    (0, 1),
+    (1, 2),
+];
+#[rustfmt::skip]
+const NETWORK_4: [(usize, usize); 5] = [
+    (0, 2), (1, 3),
+    (0, 1), (2, 3),
+    (1, 2),
+];
+#[rustfmt::skip]
+const NETWORK_5: [(usize, usize); 9] = [
+    (0, 3), (1, 4),
+    (0, 2), (1, 3),
+    (0, 1), (2, 4),
+    (1, 2), (3, 4),
+    (2, 3),
+];
+#[rustfmt::skip]
+const NETWORK_6: [(usize, usize); 12] = [
+    (0, 5), (1, 3), (2, 4),
+    (1, 2), (3, 4),
+    (0, 3), (2, 5),
+    (0, 1), (2, 3), (4, 5),
+    (1, 2), (3, 4),
+];
+#[rustfmt::skip]
+const NETWORK_7: [(usize, usize); 16] = [
+    (0, 6), (2, 3), (4, 5),
+    (0, 2), (1, 4), (3, 6),
+    (0, 1), (2, 5), (3, 4),
+    (1, 2), (4, 6),
+    (2, 3), (4, 5),
+    (1, 2), (3, 4), (5, 6),
+];
+#[rustfmt::skip]
+const NETWORK_8: [(usize, usize); 19] = [
+    (0, 2), (1, 3), (4, 6), (5, 7),
+    (0, 4), (1, 5), (2, 6), (3, 7),
+    (0, 1), (2, 3), (4, 5), (6, 7),
+    (2, 4), (3, 5),
+    (1, 4), (3, 6),
+    (1, 2), (3, 4), (5, 6),
+];
+#[rustfmt::skip]
+const NETWORK_9: [(usize, usize); 25] = [
+    (0, 3), (1, 7), (2, 5), (4, 8),
+    (0, 7), (2, 4), (3, 8), (5, 6),
+    (0, 2), (1, 3), (4, 5), (7, 8),
+    (1, 4), (3, 6), (5, 7),
+    (0, 1), (2, 4), (3, 5), (6, 8),
+    (2, 3), (4, 5), (6, 7),
+    (1, 2), (3, 4), (5, 6),
+];
+#[rustfmt::skip]
+const NETWORK_10: [(usize, usize); 29] = [
+    (0, 8), (1, 9), (2, 7), (3, 5), (4, 6),
+    (0, 2), (1, 4), (5, 8), (7, 9),
+    (0, 3), (2, 4), (5, 7), (6, 9),
+    (0, 1), (3, 6), (8, 9),
+    (1, 5), (2, 3), (4, 8), (6, 7),
+    (1, 2), (3, 5), (4, 6), (7, 8),
+    (2, 3), (4, 5), (6, 7),
+    (3, 4), (5, 6),
+];
+#[rustfmt::skip]
+const NETWORK_11: [(usize, usize); 35] = [
+    (0, 9), (1, 6), (2, 4), (3, 7), (5, 8),
+    (0, 1), (3, 5), (4, 10), (6, 9), (7, 8),
+    (1, 3), (2, 5), (4, 7), (8, 10),
+    (0, 4), (1, 2), (3, 7), (5, 9), (6, 8),
+    (0, 1), (2, 6), (4, 5), (7, 8), (9, 10),
+    (2, 4), (3, 6), (5, 7), (8, 9),
+    (1, 2), (3, 4), (5, 6), (7, 8),
+    (2, 3), (4, 5), (6, 7),
+];
+#[rustfmt::skip]
+const NETWORK_12: [(usize, usize); 39] = [
+    (0, 8), (1, 7), (2, 6), (3, 11), (4, 10), (5, 9),
+    (0, 1), (2, 5), (3, 4), (6, 9), (7, 8), (10, 11),
+    (0, 2), (1, 6), (5, 10), (9, 11),
+    (0, 3), (1, 2), (4, 6), (5, 7), (8, 11), (9, 10),
+    (1, 4), (3, 5), (6, 8), (7, 10),
+    (1, 3), (2, 5), (6, 9), (8, 10),
+    (2, 3), (4, 5), (6, 7), (8, 9),
+    (4, 6), (5, 7),
+    (3, 4), (5, 6), (7, 8),
+];
+#[rustfmt::skip]
+const NETWORK_13: [(usize, usize); 45] = [
+    (0, 12), (1, 10), (2, 9), (3, 7), (5, 11), (6, 8),
+    (1, 6), (2, 3), (4, 11), (7, 9), (8, 10),
+    (0, 4), (1, 2), (3, 6), (7, 8), (9, 10), (11, 12),
+    (4, 6), (5, 9), (8, 11), (10, 12),
+    (0, 5), (3, 8), (4, 7), (6, 11), (9, 10),
+    (0, 1), (2, 5), (6, 9), (7, 8), (10, 11),
+    (1, 3), (2, 4), (5, 6), (9, 10),
+    (1, 2), (3, 4), (5, 7), (6, 8),
+    (2, 3), (4, 5), (6, 7), (8, 9),
+    (3, 4), (5, 6),
+];
+#[rustfmt::skip]
+const NETWORK_14: [(usize, usize); 51] = [
+    (0, 13), (1, 12), (2, 6), (3, 4), (5, 9), (7, 8),
+    (0, 7), (1, 2), (4, 11), (6, 12), (8, 13), (9, 10),
+    (0, 1), (2, 3), (4, 6), (5, 7), (8, 9), (10, 11), (12, 13),
+    (2, 8), (3, 9), (4, 5), (6, 7), (10, 12), (11, 13),
+    (1, 10), (2, 4), (3, 5), (6, 8), (7, 9), (11, 12),
+    (0, 4), (3, 6), (5, 8), (7, 11), (9, 12),
+    (0, 2), (1, 4), (7, 10), (9, 11),
+    (1, 3), (4, 6), (5, 7), (8, 10),
+    (1, 2), (3, 4), (5, 6), (7, 8), (9, 10),
+    (4, 5), (6, 7),
+];
+#[rustfmt::skip]
+const NETWORK_15: [(usize, usize); 56] = [
+    (0, 11), (1, 14), (2, 13), (3, 7), (4, 5), (6, 10), (8, 9),
+    (0, 6), (1, 8), (2, 3), (5, 12), (7, 13), (9, 14), (10, 11),
+    (1, 2), (3, 4), (5, 7), (6, 8), (9, 10), (11, 12), (13, 14),
+    (0, 2), (3, 9), (4, 10), (5, 6), (7, 8), (11, 13), (12, 14),
+    (0, 1), (2, 11), (3, 5), (4, 6), (7, 9), (8, 10), (12, 13),
+    (0, 3), (1, 5), (4, 7), (6, 9), (8, 12), (10, 13),
+    (1, 3), (2, 5), (8, 11), (10, 12),
+    (2, 4), (5, 7), (6, 8), (9, 11),
+    (2, 3), (4, 5), (6, 7), (8, 9), (10, 11),
+    (5, 6), (7, 8),
+];
+#[rustfmt::skip]
+const NETWORK_16: [(usize, usize); 60] = [
+    (0, 13), (1, 12), (2, 15), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+    (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (10, 15), (11, 12),
+    (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13), (14, 15),
+    (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14), (13, 15),
+    (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+    (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+    (2, 4), (3, 6), (9, 12), (11, 13),
+    (3, 5), (6, 8), (7, 9), (10, 12),
+    (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+    (6, 7), (8, 9),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{network, network_sort, verify_network, MAX_NETWORK_LEN};
+
+    #[test]
+    fn test_verify_networks() {
+        for len in 0..=MAX_NETWORK_LEN {
+            assert!(verify_network(len, network(len).unwrap()), "network {len}");
+        }
+        assert_eq!(network(MAX_NETWORK_LEN + 1), None);
+        // The smallest number of comparators known for each length.
+        let sizes: Vec<usize> = (2..=MAX_NETWORK_LEN)
+            .map(|len| network(len).unwrap().len())
+            .collect();
+        assert_eq!(
+            sizes,
+            [1, 3, 5, 9, 12, 16, 19, 25, 29, 35, 39, 45, 51, 56, 60]
+        );
+        // Dropping the last comparator breaks any of them.
+        for len in 2..=MAX_NETWORK_LEN {
+            let comparators = network(len).unwrap();
+            assert!(!verify_network(len, &comparators[..comparators.len() - 1]));
+        }
+    }
+
+    #[test]
+    fn test_network_sort() {
+        let mut arr = [
+            9.5, -1.0, 3.25, 0.0, 7.0, 2.0, 2.0, 8.0, -4.5, 1.0, 6.0, 5.0, 11.0, 10.0, 4.0, 3.0,
+        ];
+        network_sort(&mut arr);
+        assert_eq!(
+            arr,
+            [-4.5, -1.0, 0.0, 1.0, 2.0, 2.0, 3.0, 3.25, 4.0, 5.0, 6.0, 7.0, 8.0, 9.5, 10.0, 11.0]
+        );
+
+        let mut arr = [3, 1, 2];
+        let stats = network_sort(&mut arr);
+        assert_eq!(arr, [1, 2, 3]);
+        assert_eq!(stats.comparisons, 3);
+
+        let mut empty: [u8; 0] = [];
+        network_sort(&mut empty);
+    }
+}
@@ -0,0 +1,200 @@
+use std::cmp::Ordering;
+
+/// Operation counters reported by every sort in this module.
+///
+/// `comparisons` is the number of times the comparator was called, `swaps`
+/// is the number of times two elements exchanged their positions and `writes`
+/// is the number of times an element was moved to a position on its own.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::{bubble_sort, SortStats};
+/// let mut arr = [1, 2, 3, 4, 5];
+/// let stats = bubble_sort(&mut arr);
+/// assert_eq!(stats, SortStats { comparisons: 4, swaps: 0, writes: 0 });
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SortStats {
+    pub comparisons: usize,
+    pub swaps: usize,
+    pub writes: usize,
+}
+
+/// An operation done by a sort over the slice, as captured by [`record`](super::record).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortEvent {
+    /// The elements at positions `a` and `b` were compared.
+    Compare { a: usize, b: usize },
+    /// The elements at positions `a` and `b` exchanged places.
+    Swap { a: usize, b: usize },
+    /// The element that was at position `element` in the input was written to
+    /// `index`.
+    Write { index: usize, element: usize },
+}
+
+/// Wraps a slice and a comparator so every compare and swap done by a sort
+/// goes through one place and gets counted.
+///
+/// When recording, it also keeps the list of events and, for each position,
+/// the input position of the element that is in it.
+pub(crate) struct Counter<'a, T, F> {
+    arr: &'a mut [T],
+    compare: F,
+    stats: SortStats,
+    events: Option<Vec<SortEvent>>,
+    ids: Vec<usize>,
+}
+
+impl<'a, T, F> Counter<'a, T, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    pub(crate) fn new(arr: &'a mut [T], compare: F) -> Self {
+        Counter {
+            arr,
+            compare,
+            stats: SortStats::default(),
+            events: None,
+            ids: Vec::new(),
+        }
+    }
+
+    pub(crate) fn recording(arr: &'a mut [T], compare: F) -> Self {
+        let ids = (0..arr.len()).collect();
+        Counter {
+            events: Some(Vec::new()),
+            ids,
+            ..Counter::new(arr, compare)
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.arr.len()
+    }
+
+    /// Returns true if the element at `i` should be placed before the element at `j`.
+    pub(crate) fn less(&mut self, i: usize, j: usize) -> bool {
+        self.stats.comparisons += 1;
+        if let Some(events) = &mut self.events {
+            events.push(SortEvent::Compare { a: i, b: j });
+        }
+        (self.compare)(&self.arr[i], &self.arr[j]) == Ordering::Less
+    }
+
+    pub(crate) fn swap(&mut self, i: usize, j: usize) {
+        self.stats.swaps += 1;
+        if let Some(events) = &mut self.events {
+            events.push(SortEvent::Swap { a: i, b: j });
+            self.ids.swap(i, j);
+        }
+        self.arr.swap(i, j);
+    }
+
+    /// Moves the element at `high` to `low`, shifting everything in [low, high)
+    /// one position to the right.
+    pub(crate) fn rotate_right(&mut self, low: usize, high: usize) {
+        self.stats.writes += high - low + 1;
+        if let Some(events) = &mut self.events {
+            let moved = self.ids[high];
+            for index in (low + 1..=high).rev() {
+                self.ids[index] = self.ids[index - 1];
+                events.push(SortEvent::Write {
+                    index,
+                    element: self.ids[index],
+                });
+            }
+            self.ids[low] = moved;
+            events.push(SortEvent::Write {
+                index: low,
+                element: moved,
+            });
+        }
+        self.arr[low..=high].rotate_right(1);
+    }
+
+    pub(crate) fn stats(&self) -> SortStats {
+        self.stats
+    }
+
+    /// Returns the recorded events, and the comparator and the input position of
+    /// each element so the final order can be checked.
+    pub(crate) fn into_recording(self) -> (Vec<SortEvent>, Vec<usize>, F) {
+        (self.events.unwrap_or_default(), self.ids, self.compare)
+    }
+}
+
+impl<'a, T, F> Counter<'a, T, F>
+where
+    T: Copy,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    /// Puts the smaller of the elements at `i` and `j` in `i` and the bigger in `j`.
+    ///
+    /// Both slots are always written with a select instead of a branch, so the
+    /// compiler can emit conditional moves.
+    pub(crate) fn compare_exchange(&mut self, i: usize, j: usize) {
+        let (a, b) = (self.arr[i], self.arr[j]);
+        let swap = (self.compare)(&b, &a) == Ordering::Less;
+        self.stats.comparisons += 1;
+        self.stats.swaps += swap as usize;
+        self.arr[i] = if swap { b } else { a };
+        self.arr[j] = if swap { a } else { b };
+    }
+}
+
+/// The comparator used by the non `_by` sorts, following the same
+/// `partial_cmp().unwrap()` convention as the rest of the crate.
+pub(crate) fn partial_cmp<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Counter, SortEvent};
+
+    #[test]
+    fn test_counter() {
+        let mut arr = [2, 1];
+        let mut counter = Counter::new(&mut arr, |a: &i32, b: &i32| a.cmp(b));
+        assert!(counter.less(1, 0));
+        assert!(!counter.less(0, 1));
+        counter.swap(0, 1);
+        let stats = counter.stats();
+        assert_eq!(stats.comparisons, 2);
+        assert_eq!(stats.swaps, 1);
+        assert_eq!(arr, [1, 2]);
+    }
+
+    #[test]
+    fn test_counter_recording() {
+        let mut arr = [3, 1, 2];
+        let mut counter = Counter::recording(&mut arr, |a: &i32, b: &i32| a.cmp(b));
+        counter.less(0, 1);
+        counter.swap(0, 1);
+        counter.rotate_right(0, 2);
+        assert_eq!(counter.stats().writes, 3);
+        let (events, ids, _) = counter.into_recording();
+        assert_eq!(
+            events,
+            [
+                SortEvent::Compare { a: 0, b: 1 },
+                SortEvent::Swap { a: 0, b: 1 },
+                SortEvent::Write {
+                    index: 2,
+                    element: 0
+                },
+                SortEvent::Write {
+                    index: 1,
+                    element: 1
+                },
+                SortEvent::Write {
+                    index: 0,
+                    element: 2
+                },
+            ]
+        );
+        assert_eq!(ids, [2, 1, 0]);
+        assert_eq!(arr, [2, 1, 3]);
+    }
+}
@@ -0,0 +1,284 @@
+use std::cmp::Ordering;
+
+/// Compares two strings in natural order, where runs of digits are compared by
+/// their numeric value, so "file2" comes before "file10".
+///
+/// Numbers of any length are supported, as they are compared digit by digit.
+/// When two strings only differ in leading zeros ("a01" and "a1") the one with
+/// less zeros comes first, and any other tie is broken by the plain string
+/// order, so it is a total order. Works with any `_by` sort of this module.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::{insertion_sort_by, natural_cmp};
+/// let mut files = ["file10.txt", "file2.txt", "file1.txt"];
+/// insertion_sort_by(&mut files, natural_cmp);
+/// assert_eq!(files, ["file1.txt", "file2.txt", "file10.txt"]);
+/// ```
+pub fn natural_cmp<S: AsRef<str> + ?Sized>(a: &S, b: &S) -> Ordering {
+    natural(a.as_ref(), b.as_ref(), false)
+}
+
+/// Same as [`natural_cmp`] but ignores the case of the letters, ties are broken
+/// by the case sensitive order.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::{shell_sort_by, natural_cmp_ignore_case, GapSequence};
+/// let mut files = vec!["b10", "B9", "a1"];
+/// shell_sort_by(&mut files, GapSequence::Ciura, natural_cmp_ignore_case);
+/// assert_eq!(files, ["a1", "B9", "b10"]);
+/// ```
+pub fn natural_cmp_ignore_case<S: AsRef<str> + ?Sized>(a: &S, b: &S) -> Ordering {
+    natural(a.as_ref(), b.as_ref(), true)
+}
+
+/// Compares two strings ignoring the case of the letters, ties are broken by the
+/// case sensitive order.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::{bubble_sort_by, case_insensitive_cmp};
+/// let mut words = vec![String::from("banana"), String::from("Apple"), String::from("cherry")];
+/// bubble_sort_by(&mut words, case_insensitive_cmp);
+/// assert_eq!(words, ["Apple", "banana", "cherry"]);
+/// ```
+pub fn case_insensitive_cmp<S: AsRef<str> + ?Sized>(a: &S, b: &S) -> Ordering {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    lowercase(a).cmp(lowercase(b)).then_with(|| a.cmp(b))
+}
+
+/// Compares two version strings made of dot separated numbers, like "1.2.10".
+///
+/// It follows the semver precedence rules:
+/// - An optional `v` prefix is ignored, and so is build metadata after a `+`.
+/// - Each component is compared by its numeric value, and missing components
+///   count as 0, so "1.2" and "1.2.0" are equal.
+/// - A version with a pre-release after a `-` ("1.0.0-alpha") comes before the
+///   same version without it. Pre-release parts are compared one by one, numbers
+///   by their value and other parts in natural order, with numbers first.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::{insertion_sort_by, version_cmp};
+/// let mut versions = ["1.10.0", "1.2.0", "1.0.0-rc.1", "1.0.0", "1.0.0-beta"];
+/// insertion_sort_by(&mut versions, version_cmp);
+/// assert_eq!(versions, ["1.0.0-beta", "1.0.0-rc.1", "1.0.0", "1.2.0", "1.10.0"]);
+/// ```
+pub fn version_cmp<S: AsRef<str> + ?Sized>(a: &S, b: &S) -> Ordering {
+    let (a_core, a_pre) = split_version(a.as_ref());
+    let (b_core, b_pre) = split_version(b.as_ref());
+    let mut a_parts = a_core.split('.');
+    let mut b_parts = b_core.split('.');
+    loop {
+        let ordering = match (a_parts.next(), b_parts.next()) {
+            (None, None) => break,
+            // A missing component counts as 0.
+            (a, b) => component_cmp(a.unwrap_or("0"), b.unwrap_or("0")),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        // A pre-release comes before the release.
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(a), Some(b)) => {
+            let mut a_parts = a.split('.');
+            let mut b_parts = b.split('.');
+            loop {
+                let ordering = match (a_parts.next(), b_parts.next()) {
+                    (None, None) => return Ordering::Equal,
+                    // More identifiers come after.
+                    (Some(_), None) => return Ordering::Greater,
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(a), Some(b)) => pre_release_cmp(a, b),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Splits a version in its dotted core and its pre-release, dropping the `v`
+/// prefix and the build metadata.
+fn split_version(version: &str) -> (&str, Option<&str>) {
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    let version = version.split('+').next().unwrap_or_default();
+    match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    }
+}
+
+fn component_cmp(a: &str, b: &str) -> Ordering {
+    if is_number(a) && is_number(b) {
+        digits_cmp(a, b)
+    } else {
+        natural_cmp(a, b)
+    }
+}
+
+fn pre_release_cmp(a: &str, b: &str) -> Ordering {
+    match (is_number(a), is_number(b)) {
+        (true, true) => digits_cmp(a, b),
+        // Numeric identifiers come before alphanumeric ones.
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => natural_cmp(a, b),
+    }
+}
+
+fn natural(a: &str, b: &str, ignore_case: bool) -> Ordering {
+    let mut a_chunks = Chunks { rest: a };
+    let mut b_chunks = Chunks { rest: b };
+    // Compared only if everything else is equal.
+    let mut zeros = Ordering::Equal;
+    loop {
+        let ordering = match (a_chunks.next(), b_chunks.next()) {
+            (None, None) => break,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(Chunk::Number(a)), Some(Chunk::Number(b))) => {
+                let ordering = digits_cmp(a, b);
+                if ordering == Ordering::Equal && zeros == Ordering::Equal {
+                    zeros = a.len().cmp(&b.len());
+                }
+                ordering
+            }
+            (Some(a), Some(b)) => {
+                let (a, b) = (a.as_str(), b.as_str());
+                if ignore_case {
+                    lowercase(a).cmp(lowercase(b))
+                } else {
+                    a.cmp(b)
+                }
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    zeros.then_with(|| a.cmp(b))
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Compares two strings of ascii digits by their numeric value.
+fn digits_cmp(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+    // Without leading zeros the longer number is the bigger one.
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+fn lowercase(s: &str) -> impl Iterator<Item = char> + '_ {
+    s.chars().flat_map(char::to_lowercase)
+}
+
+enum Chunk<'a> {
+    Number(&'a str),
+    Text(&'a str),
+}
+
+impl<'a> Chunk<'a> {
+    fn as_str(&self) -> &'a str {
+        match self {
+            Chunk::Number(s) | Chunk::Text(s) => s,
+        }
+    }
+}
+
+/// Splits a string in runs of ascii digits and runs of everything else.
+struct Chunks<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = Chunk<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.rest.bytes().next()?;
+        let is_digit = first.is_ascii_digit();
+        let end = self
+            .rest
+            .bytes()
+            .position(|b| b.is_ascii_digit() != is_digit)
+            .unwrap_or(self.rest.len());
+        // Ascii digits are single bytes, so 'end' is always a char boundary.
+        let (chunk, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(if is_digit {
+            Chunk::Number(chunk)
+        } else {
+            Chunk::Text(chunk)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{case_insensitive_cmp, natural_cmp, natural_cmp_ignore_case, version_cmp};
+    use crate::sort::{comb_sort_by, top_k_by};
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+        assert_eq!(natural_cmp("file10", "file10"), Ordering::Equal);
+        assert_eq!(natural_cmp("a1b2", "a1b10"), Ordering::Less);
+        assert_eq!(natural_cmp("a1", "a01"), Ordering::Less);
+        assert_eq!(natural_cmp("a01", "a1"), Ordering::Greater);
+        assert_eq!(natural_cmp("x", "x1"), Ordering::Less);
+        assert_eq!(natural_cmp("1", "a"), Ordering::Less);
+        assert_eq!(natural_cmp("B", "a"), Ordering::Less);
+        // Bigger than any integer type.
+        assert_eq!(
+            natural_cmp("n99999999999999999999999", "n100000000000000000000000"),
+            Ordering::Less
+        );
+        assert_eq!(natural_cmp("ü2", "ü10"), Ordering::Less);
+
+        let mut files = vec!["img12.png", "img10.png", "IMG2.png", "img1.png"];
+        comb_sort_by(&mut files, natural_cmp_ignore_case);
+        assert_eq!(files, ["img1.png", "IMG2.png", "img10.png", "img12.png"]);
+    }
+
+    #[test]
+    fn test_case_insensitive_cmp() {
+        assert_eq!(case_insensitive_cmp("abc", "ABD"), Ordering::Less);
+        assert_eq!(case_insensitive_cmp("ÉCOLE", "école"), Ordering::Less);
+        assert_eq!(case_insensitive_cmp("a", "a"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_version_cmp() {
+        assert_eq!(version_cmp("1.2.9", "1.2.10"), Ordering::Less);
+        assert_eq!(version_cmp("1.2", "1.2.0"), Ordering::Equal);
+        assert_eq!(version_cmp("v2.0.0", "1.99.99"), Ordering::Greater);
+        assert_eq!(version_cmp("1.0.0+build.5", "1.0.0"), Ordering::Equal);
+        assert_eq!(version_cmp("1.0.0-alpha", "1.0.0-alpha.1"), Ordering::Less);
+        assert_eq!(
+            version_cmp("1.0.0-alpha.1", "1.0.0-alpha.beta"),
+            Ordering::Less
+        );
+        assert_eq!(version_cmp("1.0.0-beta.2", "1.0.0-beta.11"), Ordering::Less);
+        assert_eq!(version_cmp("1.0.0-rc.1", "1.0.0"), Ordering::Less);
+        assert_eq!(version_cmp("1.2.0", "1.10"), Ordering::Less);
+
+        let newest = top_k_by(["0.9", "1.0.1", "1.0.0", "0.10"], 2, |a, b| {
+            version_cmp(b, a)
+        });
+        assert_eq!(newest, ["1.0.1", "1.0.0"]);
+    }
+}
@@ -0,0 +1,123 @@
+use std::cmp::Ordering;
+
+use super::stats::partial_cmp;
+
+/// Consumes an iterator and returns its `k` smallest items in sorted order.
+///
+/// Only `k` items are kept in memory at any time, in a max heap, so it can be
+/// used over streams that do not fit in memory. It runs in **O (N log K)**.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::top_k;
+/// let smallest = top_k([9, 1, 8, 2, 7, 3, 6, 4, 5], 3);
+/// assert_eq!(smallest, vec![1, 2, 3]);
+/// ```
+pub fn top_k<T, I>(iter: I, k: usize) -> Vec<T>
+where
+    T: PartialOrd,
+    I: IntoIterator<Item = T>,
+{
+    top_k_by(iter, k, partial_cmp)
+}
+
+/// Same as [`top_k`] but orders the items with a comparator function.
+///
+/// # Examples
+///
+///```rust
+/// # use algo_front_end_masters::sort::top_k_by;
+/// let biggest = top_k_by(0..1000, 3, |a, b| b.cmp(a));
+/// assert_eq!(biggest, vec![999, 998, 997]);
+/// ```
+pub fn top_k_by<T, I, F>(iter: I, k: usize, mut compare: F) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if k == 0 {
+        return Vec::new();
+    }
+    let iter = iter.into_iter();
+    // 'k' can be much bigger than the stream, so it only reserves what it knows
+    // will be kept.
+    let mut heap: Vec<T> = Vec::with_capacity(k.min(iter.size_hint().0));
+    for item in iter {
+        if heap.len() < k {
+            heap.push(item);
+            sift_up(&mut heap, &mut compare);
+        } else if compare(&item, &heap[0]) == Ordering::Less {
+            // The new item is smaller than the biggest one we kept, replace it.
+            heap[0] = item;
+            sift_down(&mut heap, 0, k, &mut compare);
+        }
+    }
+    // Heap sort what is left.
+    for end in (1..heap.len()).rev() {
+        heap.swap(0, end);
+        sift_down(&mut heap, 0, end, &mut compare);
+    }
+    heap
+}
+
+fn sift_up<T, F>(heap: &mut [T], compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut node = heap.len() - 1;
+    while node > 0 {
+        let parent = (node - 1) / 2;
+        if compare(&heap[parent], &heap[node]) != Ordering::Less {
+            return;
+        }
+        heap.swap(parent, node);
+        node = parent;
+    }
+}
+
+fn sift_down<T, F>(heap: &mut [T], mut node: usize, len: usize, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    loop {
+        let mut child = 2 * node + 1;
+        if child >= len {
+            return;
+        }
+        if child + 1 < len && compare(&heap[child], &heap[child + 1]) == Ordering::Less {
+            child += 1;
+        }
+        if compare(&heap[node], &heap[child]) != Ordering::Less {
+            return;
+        }
+        heap.swap(node, child);
+        node = child;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{top_k, top_k_by};
+
+    #[test]
+    fn test_top_k() {
+        let items: Vec<u32> = (0..1000).map(|i| (i * 7919) % 1009).collect();
+        let mut sorted = items.clone();
+        sorted.sort();
+        for k in [0, 1, 5, 999, 1000, 2000] {
+            let expected = &sorted[..k.min(sorted.len())];
+            assert_eq!(top_k(items.iter().copied(), k), expected);
+        }
+
+        // A 'k' bigger than the stream keeps all of it.
+        assert_eq!(top_k(vec![3, 1, 2], 10), [1, 2, 3]);
+        assert_eq!(top_k(vec![3, 1, 2], usize::MAX), [1, 2, 3]);
+        let streamed = top_k((0..5).rev().filter(|i| i % 2 == 0), usize::MAX);
+        assert_eq!(streamed, [0, 2, 4]);
+
+        let words = ["pear", "fig", "banana", "apple"];
+        let longest = top_k_by(words, 2, |a, b| b.len().cmp(&a.len()));
+        assert_eq!(longest, vec!["banana", "apple"]);
+    }
+}